@@ -0,0 +1,272 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyticsConfig {
+    pub damping: f64,
+    pub max_iterations: usize,
+    pub tolerance: f64,
+    pub max_label_iterations: usize,
+}
+
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        AnalyticsConfig {
+            damping: 0.85,
+            max_iterations: 100,
+            tolerance: 1e-6,
+            max_label_iterations: 20,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphAnalytics {
+    pub degree_centrality: HashMap<Uuid, f64>,
+    pub pagerank: HashMap<Uuid, f64>,
+    pub components: Vec<Vec<Uuid>>,
+    pub communities: Vec<Vec<Uuid>>,
+    pub isolated: Vec<Uuid>,
+}
+
+impl GraphAnalytics {
+    pub fn top_hubs(&self, count: usize) -> Vec<(Uuid, f64)> {
+        let mut hubs: Vec<(Uuid, f64)> = self.pagerank.iter().map(|(id, r)| (*id, *r)).collect();
+        hubs.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.0.cmp(&b.0))
+        });
+        hubs.truncate(count);
+        hubs
+    }
+
+    pub fn hub_prior(&self, weight: f64) -> HashMap<Uuid, f64> {
+        let max_rank = self.pagerank.values().cloned().fold(0.0, f64::max);
+        if max_rank <= 0.0 {
+            return HashMap::new();
+        }
+
+        self.pagerank
+            .iter()
+            .map(|(id, rank)| (*id, weight * rank / max_rank))
+            .collect()
+    }
+
+    pub fn community_of(&self, id: Uuid) -> Option<usize> {
+        self.communities
+            .iter()
+            .position(|community| community.contains(&id))
+    }
+}
+
+pub fn analyze_graph(memory: &MemoryGraph) -> GraphAnalytics {
+    analyze_graph_with(memory, &AnalyticsConfig::default())
+}
+
+pub fn analyze_graph_with(memory: &MemoryGraph, config: &AnalyticsConfig) -> GraphAnalytics {
+    let components = connected_components(memory);
+    let isolated = components
+        .iter()
+        .filter(|c| c.len() == 1)
+        .map(|c| c[0])
+        .collect();
+
+    GraphAnalytics {
+        degree_centrality: degree_centrality(memory),
+        pagerank: pagerank(memory, config),
+        components,
+        communities: label_propagation(memory, config.max_label_iterations),
+        isolated,
+    }
+}
+
+fn sorted_nodes(memory: &MemoryGraph) -> Vec<Uuid> {
+    let mut nodes: Vec<Uuid> = memory.fragments.keys().copied().collect();
+    nodes.sort();
+    nodes
+}
+
+fn live_edges(memory: &MemoryGraph) -> impl Iterator<Item = &Edge> {
+    memory.edges.values().filter(|e| {
+        e.from_fragment != e.to_fragment
+            && memory.fragments.contains_key(&e.from_fragment)
+            && memory.fragments.contains_key(&e.to_fragment)
+    })
+}
+
+fn undirected_neighbors(memory: &MemoryGraph) -> HashMap<Uuid, BTreeMap<Uuid, f64>> {
+    let mut neighbors: HashMap<Uuid, BTreeMap<Uuid, f64>> = memory
+        .fragments
+        .keys()
+        .map(|id| (*id, BTreeMap::new()))
+        .collect();
+
+    for edge in live_edges(memory) {
+        for (a, b) in [
+            (edge.from_fragment, edge.to_fragment),
+            (edge.to_fragment, edge.from_fragment),
+        ] {
+            if let Some(adjacent) = neighbors.get_mut(&a) {
+                *adjacent.entry(b).or_insert(0.0) += edge.strength;
+            }
+        }
+    }
+
+    neighbors
+}
+
+pub fn degree_centrality(memory: &MemoryGraph) -> HashMap<Uuid, f64> {
+    let neighbors = undirected_neighbors(memory);
+    let denominator = (memory.fragments.len().saturating_sub(1)).max(1) as f64;
+
+    neighbors
+        .iter()
+        .map(|(id, adjacent)| (*id, adjacent.len() as f64 / denominator))
+        .collect()
+}
+
+pub fn pagerank(memory: &MemoryGraph, config: &AnalyticsConfig) -> HashMap<Uuid, f64> {
+    let nodes = sorted_nodes(memory);
+    let n = nodes.len();
+    if n == 0 {
+        return HashMap::new();
+    }
+
+    let mut out_weight: HashMap<Uuid, f64> = HashMap::new();
+    let mut incoming: HashMap<Uuid, Vec<(Uuid, f64)>> = HashMap::new();
+    for edge in live_edges(memory) {
+        *out_weight.entry(edge.from_fragment).or_insert(0.0) += edge.strength;
+        incoming
+            .entry(edge.to_fragment)
+            .or_default()
+            .push((edge.from_fragment, edge.strength));
+    }
+
+    let uniform = 1.0 / n as f64;
+    let mut ranks: HashMap<Uuid, f64> = nodes.iter().map(|id| (*id, uniform)).collect();
+
+    for _ in 0..config.max_iterations {
+        let dangling: f64 = nodes
+            .iter()
+            .filter(|id| out_weight.get(id).copied().unwrap_or(0.0) <= 0.0)
+            .map(|id| ranks[id])
+            .sum();
+        let base = (1.0 - config.damping) * uniform + config.damping * dangling * uniform;
+
+        let mut next = HashMap::with_capacity(n);
+        for id in &nodes {
+            let inflow: f64 = incoming
+                .get(id)
+                .map(|sources| {
+                    sources
+                        .iter()
+                        .map(|(source, weight)| ranks[source] * weight / out_weight[source])
+                        .sum()
+                })
+                .unwrap_or(0.0);
+            next.insert(*id, base + config.damping * inflow);
+        }
+
+        let delta: f64 = nodes.iter().map(|id| (next[id] - ranks[id]).abs()).sum();
+        ranks = next;
+        if delta < config.tolerance {
+            break;
+        }
+    }
+
+    ranks
+}
+
+pub fn connected_components(memory: &MemoryGraph) -> Vec<Vec<Uuid>> {
+    let neighbors = undirected_neighbors(memory);
+    let mut visited = HashSet::new();
+    let mut components = Vec::new();
+
+    for start in sorted_nodes(memory) {
+        if !visited.insert(start) {
+            continue;
+        }
+
+        let mut component = Vec::new();
+        let mut stack = vec![start];
+        while let Some(current) = stack.pop() {
+            component.push(current);
+            for next in neighbors[&current].keys() {
+                if visited.insert(*next) {
+                    stack.push(*next);
+                }
+            }
+        }
+
+        component.sort();
+        components.push(component);
+    }
+
+    sort_groups(&mut components);
+    components
+}
+
+pub fn label_propagation(memory: &MemoryGraph, max_iterations: usize) -> Vec<Vec<Uuid>> {
+    let neighbors = undirected_neighbors(memory);
+    let nodes = sorted_nodes(memory);
+    let mut labels: HashMap<Uuid, Uuid> = nodes.iter().map(|id| (*id, *id)).collect();
+
+    for _ in 0..max_iterations {
+        let mut changed = false;
+
+        for id in &nodes {
+            let mut votes: BTreeMap<Uuid, f64> = BTreeMap::new();
+            for (neighbor, weight) in &neighbors[id] {
+                *votes.entry(labels[neighbor]).or_insert(0.0) += weight;
+            }
+
+            let best =
+                votes
+                    .iter()
+                    .fold(None, |best: Option<(Uuid, f64)>, (label, w)| match best {
+                        Some((_, best_w)) if best_w >= *w => best,
+                        _ => Some((*label, *w)),
+                    });
+
+            if let Some((label, _)) = best {
+                if labels[id] != label {
+                    labels.insert(*id, label);
+                    changed = true;
+                }
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let mut grouped: BTreeMap<Uuid, Vec<Uuid>> = BTreeMap::new();
+    for id in &nodes {
+        grouped.entry(labels[id]).or_default().push(*id);
+    }
+
+    let mut communities: Vec<Vec<Uuid>> = grouped.into_values().collect();
+    sort_groups(&mut communities);
+    communities
+}
+
+fn sort_groups(groups: &mut [Vec<Uuid>]) {
+    groups.sort_by(|a, b| b.len().cmp(&a.len()).then(a.first().cmp(&b.first())));
+}
+
+impl MemoryGraph {
+    pub fn apply_hub_prior(&mut self, analytics: &GraphAnalytics, weight: f64) {
+        self.activation_priors = analytics.hub_prior(weight);
+        self.touch();
+    }
+
+    pub fn clear_activation_priors(&mut self) {
+        self.activation_priors.clear();
+        self.touch();
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::context::RELATION_FIELDS;
+use crate::memory::content_fields;
+use crate::normalize::{fold_case, normalize_term};
+use crate::types::*;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+impl MemoryGraph {
+    pub fn matches_pattern(&self, id: Uuid, pattern: &str) -> bool {
+        let fragment = match self.fragments.get(&id) {
+            Some(fragment) => fragment,
+            None => return false,
+        };
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return false;
+        }
+        if pattern.eq_ignore_ascii_case(&id.to_string()) {
+            return true;
+        }
+
+        let fields = content_fields(&fragment.content);
+        if let Some((key, value)) = pattern.split_once('=') {
+            let key = fold_case(key.trim());
+            if key == "domain" {
+                return self.fragment_domains(id).contains(&fold_case(value.trim()));
+            }
+            let value = normalize_term(value.trim());
+            return fields
+                .iter()
+                .any(|(name, field)| fold_case(name) == key && normalize_term(field) == value);
+        }
+
+        if self.fragment_domains(id).contains(&fold_case(pattern)) {
+            return true;
+        }
+        let term = normalize_term(pattern);
+        if fields
+            .iter()
+            .any(|(_, field)| normalize_term(field) == term)
+        {
+            return true;
+        }
+
+        let pattern_terms = self.tokenizer.terms(pattern);
+        if pattern_terms.is_empty() {
+            return false;
+        }
+        let fragment_terms: Vec<String> = self
+            .tokenizer
+            .fragment_tokens(fragment)
+            .into_iter()
+            .map(|token| token.term)
+            .collect();
+        fragment_terms
+            .windows(pattern_terms.len())
+            .any(|window| window == pattern_terms.as_slice())
+    }
+
+    pub fn matches_any(&self, id: Uuid, patterns: &HashSet<String>) -> bool {
+        patterns
+            .iter()
+            .any(|pattern| self.matches_pattern(id, pattern))
+    }
+
+    pub fn is_excluded(&self, id: Uuid, context: &ContextVector) -> bool {
+        self.matches_any(id, &context.attention_window.exclusion_patterns)
+            || self.matches_any(id, &context.environmental_constraints.must_exclude)
+    }
+
+    pub fn required_fragments(&self, context: &ContextVector) -> Vec<Uuid> {
+        let must_include = &context.environmental_constraints.must_include;
+        if must_include.is_empty() {
+            return Vec::new();
+        }
+
+        let mut required: Vec<Uuid> = self
+            .fragments
+            .keys()
+            .filter(|id| self.matches_any(**id, must_include) && !self.is_excluded(**id, context))
+            .copied()
+            .collect();
+        required.sort();
+        required
+    }
+
+    pub fn focus_boost(&self, id: Uuid, context: &ContextVector) -> f64 {
+        let window = &context.attention_window;
+        let fragment = match self.fragments.get(&id) {
+            Some(fragment) => fragment,
+            None => return 0.0,
+        };
+        let mut boost = 0.0;
+
+        if !window.focus_entities.is_empty() {
+            let entities: HashSet<String> = window
+                .focus_entities
+                .iter()
+                .map(|entity| normalize_term(entity))
+                .collect();
+            let mentioned = self
+                .tokenizer
+                .fragment_tokens(fragment)
+                .into_iter()
+                .any(|token| entities.contains(&token.term));
+            if mentioned {
+                boost += self.attention.focus_entity_boost;
+            }
+        }
+
+        if !window.focus_relations.is_empty() {
+            let relations: HashSet<String> = window
+                .focus_relations
+                .iter()
+                .map(|relation| normalize_term(relation))
+                .collect();
+            let related = content_fields(&fragment.content)
+                .iter()
+                .any(|(name, value)| {
+                    RELATION_FIELDS.contains(&name.as_str())
+                        && relations.contains(&normalize_term(value))
+                });
+            if related {
+                boost += self.attention.focus_relation_boost;
+            }
+        }
+
+        if !window.focus_domains.is_empty() {
+            let focused = self
+                .fragment_domains(id)
+                .iter()
+                .any(|domain| window.focus_domains.iter().any(|d| fold_case(d) == *domain));
+            if focused {
+                boost += self.attention.focus_domain_boost;
+            }
+        }
+
+        boost
+    }
+}
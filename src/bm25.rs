@@ -0,0 +1,145 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::embedding::fragment_text;
+use crate::types::*;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+impl Bm25Index {
+    pub fn new(config: Bm25Config) -> Self {
+        Bm25Index {
+            config,
+            ..Bm25Index::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.doc_lengths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.doc_lengths.is_empty()
+    }
+
+    pub fn insert(&mut self, id: Uuid, text: &str, tokenizer: &Tokenizer) {
+        self.insert_terms(id, &tokenizer.terms(text));
+    }
+
+    pub fn insert_terms(&mut self, id: Uuid, terms: &[String]) {
+        self.remove(id);
+
+        let mut frequencies: HashMap<String, u32> = HashMap::new();
+        for term in terms {
+            *frequencies.entry(term.clone()).or_insert(0) += 1;
+        }
+
+        for (term, frequency) in frequencies {
+            self.postings.entry(term).or_default().insert(id, frequency);
+        }
+        self.doc_lengths.insert(id, terms.len());
+        self.total_length += terms.len();
+    }
+
+    pub fn remove(&mut self, id: Uuid) {
+        let length = match self.doc_lengths.remove(&id) {
+            Some(length) => length,
+            None => return,
+        };
+        self.total_length = self.total_length.saturating_sub(length);
+        self.postings.retain(|_, documents| {
+            documents.remove(&id);
+            !documents.is_empty()
+        });
+    }
+
+    pub fn containing_all(&self, terms: &[String]) -> HashSet<Uuid> {
+        let mut found: Option<HashSet<Uuid>> = None;
+        for term in terms {
+            let documents: HashSet<Uuid> = self
+                .postings
+                .get(term)
+                .map(|documents| documents.keys().copied().collect())
+                .unwrap_or_default();
+            found = Some(match found {
+                Some(found) => found.intersection(&documents).copied().collect(),
+                None => documents,
+            });
+        }
+        found.unwrap_or_default()
+    }
+
+    pub fn document_frequency(&self, term: &str) -> usize {
+        self.postings.get(term).map(|d| d.len()).unwrap_or(0)
+    }
+
+    pub fn idf(&self, term: &str) -> f64 {
+        let n = self.doc_lengths.len() as f64;
+        let df = self.document_frequency(term) as f64;
+        ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+    }
+
+    pub fn score_terms(&self, terms: &[String]) -> HashMap<Uuid, f64> {
+        let mut scores: HashMap<Uuid, f64> = HashMap::new();
+        if !self.config.enabled || self.doc_lengths.is_empty() {
+            return scores;
+        }
+
+        let average_length =
+            (self.total_length as f64 / self.doc_lengths.len() as f64).max(f64::EPSILON);
+        let unique: HashSet<&String> = terms.iter().collect();
+
+        for term in unique {
+            let documents = match self.postings.get(term) {
+                Some(documents) => documents,
+                None => continue,
+            };
+            let idf = self.idf(term);
+
+            for (id, frequency) in documents {
+                let tf = *frequency as f64;
+                let length = self.doc_lengths.get(id).copied().unwrap_or(0) as f64;
+                let norm = self.config.k1
+                    * (1.0 - self.config.b + self.config.b * length / average_length);
+                *scores.entry(*id).or_insert(0.0) +=
+                    idf * tf * (self.config.k1 + 1.0) / (tf + norm);
+            }
+        }
+
+        scores
+    }
+
+    pub fn search(&self, text: &str, tokenizer: &Tokenizer) -> Vec<(Uuid, f64)> {
+        let mut ranked: Vec<(Uuid, f64)> = self
+            .score_terms(&tokenizer.terms(text))
+            .into_iter()
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.0.cmp(&b.0))
+        });
+        ranked.truncate(self.config.max_candidates);
+        ranked
+    }
+
+    pub fn clear(&mut self) {
+        self.postings.clear();
+        self.doc_lengths.clear();
+        self.total_length = 0;
+    }
+}
+
+impl MemoryGraph {
+    pub fn bm25_search(&self, text: &str) -> Vec<(Uuid, f64)> {
+        self.text_index.search(text, &self.tokenizer)
+    }
+
+    pub fn rebuild_text_index(&mut self) {
+        self.text_index.clear();
+        let mut ids: Vec<Uuid> = self.fragments.keys().copied().collect();
+        ids.sort();
+        for id in ids {
+            let terms = self.tokenizer.terms(&fragment_text(&self.fragments[&id]));
+            self.text_index.insert_terms(id, &terms);
+        }
+    }
+}
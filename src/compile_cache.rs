@@ -0,0 +1,217 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::compiler::compile_thought;
+use crate::explain::compiled_fragments;
+use crate::types::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static REVISIONS: AtomicU64 = AtomicU64::new(1);
+
+pub(crate) fn next_revision() -> u64 {
+    REVISIONS.fetch_add(1, Ordering::Relaxed)
+}
+
+impl MemoryGraph {
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    pub fn touch(&mut self) {
+        self.revision = next_revision();
+    }
+}
+
+fn sorted<'a>(items: impl IntoIterator<Item = &'a String>) -> Vec<&'a String> {
+    let mut items: Vec<&String> = items.into_iter().collect();
+    items.sort();
+    items
+}
+
+#[derive(Default)]
+struct KeyWriter(Vec<u8>);
+
+impl Hasher for KeyWriter {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.0.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+pub fn context_fingerprint(context: &ContextVector) -> u64 {
+    context_key(context).0
+}
+
+fn context_key(context: &ContextVector) -> (u64, Vec<u8>) {
+    let mut writer = KeyWriter::default();
+    write_context(context, &mut writer);
+    (writer.finish(), writer.0)
+}
+
+fn write_context(context: &ContextVector, hasher: &mut KeyWriter) {
+    let goal = &context.goal;
+    goal.description.hash(hasher);
+    format!("{:?}", goal.goal_type).hash(hasher);
+    let mut parameters: Vec<(&String, &String)> = goal.parameters.iter().collect();
+    parameters.sort();
+    parameters.hash(hasher);
+    goal.priority.to_bits().hash(hasher);
+
+    let window = &context.attention_window;
+    for set in [
+        &window.focus_entities,
+        &window.focus_domains,
+        &window.focus_relations,
+        &window.exclusion_patterns,
+    ] {
+        sorted(set).hash(hasher);
+    }
+
+    let bias = &context.emotional_bias;
+    for value in [
+        bias.frustration,
+        bias.curiosity,
+        bias.confidence,
+        bias.urgency,
+        bias.satisfaction,
+    ] {
+        value.to_bits().hash(hasher);
+    }
+
+    let constraints = &context.environmental_constraints;
+    sorted(&constraints.must_include).hash(hasher);
+    sorted(&constraints.must_exclude).hash(hasher);
+    let mut limits: Vec<(&String, u64)> = constraints
+        .resource_limits
+        .iter()
+        .map(|(name, limit)| (name, limit.to_bits()))
+        .collect();
+    limits.sort();
+    limits.hash(hasher);
+
+    context.recent_activations.hash(hasher);
+    let mut primes: Vec<(uuid::Uuid, u64)> = context
+        .activation_primes
+        .iter()
+        .map(|(id, weight)| (*id, weight.to_bits()))
+        .collect();
+    primes.sort();
+    primes.hash(hasher);
+
+    context.time_pressure.to_bits().hash(hasher);
+    context.domain_hint.domain.hash(hasher);
+    context.domain_hint.subdomain.hash(hasher);
+    sorted(&context.domain_hint.tags).hash(hasher);
+    context.confidence_threshold.to_bits().hash(hasher);
+    context.max_fragments.hash(hasher);
+}
+
+impl CompilationCache {
+    pub fn new(capacity: usize) -> Self {
+        CompilationCache {
+            capacity,
+            ..CompilationCache::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    fn mark_used(&mut self, key: u64) {
+        self.recency.retain(|k| *k != key);
+        self.recency.push_back(key);
+    }
+
+    pub fn get(&mut self, context: &ContextVector, memory: &MemoryGraph) -> Option<EEG> {
+        self.lookup(context, memory).map(|entry| entry.eeg)
+    }
+
+    fn lookup(&mut self, context: &ContextVector, memory: &MemoryGraph) -> Option<CachedEEG> {
+        let (hash, key) = context_key(context);
+        let stale = match self.entries.get(&hash) {
+            Some(entry) if entry.key == key && entry.revision == memory.revision() => {
+                let entry = entry.clone();
+                self.stats.hits += 1;
+                self.mark_used(hash);
+                return Some(entry);
+            }
+            Some(entry) => entry.key == key,
+            None => false,
+        };
+        if stale {
+            self.entries.remove(&hash);
+            self.recency.retain(|k| *k != hash);
+            self.stats.invalidations += 1;
+        }
+        self.stats.misses += 1;
+        None
+    }
+
+    pub fn insert(&mut self, context: &ContextVector, memory: &MemoryGraph, eeg: EEG) {
+        if self.capacity == 0 {
+            return;
+        }
+        let (hash, key) = context_key(context);
+        self.entries.insert(
+            hash,
+            CachedEEG {
+                key,
+                revision: memory.revision(),
+                activated: compiled_fragments(&eeg),
+                eeg,
+            },
+        );
+        self.mark_used(hash);
+        while self.entries.len() > self.capacity {
+            match self.recency.pop_front() {
+                Some(oldest) => {
+                    self.entries.remove(&oldest);
+                    self.stats.evictions += 1;
+                }
+                None => break,
+            }
+        }
+    }
+
+    pub fn get_or_compile(
+        &mut self,
+        context: &ContextVector,
+        memory: &mut MemoryGraph,
+        compile: impl FnOnce(&ContextVector, &mut MemoryGraph) -> EEG,
+    ) -> EEG {
+        if let Some(entry) = self.lookup(context, memory) {
+            memory.record_activation(&entry.activated);
+            return entry.eeg;
+        }
+        let eeg = compile(context, memory);
+        self.insert(context, memory, eeg.clone());
+        eeg
+    }
+}
+
+pub fn compile_thought_cached(
+    context: &ContextVector,
+    memory: &mut MemoryGraph,
+    cache: &mut CompilationCache,
+) -> EEG {
+    cache.get_or_compile(context, memory, compile_thought)
+}
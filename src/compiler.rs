@@ -1,5 +1,8 @@
 // Copyright (c) 2026 Nolan Taft
+use crate::conflicts::*;
 use crate::fossilization::*;
+use crate::normalize::fold_case;
+use crate::passes::*;
 use crate::types::*;
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
@@ -12,6 +15,31 @@ pub fn compile_thought_with_modules(
     context: &ContextVector,
     memory: &mut crate::types::MemoryGraph,
     compiled_modules: Option<&[CompiledModule]>,
+) -> EEG {
+    compile_thought_with_resolver(context, memory, compiled_modules, &ConfidenceResolver)
+}
+
+pub fn compile_thought_with_resolver(
+    context: &ContextVector,
+    memory: &mut crate::types::MemoryGraph,
+    compiled_modules: Option<&[CompiledModule]>,
+    resolver: &dyn ConflictResolver,
+) -> EEG {
+    compile_thought_with_cost_model(
+        context,
+        memory,
+        compiled_modules,
+        resolver,
+        &CostModel::default(),
+    )
+}
+
+pub fn compile_thought_with_cost_model(
+    context: &ContextVector,
+    memory: &mut crate::types::MemoryGraph,
+    compiled_modules: Option<&[CompiledModule]>,
+    resolver: &dyn ConflictResolver,
+    cost_model: &CostModel,
 ) -> EEG {
     if let Some(modules) = compiled_modules {
         if let Some(module) = find_applicable_module(context, modules) {
@@ -19,18 +47,7 @@ pub fn compile_thought_with_modules(
         }
     }
 
-    let activated = memory.activate_fragments(context);
-
-    if activated.is_empty() {
-        return create_empty_eeg(&context);
-    }
-
-    let resolved = resolve_conflicts(&activated, memory);
-    let filled = fill_gaps(&resolved, memory, context);
-    let ordered = order_fragments(&filled, memory);
-    let branched = add_branching(&ordered, memory, context);
-    let pruned = prune_resources(&branched, context);
-    construct_eeg(&pruned, context, memory)
+    PassManager::standard(Box::new(resolver), cost_model.clone()).compile(context, memory)
 }
 
 fn create_eeg_from_module(module: &CompiledModule, _context: &ContextVector) -> EEG {
@@ -66,169 +83,495 @@ fn create_eeg_from_module(module: &CompiledModule, _context: &ContextVector) ->
     }
 }
 
-fn resolve_conflicts(activated: &HashSet<Uuid>, memory: &crate::types::MemoryGraph) -> Vec<Uuid> {
+pub(crate) fn resolve_conflicts(
+    nodes: &[IRNode],
+    memory: &crate::types::MemoryGraph,
+    required: &HashSet<Uuid>,
+    resolver: &dyn ConflictResolver,
+) -> Vec<IRNode> {
+    let mut ids: Vec<Uuid> = nodes
+        .iter()
+        .filter(|node| node.node_type == NodeType::FragmentNode)
+        .map(|node| node.id)
+        .filter(|id| memory.fragments.contains_key(id))
+        .collect();
+    ids.sort();
+
+    let mut conflicts: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    let mut dropped = HashSet::new();
+    for group in detect_conflicts(&ids, memory) {
+        let mut candidates: Vec<&MFragment> = group
+            .iter()
+            .filter(|id| required.contains(id))
+            .map(|id| &memory.fragments[id])
+            .collect();
+        if candidates.is_empty() {
+            candidates = group.iter().map(|id| &memory.fragments[id]).collect();
+        }
+        if let Some(selected) = resolver.select(&candidates, memory) {
+            dropped.extend(
+                group
+                    .iter()
+                    .copied()
+                    .filter(|id| *id != selected && !required.contains(id)),
+            );
+            conflicts.insert(selected, group);
+        }
+    }
+
     let mut resolved = Vec::new();
-    let mut conflicts = Vec::new();
-
-    let fragment_list: Vec<Uuid> = activated.iter().copied().collect();
-
-    for i in 0..fragment_list.len() {
-        let frag1_id = fragment_list[i];
-        if let Some(frag1) = memory.fragments.get(&frag1_id) {
-            for j in (i + 1)..fragment_list.len() {
-                let frag2_id = fragment_list[j];
-                if let Some(frag2) = memory.fragments.get(&frag2_id) {
-                    if check_conflict(frag1, frag2) {
-                        conflicts.push((frag1_id, frag2_id));
-                    }
+    for node in nodes {
+        if dropped.contains(&node.id) {
+            continue;
+        }
+        if let Some(group) = conflicts.remove(&node.id) {
+            resolved.push(IRNode {
+                id: Uuid::new_v4(),
+                node_type: NodeType::ConflictNode,
+                order: resolved.len(),
+                confidence: node.confidence,
+                execution_cost: 1.0,
+                content: Some(NodeContent::Conflict {
+                    conflicting_fragments: group,
+                    selected_fragment: Some(node.id),
+                }),
+            });
+        }
+        resolved.push(IRNode {
+            order: resolved.len(),
+            ..node.clone()
+        });
+    }
+
+    resolved
+}
+
+pub(crate) fn fill_gaps(
+    resolved: &[IRNode],
+    activated: &HashSet<Uuid>,
+    memory: &crate::types::MemoryGraph,
+    context: &ContextVector,
+) -> Vec<IRNode> {
+    let mut nodes = resolved.to_vec();
+    let mut present: Vec<Uuid> = nodes
+        .iter()
+        .filter(|node| node.node_type == NodeType::FragmentNode)
+        .map(|node| node.id)
+        .collect();
+    let mut skip = activated.clone();
+    let mut neighbors = None;
+
+    for requirement in GoalRequirement::from_context(context) {
+        if present.iter().any(|&id| memory.satisfies(id, &requirement)) {
+            continue;
+        }
+
+        let neighbors = neighbors.get_or_insert_with(|| memory.bridge_neighbors());
+        match memory.bridge_gap_with(&requirement, &present, &skip, context, neighbors) {
+            Some(path) => {
+                for id in path {
+                    let fragment = &memory.fragments[&id];
+                    nodes.push(IRNode {
+                        id,
+                        node_type: NodeType::FragmentNode,
+                        order: nodes.len(),
+                        confidence: fragment.confidence,
+                        execution_cost: 1.0,
+                        content: None,
+                    });
+                    present.push(id);
+                    skip.insert(id);
                 }
             }
-
-            resolved.push(frag1_id);
+            None => nodes.push(IRNode {
+                id: Uuid::new_v4(),
+                node_type: NodeType::GapFillNode,
+                order: nodes.len(),
+                confidence: 0.3,
+                execution_cost: 1.0,
+                content: Some(NodeContent::GapFill {
+                    gap_description: format!("No memory provides {}", requirement.describe()),
+                    estimated_confidence: 0.3,
+                }),
+            }),
         }
     }
 
-    resolved
+    nodes
 }
 
-fn check_conflict(frag1: &MFragment, frag2: &MFragment) -> bool {
-    match (&frag1.content, &frag2.content) {
-        (
-            FragmentContent::EntityRelation {
-                entity: e1,
-                relation: r1,
-                target: t1,
-            },
-            FragmentContent::EntityRelation {
-                entity: e2,
-                relation: r2,
-                target: t2,
-            },
-        ) => e1 == e2 && r1 == r2 && t1 != t2,
-        (
-            FragmentContent::CausalRule {
-                condition: c1,
-                outcome: o1,
-                ..
-            },
-            FragmentContent::CausalRule {
-                condition: c2,
-                outcome: o2,
-                ..
-            },
-        ) => c1 == c2 && o1 != o2,
-        _ => false,
+fn dependencies(
+    nodes: &[IRNode],
+    memory: &crate::types::MemoryGraph,
+) -> Vec<(usize, usize, EdgeType, f64)> {
+    let position: HashMap<Uuid, usize> = nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.node_type == NodeType::FragmentNode)
+        .map(|(i, node)| (node.id, i))
+        .collect();
+
+    let mut links: Vec<(usize, usize, EdgeType, f64)> = memory
+        .edges
+        .iter()
+        .filter(|(_, edge)| matches!(edge.edge_type, EdgeType::Causal | EdgeType::Temporal))
+        .filter_map(|((from, to), edge)| {
+            let (&from, &to) = (position.get(from)?, position.get(to)?);
+            (from != to).then(|| (from, to, edge.edge_type.clone(), edge.strength))
+        })
+        .collect();
+
+    for (i, node) in nodes.iter().enumerate() {
+        if let Some(NodeContent::Conflict {
+            selected_fragment: Some(selected),
+            ..
+        }) = &node.content
+        {
+            if let Some(&to) = position.get(selected) {
+                let prerequisites: Vec<(usize, usize, EdgeType, f64)> = links
+                    .iter()
+                    .filter(|link| link.1 == to)
+                    .map(|link| (link.0, i, link.2.clone(), link.3))
+                    .collect();
+                links.extend(prerequisites);
+                links.push((i, to, EdgeType::Contextual, 1.0));
+            }
+        }
     }
+
+    links.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+    links
 }
 
-fn fill_gaps(
-    resolved: &[Uuid],
+pub(crate) fn order_fragments(
+    ordered_nodes: &[IRNode],
     memory: &crate::types::MemoryGraph,
-    _context: &ContextVector,
-) -> Vec<OrderedNode> {
-    let mut nodes = Vec::new();
-
-    for &frag_id in resolved {
-        if let Some(fragment) = memory.fragments.get(&frag_id) {
-            nodes.push(OrderedNode {
-                id: frag_id,
-                node_type: NodeType::FragmentNode,
-                order: nodes.len(),
-                confidence: fragment.confidence,
+) -> Vec<IRNode> {
+    let confidence: Vec<f64> = ordered_nodes
+        .iter()
+        .map(|node| {
+            memory
+                .fragments
+                .get(&node.id)
+                .map(|f| f.confidence)
+                .unwrap_or(node.confidence)
+        })
+        .collect();
+    let mut links = dependencies(ordered_nodes, memory);
+    let mut placed = vec![false; ordered_nodes.len()];
+    let mut result = Vec::with_capacity(ordered_nodes.len());
+
+    while result.len() < ordered_nodes.len() {
+        let ready = (0..ordered_nodes.len())
+            .filter(|&i| !placed[i] && !links.iter().any(|link| link.1 == i && !placed[link.0]))
+            .max_by(|&a, &b| {
+                confidence[a]
+                    .partial_cmp(&confidence[b])
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(b.cmp(&a))
             });
+
+        match ready {
+            Some(i) => {
+                placed[i] = true;
+                result.push(ordered_nodes[i].clone());
+            }
+            None => {
+                let weakest = links
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, link)| !placed[link.0] && !placed[link.1])
+                    .min_by(|(_, a), (_, b)| {
+                        a.3.partial_cmp(&b.3)
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                            .then(ordered_nodes[a.0].id.cmp(&ordered_nodes[b.0].id))
+                            .then(ordered_nodes[a.1].id.cmp(&ordered_nodes[b.1].id))
+                    })
+                    .map(|(i, _)| i);
+                match weakest {
+                    Some(i) => {
+                        links.remove(i);
+                    }
+                    None => break,
+                }
+            }
         }
     }
 
-    if nodes.len() < 3 {
-        let gap_id = Uuid::new_v4();
-        nodes.push(OrderedNode {
-            id: gap_id,
-            node_type: NodeType::GapFillNode,
-            order: nodes.len(),
-            confidence: 0.5,
-        });
+    for (i, node) in result.iter_mut().enumerate() {
+        node.order = i;
     }
 
-    nodes
+    result
 }
 
-#[derive(Debug, Clone)]
-struct OrderedNode {
-    id: Uuid,
-    node_type: NodeType,
-    order: usize,
-    confidence: f64,
+fn causal_condition(memory: &crate::types::MemoryGraph, id: Uuid) -> Option<&str> {
+    match &memory.fragments.get(&id)?.content {
+        FragmentContent::CausalRule { condition, .. } => Some(condition),
+        _ => None,
+    }
 }
 
-fn order_fragments(
-    ordered_nodes: &[OrderedNode],
+fn alternative_outcomes(
     memory: &crate::types::MemoryGraph,
-) -> Vec<OrderedNode> {
-    let mut result = ordered_nodes.to_vec();
-
-    result.sort_by(|a, b| {
-        let a_conf = memory
-            .fragments
-            .get(&a.id)
-            .map(|f| f.confidence)
-            .unwrap_or(a.confidence);
-        let b_conf = memory
-            .fragments
-            .get(&b.id)
-            .map(|f| f.confidence)
-            .unwrap_or(b.confidence);
-        b_conf
-            .partial_cmp(&a_conf)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
-
-    for (i, node) in result.iter_mut().enumerate() {
-        node.order = i;
+    condition: &str,
+    context: &ContextVector,
+    present: &HashSet<Uuid>,
+) -> Vec<Uuid> {
+    let key = fold_case(condition.trim());
+    let terms = memory.tokenizer.terms(condition);
+    if terms.is_empty() {
+        return Vec::new();
     }
 
-    result
+    let mut alternatives: Vec<Uuid> = memory
+        .text_index
+        .containing_all(&terms)
+        .into_iter()
+        .filter(|&id| {
+            causal_condition(memory, id).is_some_and(|c| fold_case(c.trim()) == key)
+                && !memory.is_excluded(id, context)
+                && (present.contains(&id)
+                    || memory.fragments[&id].confidence >= context.confidence_threshold)
+        })
+        .collect();
+    alternatives.sort();
+    alternatives
+}
+
+fn branch_weight(fragment: &MFragment) -> f64 {
+    let successes = fragment.reinforcement_count as f64;
+    fragment.confidence * (successes + 1.0) / (successes + 2.0)
 }
 
-fn add_branching(
-    ordered: &[OrderedNode],
+pub(crate) fn add_branching(
+    ordered: &[IRNode],
     memory: &crate::types::MemoryGraph,
-    _context: &ContextVector,
-) -> Vec<OrderedNode> {
-    let mut branched = Vec::new();
+    context: &ContextVector,
+) -> Vec<IRNode> {
+    let mut groups: HashMap<Uuid, (String, Vec<Uuid>)> = HashMap::new();
+    let mut grouped = HashSet::new();
+    let mut seen_conditions = HashSet::new();
+    let present: HashSet<Uuid> = ordered.iter().map(|node| node.id).collect();
 
     for node in ordered {
-        branched.push(node.clone());
-
-        if let NodeType::FragmentNode = node.node_type {
-            if let Some(fragment) = memory.fragments.get(&node.id) {
-                if let FragmentContent::CausalRule { .. } = &fragment.content {
-                    let decision_id = Uuid::new_v4();
-                    branched.push(OrderedNode {
-                        id: decision_id,
-                        node_type: NodeType::DecisionNode,
-                        order: branched.len(),
-                        confidence: 0.8,
-                    });
-                }
+        if node.node_type != NodeType::FragmentNode || grouped.contains(&node.id) {
+            continue;
+        }
+        let condition = match causal_condition(memory, node.id) {
+            Some(condition) => condition,
+            None => continue,
+        };
+        if !seen_conditions.insert(fold_case(condition.trim())) {
+            continue;
+        }
+        let alternatives = alternative_outcomes(memory, condition, context, &present);
+        if alternatives.len() >= 2 {
+            grouped.extend(alternatives.iter().copied());
+            groups.insert(node.id, (condition.to_string(), alternatives));
+        }
+    }
+
+    let mut branched = Vec::new();
+    for node in ordered {
+        if let Some((condition, alternatives)) = groups.remove(&node.id) {
+            let mut weighted: Vec<(&MFragment, f64)> = alternatives
+                .iter()
+                .map(|id| &memory.fragments[id])
+                .map(|fragment| (fragment, branch_weight(fragment)))
+                .collect();
+            weighted.sort_by(|a, b| {
+                b.1.partial_cmp(&a.1)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(a.0.id.cmp(&b.0.id))
+            });
+            let total: f64 = weighted.iter().map(|(_, weight)| weight).sum();
+
+            let branches = weighted
+                .iter()
+                .map(|(fragment, weight)| Branch {
+                    condition: match &fragment.content {
+                        FragmentContent::CausalRule { outcome, .. } => outcome.clone(),
+                        _ => String::new(),
+                    },
+                    target_node: fragment.id,
+                    weight: if total > 0.0 { weight / total } else { 0.0 },
+                })
+                .collect();
+            branched.push(IRNode {
+                id: Uuid::new_v4(),
+                node_type: NodeType::DecisionNode,
+                order: branched.len(),
+                confidence: weighted
+                    .iter()
+                    .map(|(fragment, _)| fragment.confidence)
+                    .fold(0.0, f64::max),
+                execution_cost: 1.0,
+                content: Some(NodeContent::Decision {
+                    condition,
+                    branches,
+                }),
+            });
+
+            for (fragment, _) in weighted {
+                let existing = ordered.iter().find(|n| n.id == fragment.id);
+                branched.push(IRNode {
+                    order: branched.len(),
+                    ..existing.cloned().unwrap_or(IRNode {
+                        id: fragment.id,
+                        node_type: NodeType::FragmentNode,
+                        order: 0,
+                        confidence: fragment.confidence,
+                        execution_cost: 1.0,
+                        content: None,
+                    })
+                });
             }
+        } else if !grouped.contains(&node.id) {
+            branched.push(IRNode {
+                order: branched.len(),
+                ..node.clone()
+            });
         }
     }
 
     branched
 }
 
-fn prune_resources(branched: &[OrderedNode], context: &ContextVector) -> Vec<OrderedNode> {
-    let threshold = context.confidence_threshold + (context.time_pressure * 0.2);
+pub(crate) fn prune_resources(
+    branched: &[IRNode],
+    context: &ContextVector,
+    memory: &crate::types::MemoryGraph,
+    required: &HashSet<Uuid>,
+    cost_model: &CostModel,
+) -> Vec<IRNode> {
+    let price = context.confidence_threshold + (context.time_pressure * 0.2);
+    let limits = &context.environmental_constraints.resource_limits;
+    let max_nodes = limits.get("max_nodes").copied().unwrap_or(f64::INFINITY);
+    let max_cost = limits.get("max_cost").copied().unwrap_or(f64::INFINITY);
+
+    let mut nodes = branched.to_vec();
+    for node in nodes.iter_mut() {
+        node.execution_cost = cost_model.node_cost(&node.node_type, memory.fragments.get(&node.id));
+    }
+    let value: Vec<f64> = nodes
+        .iter()
+        .map(|node| cost_model.expected_value(node.confidence, node.execution_cost, price))
+        .collect();
 
-    branched
+    let position: HashMap<Uuid, usize> = nodes
         .iter()
-        .filter(|node| node.confidence >= threshold)
-        .cloned()
-        .collect()
+        .enumerate()
+        .map(|(i, node)| (node.id, i))
+        .collect();
+    let mut prerequisites: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    for (from, to, _, _) in dependencies(&nodes, memory) {
+        prerequisites[to].push(from);
+    }
+    for (i, node) in nodes.iter().enumerate() {
+        if let Some(NodeContent::Decision { branches, .. }) = &node.content {
+            for branch in branches {
+                if let Some(&target) = position.get(&branch.target_node) {
+                    prerequisites[target].push(i);
+                }
+            }
+        }
+    }
+
+    let closure = |start: usize, selected: &[bool]| -> Vec<usize> {
+        let mut members = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![start];
+        while let Some(i) = stack.pop() {
+            if selected[i] || !visited.insert(i) {
+                continue;
+            }
+            members.push(i);
+            stack.extend(prerequisites[i].iter().copied());
+        }
+        members
+    };
+
+    let mut selected = vec![false; nodes.len()];
+    let mut node_count = 0.0;
+    let mut total_cost = 0.0;
+    for i in (0..nodes.len()).filter(|&i| required.contains(&nodes[i].id)) {
+        for member in closure(i, &selected) {
+            selected[member] = true;
+            node_count += 1.0;
+            total_cost += nodes[member].execution_cost;
+        }
+    }
+
+    loop {
+        let best = (0..nodes.len())
+            .filter(|&i| !selected[i])
+            .filter_map(|i| {
+                let members = closure(i, &selected);
+                let gain: f64 = members.iter().map(|&m| value[m]).sum();
+                let cost: f64 = members.iter().map(|&m| nodes[m].execution_cost).sum();
+                let density = if cost > 0.0 {
+                    gain / cost
+                } else {
+                    f64::INFINITY
+                };
+                (gain >= 0.0
+                    && node_count + members.len() as f64 <= max_nodes
+                    && total_cost + cost <= max_cost)
+                    .then_some((i, members, gain, cost, density))
+            })
+            .max_by(|a, b| {
+                a.4.partial_cmp(&b.4)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal))
+                    .then(b.0.cmp(&a.0))
+            });
+
+        match best {
+            Some((_, members, _, cost, _)) => {
+                node_count += members.len() as f64;
+                total_cost += cost;
+                for member in members {
+                    selected[member] = true;
+                }
+            }
+            None => break,
+        }
+    }
+
+    let mut kept: Vec<IRNode> = nodes
+        .into_iter()
+        .zip(selected)
+        .filter_map(|(node, keep)| keep.then_some(node))
+        .collect();
+
+    let kept_ids: HashSet<Uuid> = kept.iter().map(|node| node.id).collect();
+    kept.retain_mut(|node| match &mut node.content {
+        Some(NodeContent::Conflict {
+            selected_fragment: Some(selected),
+            ..
+        }) => kept_ids.contains(selected),
+        Some(NodeContent::Decision { branches, .. }) => {
+            branches.retain(|branch| kept_ids.contains(&branch.target_node));
+            let total: f64 = branches.iter().map(|branch| branch.weight).sum();
+            if total > 0.0 {
+                for branch in branches.iter_mut() {
+                    branch.weight /= total;
+                }
+            }
+            branches.len() >= 2
+        }
+        _ => true,
+    });
+
+    for (i, node) in kept.iter_mut().enumerate() {
+        node.order = i;
+    }
+    kept
 }
 
-fn construct_eeg(
-    pruned: &[OrderedNode],
+pub(crate) fn construct_eeg(
+    pruned: &[IRNode],
     context: &ContextVector,
     memory: &crate::types::MemoryGraph,
 ) -> EEG {
@@ -236,7 +579,7 @@ fn construct_eeg(
     let mut edges = Vec::new();
 
     if pruned.is_empty() {
-        return create_empty_eeg(&context);
+        return create_empty_eeg(context);
     }
 
     let entry_point = pruned[0].id;
@@ -255,7 +598,7 @@ fn construct_eeg(
                         },
                         confidence: fragment.confidence,
                         source_fragments: vec![node.id],
-                        execution_cost: 1.0,
+                        execution_cost: node.execution_cost,
                     }
                 } else {
                     continue;
@@ -264,46 +607,127 @@ fn construct_eeg(
             NodeType::GapFillNode => EEGNode {
                 id: node.id,
                 node_type: NodeType::GapFillNode,
-                content: NodeContent::GapFill {
+                content: node.content.clone().unwrap_or(NodeContent::GapFill {
                     gap_description: "Missing link".to_string(),
-                    estimated_confidence: 0.5,
-                },
-                confidence: 0.5,
+                    estimated_confidence: node.confidence,
+                }),
+                confidence: node.confidence,
                 source_fragments: Vec::new(),
-                execution_cost: 1.0,
+                execution_cost: node.execution_cost,
             },
             NodeType::DecisionNode => EEGNode {
                 id: node.id,
                 node_type: NodeType::DecisionNode,
-                content: NodeContent::Decision {
+                content: node.content.clone().unwrap_or(NodeContent::Decision {
                     condition: "check_condition".to_string(),
                     branches: Vec::new(),
+                }),
+                confidence: node.confidence,
+                source_fragments: match &node.content {
+                    Some(NodeContent::Decision { branches, .. }) => {
+                        branches.iter().map(|branch| branch.target_node).collect()
+                    }
+                    _ => Vec::new(),
                 },
-                confidence: 0.8,
-                source_fragments: Vec::new(),
-                execution_cost: 1.0,
+                execution_cost: node.execution_cost,
+            },
+            NodeType::ConflictNode => match &node.content {
+                Some(
+                    content @ NodeContent::Conflict {
+                        conflicting_fragments,
+                        ..
+                    },
+                ) => EEGNode {
+                    id: node.id,
+                    node_type: NodeType::ConflictNode,
+                    content: content.clone(),
+                    confidence: node.confidence,
+                    source_fragments: conflicting_fragments.clone(),
+                    execution_cost: node.execution_cost,
+                },
+                _ => continue,
             },
             _ => continue,
         };
 
         nodes.insert(node.id, eeg_node);
+    }
 
-        if node.order == pruned.len() - 1 {
-            exit_points.push(node.id);
+    let position: HashMap<Uuid, usize> = pruned
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.id, i))
+        .collect();
+    let mut next: Vec<Option<usize>> = (0..pruned.len())
+        .map(|i| Some(i + 1).filter(|&j| j < pruned.len()))
+        .collect();
+    let mut siblings = HashSet::new();
+    let mut branch_edges = Vec::new();
+
+    for (i, node) in pruned.iter().enumerate() {
+        if let Some(NodeContent::Decision { branches, .. }) = &node.content {
+            let targets: Vec<usize> = branches
+                .iter()
+                .filter_map(|branch| position.get(&branch.target_node).copied())
+                .collect();
+            let join = targets
+                .iter()
+                .max()
+                .map(|&last| last + 1)
+                .filter(|&j| j < pruned.len());
+            for &target in &targets {
+                next[target] = join;
+                siblings.extend(targets.iter().map(|&other| (target, other)));
+            }
+            next[i] = None;
+            branch_edges.extend(branches.iter().map(|branch| EEGEdge {
+                from_node: node.id,
+                to_node: branch.target_node,
+                edge_type: EdgeType::Causal,
+                condition: Some(branch.condition.clone()),
+                weight: branch.weight,
+            }));
         }
     }
 
-    for i in 0..pruned.len().saturating_sub(1) {
-        edges.push(EEGEdge {
-            from_node: pruned[i].id,
-            to_node: pruned[i + 1].id,
-            edge_type: EdgeType::Causal,
-            condition: None,
-            weight: 1.0,
-        });
+    let links = dependencies(pruned, memory);
+    for (i, successor) in next.iter().enumerate() {
+        if let Some(j) = *successor {
+            let (edge_type, weight) = links
+                .iter()
+                .find(|link| link.0 == i && link.1 == j)
+                .map(|link| (link.2.clone(), link.3))
+                .unwrap_or((EdgeType::Contextual, 1.0));
+            edges.push(EEGEdge {
+                from_node: pruned[i].id,
+                to_node: pruned[j].id,
+                edge_type,
+                condition: None,
+                weight,
+            });
+        }
+    }
+    edges.extend(branch_edges);
+    for (from, to, edge_type, weight) in links {
+        if from < to && next[from] != Some(to) && !siblings.contains(&(from, to)) {
+            edges.push(EEGEdge {
+                from_node: pruned[from].id,
+                to_node: pruned[to].id,
+                edge_type,
+                condition: None,
+                weight,
+            });
+        }
     }
 
-    if exit_points.is_empty() && !pruned.is_empty() {
+    exit_points.extend(
+        pruned
+            .iter()
+            .filter(|node| nodes.contains_key(&node.id))
+            .filter(|node| !edges.iter().any(|edge| edge.from_node == node.id))
+            .map(|node| node.id),
+    );
+    if exit_points.is_empty() {
         exit_points.push(pruned[pruned.len() - 1].id);
     }
 
@@ -315,14 +739,14 @@ fn construct_eeg(
         metadata: EEGMetadata {
             compilation_timestamp: current_timestamp(),
             fragment_count: pruned.len(),
-            estimated_execution_time: pruned.len() as f64,
+            estimated_execution_time: pruned.iter().map(|n| n.execution_cost).sum(),
             confidence_score: pruned.iter().map(|n| n.confidence).sum::<f64>()
                 / pruned.len() as f64,
         },
     }
 }
 
-fn create_empty_eeg(_context: &ContextVector) -> EEG {
+pub(crate) fn create_empty_eeg(_context: &ContextVector) -> EEG {
     let gap_id = uuid::Uuid::new_v4();
     let mut nodes = HashMap::new();
     nodes.insert(
@@ -0,0 +1,277 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::normalize::fold_case;
+use crate::types::*;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+const POSITIVE_RELATIONS: &[&str] = &[
+    "likes",
+    "like",
+    "loves",
+    "love",
+    "enjoys",
+    "enjoy",
+    "prefers",
+    "prefer",
+    "preference",
+    "favorite",
+];
+
+const NEGATIVE_RELATIONS: &[&str] = &["dislikes", "dislike", "hates", "hate", "avoids", "avoid"];
+
+pub trait ConflictResolver {
+    fn score(&self, fragment: &MFragment, memory: &MemoryGraph) -> f64;
+
+    fn select(&self, candidates: &[&MFragment], memory: &MemoryGraph) -> Option<Uuid> {
+        candidates
+            .iter()
+            .map(|fragment| (fragment, self.score(fragment, memory)))
+            .max_by(|(a, a_score), (b, b_score)| {
+                a_score
+                    .partial_cmp(b_score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(
+                        a.confidence
+                            .partial_cmp(&b.confidence)
+                            .unwrap_or(std::cmp::Ordering::Equal),
+                    )
+                    .then(
+                        a.created_at
+                            .partial_cmp(&b.created_at)
+                            .unwrap_or(std::cmp::Ordering::Equal),
+                    )
+                    .then(b.id.cmp(&a.id))
+            })
+            .map(|(fragment, _)| fragment.id)
+    }
+}
+
+impl<R: ConflictResolver + ?Sized> ConflictResolver for &R {
+    fn score(&self, fragment: &MFragment, memory: &MemoryGraph) -> f64 {
+        (**self).score(fragment, memory)
+    }
+
+    fn select(&self, candidates: &[&MFragment], memory: &MemoryGraph) -> Option<Uuid> {
+        (**self).select(candidates, memory)
+    }
+}
+
+pub struct ConfidenceResolver;
+
+impl ConflictResolver for ConfidenceResolver {
+    fn score(&self, fragment: &MFragment, _memory: &MemoryGraph) -> f64 {
+        fragment.confidence
+    }
+}
+
+pub struct RecencyResolver;
+
+impl ConflictResolver for RecencyResolver {
+    fn score(&self, fragment: &MFragment, _memory: &MemoryGraph) -> f64 {
+        fragment.created_at
+    }
+}
+
+pub struct ReinforcementResolver;
+
+impl ConflictResolver for ReinforcementResolver {
+    fn score(&self, fragment: &MFragment, _memory: &MemoryGraph) -> f64 {
+        fragment.reinforcement_count as f64
+    }
+}
+
+impl ConflictResolver for SourceTrustResolver {
+    fn score(&self, fragment: &MFragment, memory: &MemoryGraph) -> f64 {
+        memory
+            .fragment_sources
+            .get(&fragment.id)
+            .and_then(|source| {
+                self.trust
+                    .iter()
+                    .find(|(name, _)| fold_case(name) == fold_case(source))
+                    .map(|(_, trust)| *trust)
+            })
+            .unwrap_or(self.default_trust)
+    }
+}
+
+fn polarity(relation: &str) -> Option<bool> {
+    let relation = fold_case(relation.trim());
+    if POSITIVE_RELATIONS.contains(&relation.as_str()) {
+        Some(true)
+    } else if NEGATIVE_RELATIONS.contains(&relation.as_str()) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn sentiment(content: &FragmentContent) -> Option<(String, bool, String)> {
+    let (subject, relation, object) = match content {
+        FragmentContent::EntityRelation {
+            entity,
+            relation,
+            target,
+        } => (entity, relation, target),
+        FragmentContent::PersonalFact {
+            person,
+            fact_type,
+            value,
+            ..
+        } => (person, fact_type, value),
+        FragmentContent::SocialRelation {
+            person1,
+            person2,
+            relation_type,
+            ..
+        } => (person1, relation_type, person2),
+        _ => return None,
+    };
+    polarity(relation).map(|positive| (fold_case(subject), positive, fold_case(object)))
+}
+
+fn same(a: &str, b: &str) -> bool {
+    fold_case(a.trim()) == fold_case(b.trim())
+}
+
+pub fn fragments_conflict(a: &MFragment, b: &MFragment) -> bool {
+    if let (Some(first), Some(second)) = (sentiment(&a.content), sentiment(&b.content)) {
+        return first.0 == second.0 && first.2 == second.2 && first.1 != second.1;
+    }
+
+    match (&a.content, &b.content) {
+        (
+            FragmentContent::PersonalFact {
+                person: p1,
+                fact_type: f1,
+                value: v1,
+                ..
+            },
+            FragmentContent::PersonalFact {
+                person: p2,
+                fact_type: f2,
+                value: v2,
+                ..
+            },
+        ) => same(p1, p2) && same(f1, f2) && !same(v1, v2),
+        (
+            FragmentContent::StateTransition {
+                entity: e1,
+                from_state: f1,
+                to_state: t1,
+                condition: c1,
+                ..
+            },
+            FragmentContent::StateTransition {
+                entity: e2,
+                from_state: f2,
+                to_state: t2,
+                condition: c2,
+                ..
+            },
+        ) => {
+            same(e1, e2)
+                && same(f1, f2)
+                && !same(t1, t2)
+                && c1.as_deref().map(fold_case) == c2.as_deref().map(fold_case)
+        }
+        (
+            FragmentContent::SpatialRelation {
+                entity: e1,
+                location: l1,
+                relation_type: r1,
+                ..
+            },
+            FragmentContent::SpatialRelation {
+                entity: e2,
+                location: l2,
+                relation_type: r2,
+                ..
+            },
+        ) => same(e1, e2) && same(r1, r2) && same(r1, "located_at") && !same(l1, l2),
+        (
+            FragmentContent::QuantitativeFact {
+                entity: e1,
+                quantity: q1,
+                unit: u1,
+                comparison: c1,
+                ..
+            },
+            FragmentContent::QuantitativeFact {
+                entity: e2,
+                quantity: q2,
+                unit: u2,
+                comparison: c2,
+                ..
+            },
+        ) => {
+            same(e1, e2)
+                && u1.as_deref().map(fold_case) == u2.as_deref().map(fold_case)
+                && c1.is_none()
+                && c2.is_none()
+                && (q1 - q2).abs() > f64::EPSILON
+        }
+        (
+            FragmentContent::Capability {
+                entity: e1,
+                capability: c1,
+                level: Some(l1),
+                ..
+            },
+            FragmentContent::Capability {
+                entity: e2,
+                capability: c2,
+                level: Some(l2),
+                ..
+            },
+        ) => same(e1, e2) && same(c1, c2) && (l1 - l2).abs() > f64::EPSILON,
+        _ => false,
+    }
+}
+
+fn root(parent: &mut HashMap<Uuid, Uuid>, id: Uuid) -> Uuid {
+    let mut current = id;
+    while parent[&current] != current {
+        current = parent[&current];
+    }
+    parent.insert(id, current);
+    current
+}
+
+pub fn detect_conflicts(ids: &[Uuid], memory: &MemoryGraph) -> Vec<Vec<Uuid>> {
+    let mut ids: Vec<Uuid> = ids
+        .iter()
+        .copied()
+        .filter(|id| memory.fragments.contains_key(id))
+        .collect();
+    ids.sort();
+    ids.dedup();
+
+    let mut parent: HashMap<Uuid, Uuid> = ids.iter().map(|&id| (id, id)).collect();
+
+    let mut conflicted = HashSet::new();
+    for i in 0..ids.len() {
+        for j in (i + 1)..ids.len() {
+            if fragments_conflict(&memory.fragments[&ids[i]], &memory.fragments[&ids[j]]) {
+                conflicted.insert(ids[i]);
+                conflicted.insert(ids[j]);
+                let (a, b) = (root(&mut parent, ids[i]), root(&mut parent, ids[j]));
+                if a != b {
+                    parent.insert(a.max(b), a.min(b));
+                }
+            }
+        }
+    }
+
+    let mut groups: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for &id in &ids {
+        if conflicted.contains(&id) {
+            let group = root(&mut parent, id);
+            groups.entry(group).or_default().push(id);
+        }
+    }
+
+    let mut groups: Vec<Vec<Uuid>> = groups.into_values().collect();
+    groups.sort();
+    groups
+}
@@ -1,19 +1,203 @@
 // Copyright (c) 2026 Nolan Taft
+use crate::domains::goal_affinities;
+use crate::memory::content_fields;
+use crate::normalize::{fold_case, normalize_term};
 use crate::types::*;
 use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+pub(crate) const RELATION_FIELDS: &[&str] = &["relation", "relation_type", "fact_type"];
+const ENTITY_FIELDS: &[&str] = &[
+    "entity",
+    "target",
+    "person",
+    "parent",
+    "child",
+    "location",
+    "event",
+    "condition",
+    "outcome",
+    "goal",
+];
+
+pub trait ContextGenerator {
+    fn features(&self, goal: &str, domain: &str, memory: &MemoryGraph) -> ContextFeatures;
+
+    fn generate(
+        &self,
+        goal: &str,
+        domain: &str,
+        time_pressure: f64,
+        memory: &MemoryGraph,
+    ) -> ContextVector {
+        build_context(
+            goal,
+            domain,
+            time_pressure,
+            self.features(goal, domain, memory),
+        )
+    }
+}
+
+pub struct HeuristicContextGenerator;
+
+impl ContextGenerator for HeuristicContextGenerator {
+    fn features(&self, goal: &str, domain: &str, _memory: &MemoryGraph) -> ContextFeatures {
+        heuristic_features(goal, domain)
+    }
+}
+
+impl ContextGenerator for VocabularyContextGenerator {
+    fn features(&self, goal: &str, domain: &str, memory: &MemoryGraph) -> ContextFeatures {
+        let tokens = memory.tokenizer.tokens(goal);
+        let phrases = memory.tokenizer.phrases(goal);
+        let goal_terms: HashSet<String> = tokens
+            .iter()
+            .chain(phrases.iter())
+            .map(|t| t.term.clone())
+            .collect();
+
+        let mut entities = HashSet::new();
+        let mut matched: HashSet<Uuid> = HashSet::new();
+        for token in phrases.iter().chain(tokens.iter()) {
+            let ids = vocabulary_matches(memory, token);
+            if !ids.is_empty() {
+                if entities.len() < self.max_entities {
+                    entities.insert(token.text.clone());
+                }
+                matched.extend(ids);
+            }
+        }
+
+        let mut ids: Vec<&Uuid> = matched.iter().collect();
+        ids.sort();
+        let mut relations = HashSet::new();
+        let mut parameters = HashMap::new();
+        let mut goal_scores: HashMap<String, f64> = HashMap::new();
+        for id in ids {
+            let fragment = match memory.fragments.get(id) {
+                Some(fragment) => fragment,
+                None => continue,
+            };
+
+            let affinities = goal_affinities(fragment);
+            for affinity in &affinities {
+                *goal_scores.entry(affinity.clone()).or_insert(0.0) +=
+                    1.0 / affinities.len() as f64;
+            }
+
+            for (key, value) in content_fields(&fragment.content) {
+                let value_terms = memory.tokenizer.terms(&value);
+                if value_terms.is_empty() || !value_terms.iter().all(|t| goal_terms.contains(t)) {
+                    continue;
+                }
+                if RELATION_FIELDS.contains(&key.as_str()) {
+                    relations.insert(fold_case(&value));
+                } else if !ENTITY_FIELDS.contains(&key.as_str()) && key != "atom_type" {
+                    parameters.insert(key, value);
+                }
+            }
+        }
+
+        for word in goal.split(|c: char| !c.is_alphanumeric()) {
+            let term = normalize_term(word);
+            for (cue, goal_type) in &self.goal_cues {
+                if normalize_term(cue) == term {
+                    *goal_scores.entry(goal_key(goal_type)).or_insert(0.0) += self.cue_weight;
+                }
+            }
+        }
+
+        let mut goal_type = self.default_goal_type.clone();
+        let mut best = 0.0;
+        for candidate in [
+            GoalType::Debug,
+            GoalType::Create,
+            GoalType::Learn,
+            GoalType::Explain,
+            GoalType::Predict,
+        ] {
+            let score = goal_scores
+                .get(&goal_key(&candidate))
+                .copied()
+                .unwrap_or(0.0);
+            if score > best {
+                best = score;
+                goal_type = candidate;
+            }
+        }
+
+        let mut tags: HashSet<String> = tokens
+            .iter()
+            .chain(phrases.iter())
+            .filter(|token| !vocabulary_matches(memory, token).is_empty())
+            .map(|token| token.term.clone())
+            .collect();
+        tags.insert(fold_case(domain));
+
+        ContextFeatures {
+            goal_type,
+            parameters,
+            entities,
+            relations,
+            tags,
+        }
+    }
+}
+
+fn vocabulary_matches(memory: &MemoryGraph, token: &Token) -> HashSet<Uuid> {
+    let mut ids: HashSet<Uuid> = memory
+        .activation_index
+        .by_keyword
+        .get(&token.term)
+        .cloned()
+        .unwrap_or_default();
+    if token.term.contains('_') {
+        let terms: Vec<String> = token.term.split('_').map(|t| t.to_string()).collect();
+        ids.extend(memory.phrase_index.find(&terms));
+    } else if let Some(documents) = memory.text_index.postings.get(&token.term) {
+        ids.extend(documents.keys());
+    }
+    ids
+}
+
+fn goal_key(goal_type: &GoalType) -> String {
+    let name = match goal_type {
+        GoalType::Debug => "debug",
+        GoalType::Create => "create",
+        GoalType::Learn => "learn",
+        GoalType::Explain => "explain",
+        GoalType::Predict => "predict",
+    };
+    normalize_term(name)
+}
 
 pub fn generate_context(goal: &str, domain: &str, time_pressure: f64) -> ContextVector {
+    build_context(
+        goal,
+        domain,
+        time_pressure,
+        heuristic_features(goal, domain),
+    )
+}
+
+pub fn build_context(
+    goal: &str,
+    domain: &str,
+    time_pressure: f64,
+    features: ContextFeatures,
+) -> ContextVector {
     let goal_spec = GoalSpec {
         description: goal.to_string(),
-        goal_type: infer_goal_type(goal),
-        parameters: extract_goal_parameters(goal),
+        goal_type: features.goal_type,
+        parameters: features.parameters,
         priority: 0.8,
     };
 
     let attention_window = AttentionWindow {
-        focus_entities: extract_entities(goal),
+        focus_entities: features.entities,
         focus_domains: HashSet::from([domain.to_string()]),
-        focus_relations: extract_relations(goal),
+        focus_relations: features.relations,
         exclusion_patterns: HashSet::new(),
     };
 
@@ -28,7 +212,7 @@ pub fn generate_context(goal: &str, domain: &str, time_pressure: f64) -> Context
     let domain_hint = DomainPattern {
         domain: domain.to_string(),
         subdomain: None,
-        tags: extract_tags(domain),
+        tags: features.tags,
     };
 
     ContextVector {
@@ -37,6 +221,7 @@ pub fn generate_context(goal: &str, domain: &str, time_pressure: f64) -> Context
         emotional_bias,
         environmental_constraints: Constraints::default(),
         recent_activations: Vec::new(),
+        activation_primes: HashMap::new(),
         time_pressure,
         domain_hint,
         confidence_threshold: 0.6,
@@ -44,6 +229,16 @@ pub fn generate_context(goal: &str, domain: &str, time_pressure: f64) -> Context
     }
 }
 
+pub fn heuristic_features(goal: &str, domain: &str) -> ContextFeatures {
+    ContextFeatures {
+        goal_type: infer_goal_type(goal),
+        parameters: extract_goal_parameters(goal),
+        entities: extract_entities(goal),
+        relations: extract_relations(goal),
+        tags: extract_tags(domain),
+    }
+}
+
 fn infer_goal_type(goal: &str) -> GoalType {
     let goal_lower = goal.to_lowercase();
     if goal_lower.contains("debug") || goal_lower.contains("fix") {
@@ -0,0 +1,17 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::types::*;
+
+impl CostModel {
+    pub fn node_cost(&self, node_type: &NodeType, fragment: Option<&MFragment>) -> f64 {
+        fragment
+            .and_then(|fragment| self.fragment_costs.get(&fragment.fragment_type))
+            .or_else(|| self.node_costs.get(node_type))
+            .copied()
+            .unwrap_or(self.default_cost)
+            .max(0.0)
+    }
+
+    pub fn expected_value(&self, confidence: f64, cost: f64, price: f64) -> f64 {
+        confidence - price * cost
+    }
+}
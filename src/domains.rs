@@ -0,0 +1,230 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::embedding::fragment_text;
+use crate::normalize::{fold_case, normalize_keyword, normalize_term, normalize_terms};
+use crate::types::*;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+const SOURCE_DOMAIN_KEYS: &[&str] = &["domain", "product", "area", "project"];
+
+impl IngestSource {
+    pub fn in_domain(domain: &str) -> Self {
+        IngestSource {
+            domain: Some(domain.to_string()),
+            context: HashMap::new(),
+        }
+    }
+
+    pub fn from_event(event: &SemanticEvent) -> Self {
+        IngestSource {
+            domain: event.source_context.get("domain").cloned(),
+            context: event.source_context.clone(),
+        }
+    }
+}
+
+pub fn normalize_domain_terms(config: &DomainClassifierConfig) -> HashMap<String, HashSet<String>> {
+    config
+        .domain_terms
+        .iter()
+        .map(|(domain, terms)| {
+            (
+                domain.clone(),
+                terms.iter().map(|term| normalize_term(term)).collect(),
+            )
+        })
+        .collect()
+}
+
+pub fn classify_domains(
+    fragment: &MFragment,
+    source: &IngestSource,
+    config: &DomainClassifierConfig,
+    domain_terms: &HashMap<String, HashSet<String>>,
+) -> Vec<(String, f64)> {
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    let mut bump = |domain: &str, score: f64| {
+        let domain = fold_case(domain.trim());
+        if !domain.is_empty() {
+            let entry = scores.entry(domain).or_insert(score);
+            *entry = entry.max(score);
+        }
+    };
+
+    if let Some(domain) = &source.domain {
+        bump(domain, 1.0);
+    }
+
+    let mut keys: Vec<&String> = source.context.keys().collect();
+    keys.sort();
+    let mut terms: HashSet<String> = normalize_terms(&fragment_text(fragment))
+        .into_iter()
+        .collect();
+    for key in keys {
+        let value = &source.context[key];
+        if SOURCE_DOMAIN_KEYS.contains(&fold_case(key).as_str()) {
+            bump(value, 1.0);
+        } else if config.domain_terms.contains_key(&fold_case(value)) {
+            bump(value, 0.9);
+        } else {
+            terms.extend(normalize_terms(value));
+        }
+    }
+
+    if config.enabled {
+        let mut domains: Vec<&String> = domain_terms.keys().collect();
+        domains.sort();
+        for domain in domains {
+            let hits = domain_terms[domain]
+                .iter()
+                .filter(|term| terms.contains(*term))
+                .count();
+            let score = (hits as f64 * 0.5).min(1.0);
+            if score >= config.min_score {
+                bump(domain, score);
+            }
+        }
+
+        if is_personal(fragment) {
+            bump("personal", 0.6);
+        }
+    }
+
+    if scores.is_empty() {
+        return vec![(fold_case(&config.fallback_domain), 1.0)];
+    }
+
+    let mut ranked: Vec<(String, f64)> = scores.into_iter().collect();
+    ranked.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.0.cmp(&b.0))
+    });
+    ranked.truncate(config.max_domains.max(1));
+    ranked
+}
+
+pub fn goal_affinities(fragment: &MFragment) -> Vec<String> {
+    let goals: &[&str] = match fragment.fragment_type {
+        FragmentType::CausalRule | FragmentType::StateTransition => {
+            &["debug", "explain", "predict"]
+        }
+        FragmentType::GoalStrategy | FragmentType::Capability | FragmentType::Constraint => {
+            &["create"]
+        }
+        FragmentType::PersonalFact
+        | FragmentType::Preference
+        | FragmentType::SocialRelation
+        | FragmentType::OwnershipRelation => &["personal"],
+        FragmentType::EntityRelation
+        | FragmentType::HierarchicalRelation
+        | FragmentType::QuantitativeFact
+        | FragmentType::SpatialRelation
+        | FragmentType::TemporalEvent
+        | FragmentType::Belief => &["learn", "explain"],
+        FragmentType::SemanticAtom if is_personal(fragment) => &["personal"],
+        FragmentType::SemanticAtom => &["learn", "explain"],
+        FragmentType::ContextSignature => &[],
+    };
+
+    goals.iter().map(|goal| normalize_keyword(goal)).collect()
+}
+
+fn is_personal(fragment: &MFragment) -> bool {
+    match &fragment.content {
+        FragmentContent::PersonalFact { .. }
+        | FragmentContent::Preference { .. }
+        | FragmentContent::SocialRelation { .. } => true,
+        FragmentContent::SemanticAtom { atom_type, .. } => *atom_type == AtomType::Person,
+        _ => false,
+    }
+}
+
+impl MemoryGraph {
+    pub fn ingest_event(&mut self, event: &SemanticEvent) -> Vec<Uuid> {
+        let fragments = crate::distillation::distill_event(event);
+        let edges = crate::distillation::create_edges_from_relationships(event, &fragments);
+        let source = IngestSource::from_event(event);
+
+        let mut edges_by_fragment: HashMap<Uuid, Vec<Edge>> = HashMap::new();
+        for edge in edges {
+            edges_by_fragment
+                .entry(edge.from_fragment)
+                .or_default()
+                .push(edge);
+        }
+
+        let mut inserted = Vec::new();
+        for fragment in fragments {
+            let fragment_edges = edges_by_fragment.remove(&fragment.id).unwrap_or_default();
+            inserted.push(fragment.id);
+            self.insert_fragment_from(fragment, fragment_edges, &source);
+        }
+        inserted
+    }
+
+    pub fn fragment_domains(&self, id: Uuid) -> Vec<String> {
+        let mut domains: Vec<String> = self
+            .activation_index
+            .by_domain
+            .iter()
+            .filter(|(_, ids)| ids.contains(&id))
+            .map(|(domain, _)| domain.clone())
+            .collect();
+        domains.sort();
+        domains
+    }
+
+    pub fn set_domain_classifier(&mut self, config: DomainClassifierConfig) {
+        self.domain_terms = normalize_domain_terms(&config);
+        self.domain_classifier = config;
+        self.touch();
+    }
+
+    pub fn reclassify_domains(&mut self) {
+        self.domain_terms = normalize_domain_terms(&self.domain_classifier);
+        let mut ids: Vec<Uuid> = self.fragments.keys().copied().collect();
+        ids.sort();
+        for id in ids {
+            let fragment = self.fragments[&id].clone();
+            self.classify_fragment(&fragment, &IngestSource::default());
+        }
+    }
+
+    pub(crate) fn restore_domains(&mut self, id: Uuid, domains: &[String]) {
+        self.activation_index.by_domain.retain(|_, ids| {
+            ids.remove(&id);
+            !ids.is_empty()
+        });
+        for domain in domains {
+            self.activation_index
+                .by_domain
+                .entry(domain.clone())
+                .or_default()
+                .insert(id);
+        }
+    }
+
+    pub(crate) fn classify_fragment(&mut self, fragment: &MFragment, source: &IngestSource) {
+        for (domain, _) in classify_domains(
+            fragment,
+            source,
+            &self.domain_classifier,
+            &self.domain_terms,
+        ) {
+            self.activation_index
+                .by_domain
+                .entry(domain)
+                .or_default()
+                .insert(fragment.id);
+        }
+
+        for goal in goal_affinities(fragment) {
+            self.activation_index
+                .by_goal
+                .entry(goal)
+                .or_default()
+                .insert(fragment.id);
+        }
+    }
+}
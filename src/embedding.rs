@@ -0,0 +1,442 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::memory::content_fields;
+use crate::types::*;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+const INDEX_VECTOR_NONZEROS: usize = 8;
+const NGRAM_WEIGHT: f32 = 1.0;
+const WORD_WEIGHT: f32 = 0.5;
+const CONTEXT_WEIGHT: f32 = 0.5;
+
+impl VectorIndex {
+    pub fn new(config: VectorIndexConfig) -> Self {
+        VectorIndex {
+            config,
+            ..VectorIndex::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.vectors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vectors.is_empty()
+    }
+
+    pub fn embed(&self, text: &str) -> Vec<f32> {
+        let dims = self.config.dimensions.max(1);
+        let mut vector = vec![0.0f32; dims];
+
+        for word in vector_words(text) {
+            add_ngrams(&mut vector, &word, &self.config);
+            add_index_vector(&mut vector, &word, WORD_WEIGHT);
+
+            if let Some(concept) = self.config.synonyms.get(&word) {
+                add_index_vector(
+                    &mut vector,
+                    &format!("synonym:{}", concept),
+                    self.config.synonym_weight,
+                );
+            }
+
+            if let Some(context) = self.word_contexts.get(&word) {
+                let norm = l2_norm(context);
+                if norm > 0.0 && context.len() == dims {
+                    for (v, c) in vector.iter_mut().zip(context) {
+                        *v += CONTEXT_WEIGHT * c / norm;
+                    }
+                }
+            }
+        }
+
+        normalize(&mut vector);
+        vector
+    }
+
+    pub fn add_synonyms(&mut self, words: &[&str]) {
+        let words: Vec<String> = words.iter().flat_map(|w| vector_words(w)).collect();
+        let concept = match words.first() {
+            Some(first) => self.config.synonyms.get(first).unwrap_or(first).clone(),
+            None => return,
+        };
+        for word in words {
+            self.config.synonyms.insert(word, concept.clone());
+        }
+    }
+
+    fn learn(&mut self, id: Uuid, text: &str) {
+        let dims = self.config.dimensions.max(1);
+        let mut words: Vec<String> = vector_words(text);
+        words.sort();
+        words.dedup();
+
+        let mut learned = Vec::new();
+        for word in &words {
+            if !self.word_contexts.contains_key(word)
+                && self.word_contexts.len() >= self.config.max_context_words
+            {
+                continue;
+            }
+            let context = self
+                .word_contexts
+                .entry(word.clone())
+                .or_insert_with(|| vec![0.0; dims]);
+            for other in &words {
+                if other != word {
+                    add_index_vector(context, other, 1.0);
+                }
+            }
+            *self.word_documents.entry(word.clone()).or_insert(0) += 1;
+            learned.push(word.clone());
+        }
+
+        self.documents.insert(id, DocumentWords { words, learned });
+    }
+
+    fn forget(&mut self, id: Uuid) {
+        let document = match self.documents.remove(&id) {
+            Some(document) => document,
+            None => return,
+        };
+
+        for word in &document.learned {
+            let count = self.word_documents.get_mut(word).map(|count| {
+                *count = count.saturating_sub(1);
+                *count
+            });
+            if count.unwrap_or(0) == 0 {
+                self.word_documents.remove(word);
+                self.word_contexts.remove(word);
+                continue;
+            }
+            if let Some(context) = self.word_contexts.get_mut(word) {
+                for other in &document.words {
+                    if other != word {
+                        add_index_vector(context, other, -1.0);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn insert(&mut self, id: Uuid, text: &str) {
+        if !self.config.enabled {
+            return;
+        }
+
+        self.remove(id);
+        self.learn(id, text);
+
+        let vector = self.embed(text);
+        self.store(id, vector);
+    }
+
+    pub fn remove(&mut self, id: Uuid) {
+        self.forget(id);
+        let vector = match self.vectors.remove(&id) {
+            Some(vector) => vector,
+            None => return,
+        };
+
+        for (table, signature) in self.signatures(&vector).into_iter().enumerate() {
+            if let Some(bucket) = self
+                .buckets
+                .get_mut(table)
+                .and_then(|t| t.get_mut(&signature))
+            {
+                bucket.retain(|other| *other != id);
+                if bucket.is_empty() {
+                    self.buckets[table].remove(&signature);
+                }
+            }
+        }
+    }
+
+    pub fn search(&self, text: &str) -> Vec<(Uuid, f64)> {
+        if !self.config.enabled || self.vectors.is_empty() {
+            return Vec::new();
+        }
+
+        let query = self.embed(text);
+        if l2_norm(&query) == 0.0 {
+            return Vec::new();
+        }
+
+        let candidates: HashSet<Uuid> = if self.vectors.len() <= self.config.exact_search_limit {
+            self.vectors.keys().copied().collect()
+        } else {
+            self.probe(&query)
+        };
+
+        let mut scored: Vec<(Uuid, f64)> = candidates
+            .into_iter()
+            .filter_map(|id| {
+                let similarity = cosine_similarity(&query, self.vectors.get(&id)?);
+                (similarity >= self.config.min_similarity).then_some((id, similarity))
+            })
+            .collect();
+
+        scored.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.0.cmp(&b.0))
+        });
+        scored.truncate(self.config.max_candidates);
+        scored
+    }
+
+    pub fn search_terms(&self, terms: &[String]) -> Vec<(Uuid, f64)> {
+        let mut best: HashMap<Uuid, f64> = HashMap::new();
+        for term in terms {
+            for (id, similarity) in self.search(term) {
+                let entry = best.entry(id).or_insert(similarity);
+                *entry = entry.max(similarity);
+            }
+        }
+
+        let mut scored: Vec<(Uuid, f64)> = best.into_iter().collect();
+        scored.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.0.cmp(&b.0))
+        });
+        scored.truncate(self.config.max_candidates);
+        scored
+    }
+
+    pub fn rebuild(&mut self, fragments: &HashMap<Uuid, MFragment>) {
+        self.vectors.clear();
+        self.buckets.clear();
+        self.word_contexts.clear();
+        self.documents.clear();
+        self.word_documents.clear();
+
+        let mut ids: Vec<&Uuid> = fragments.keys().collect();
+        ids.sort();
+        for id in &ids {
+            self.learn(**id, &fragment_text(&fragments[id]));
+        }
+        for id in ids {
+            let vector = self.embed(&fragment_text(&fragments[id]));
+            self.store(*id, vector);
+        }
+    }
+
+    fn store(&mut self, id: Uuid, vector: Vec<f32>) {
+        if l2_norm(&vector) == 0.0 {
+            return;
+        }
+
+        while self.buckets.len() < self.config.hash_tables {
+            self.buckets.push(HashMap::new());
+        }
+        for (table, signature) in self.signatures(&vector).into_iter().enumerate() {
+            self.buckets[table].entry(signature).or_default().push(id);
+        }
+        self.vectors.insert(id, vector);
+    }
+
+    fn probe(&self, query: &[f32]) -> HashSet<Uuid> {
+        let bits = self.config.hash_bits.min(63);
+        let mut candidates = HashSet::new();
+
+        for (table, signature) in self.signatures(query).into_iter().enumerate() {
+            let buckets = match self.buckets.get(table) {
+                Some(buckets) => buckets,
+                None => continue,
+            };
+
+            let mut probes = vec![signature];
+            for first in 0..bits {
+                probes.push(signature ^ (1 << first));
+                for second in (first + 1)..bits {
+                    probes.push(signature ^ (1 << first) ^ (1 << second));
+                }
+            }
+
+            for probe in probes {
+                if let Some(ids) = buckets.get(&probe) {
+                    candidates.extend(ids.iter().copied());
+                }
+            }
+        }
+
+        candidates
+    }
+
+    fn signatures(&self, vector: &[f32]) -> Vec<u64> {
+        let cached = self
+            .hyperplanes
+            .get_or_init(|| Hyperplanes::generate(&self.config, vector.len()));
+        let fresh;
+        let hyperplanes = if cached.fits(&self.config, vector.len()) {
+            cached
+        } else {
+            fresh = Hyperplanes::generate(&self.config, vector.len());
+            &fresh
+        };
+        if hyperplanes.planes.is_empty() {
+            return vec![0; self.config.hash_tables];
+        }
+
+        hyperplanes
+            .planes
+            .chunks(hyperplanes.dimensions * hyperplanes.bits)
+            .map(|table| {
+                let mut signature = 0u64;
+                for (bit, plane) in table.chunks(hyperplanes.dimensions).enumerate() {
+                    let dot: f32 = vector.iter().zip(plane).map(|(v, p)| v * p).sum();
+                    if dot >= 0.0 {
+                        signature |= 1 << bit;
+                    }
+                }
+                signature
+            })
+            .collect()
+    }
+}
+
+impl Hyperplanes {
+    fn generate(config: &VectorIndexConfig, dimensions: usize) -> Self {
+        let bits = config.hash_bits.min(63);
+        let mut rng = XorShift::new(config.seed);
+        let planes = (0..config.hash_tables * bits * dimensions)
+            .map(|_| rng.next_signed())
+            .collect();
+        Hyperplanes {
+            seed: config.seed,
+            tables: config.hash_tables,
+            bits,
+            dimensions,
+            planes,
+        }
+    }
+
+    fn fits(&self, config: &VectorIndexConfig, dimensions: usize) -> bool {
+        self.seed == config.seed
+            && self.tables == config.hash_tables
+            && self.bits == config.hash_bits.min(63)
+            && self.dimensions == dimensions
+    }
+}
+
+pub fn fragment_text(fragment: &MFragment) -> String {
+    content_fields(&fragment.content)
+        .into_iter()
+        .filter(|(key, value)| key != "atom_type" && value.parse::<f64>().is_err())
+        .map(|(_, value)| value)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = l2_norm(a) * l2_norm(b);
+    if norm == 0.0 {
+        0.0
+    } else {
+        (dot / norm) as f64
+    }
+}
+
+fn vector_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+fn add_ngrams(vector: &mut [f32], word: &str, config: &VectorIndexConfig) {
+    let padded: Vec<char> = format!("<{}>", word).chars().collect();
+    let dims = vector.len();
+
+    for n in config.min_ngram..=config.max_ngram {
+        if padded.len() < n {
+            continue;
+        }
+        for window in padded.windows(n) {
+            let gram: String = window.iter().collect();
+            let hash = fnv1a(gram.as_bytes());
+            let sign = if hash & (1 << 63) == 0 { 1.0 } else { -1.0 };
+            vector[(hash % dims as u64) as usize] += sign * NGRAM_WEIGHT;
+        }
+    }
+}
+
+fn add_index_vector(vector: &mut [f32], word: &str, weight: f32) {
+    let dims = vector.len() as u64;
+    let mut rng = XorShift::new(fnv1a(word.as_bytes()));
+    for _ in 0..INDEX_VECTOR_NONZEROS {
+        let position = (rng.next_u64() % dims) as usize;
+        let sign = if rng.next_u64() & 1 == 0 { 1.0 } else { -1.0 };
+        vector[position] += sign * weight;
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = FNV_OFFSET;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+fn l2_norm(vector: &[f32]) -> f32 {
+    vector.iter().map(|v| v * v).sum::<f32>().sqrt()
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm = l2_norm(vector);
+    if norm > 0.0 {
+        for v in vector.iter_mut() {
+            *v /= norm;
+        }
+    }
+}
+
+struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        XorShift { state: seed.max(1) }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    fn next_signed(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+    }
+}
+
+impl MemoryGraph {
+    pub fn semantic_search(&self, text: &str) -> Vec<(Uuid, f64)> {
+        self.vector_index.search(text)
+    }
+
+    pub fn rebuild_vector_index(&mut self) {
+        self.vector_index.rebuild(&self.fragments);
+    }
+
+    pub fn add_synonyms(&mut self, words: &[&str]) {
+        self.vector_index.add_synonyms(words);
+        self.rebuild_vector_index();
+        self.touch();
+    }
+}
@@ -0,0 +1,444 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::memory::content_fields;
+use crate::types::*;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+const BASE_LEVEL_DECAY: f64 = 0.5;
+const POSTING_BYTES: usize = 24;
+const HISTORY_ENTRY_BYTES: usize = 9;
+const PATTERN_BYTES: usize = 40;
+
+impl MemoryGraph {
+    pub fn set_budget(&mut self, budget: Option<MemoryBudget>) {
+        self.budget = budget;
+    }
+
+    pub fn estimated_bytes(&self) -> usize {
+        let dims = self.vector_index.config.dimensions;
+        let context_bytes = self.vector_index.word_contexts.len() * (dims * 4 + POSTING_BYTES);
+        let pattern_bytes: usize = self
+            .co_activation_patterns
+            .iter()
+            .map(|p| PATTERN_BYTES + p.fragment_ids.len() * 16)
+            .sum();
+
+        self.footprint.total + self.footprint.edges + context_bytes + pattern_bytes
+    }
+
+    pub(crate) fn track_fragment(&mut self, id: Uuid, terms: &[String], tokens: &[Token]) {
+        self.untrack_fragment(id);
+        let bytes = match self.fragments.get(&id) {
+            Some(fragment) => {
+                estimate_fragment_bytes(fragment) + self.index_bytes(fragment, terms, tokens)
+            }
+            None => return,
+        };
+        self.footprint.fragments.insert(id, bytes);
+        self.footprint.total += bytes;
+    }
+
+    pub(crate) fn untrack_fragment(&mut self, id: Uuid) {
+        if let Some(bytes) = self.footprint.fragments.remove(&id) {
+            self.footprint.total = self.footprint.total.saturating_sub(bytes);
+        }
+    }
+
+    pub(crate) fn track_history(&mut self, id: Uuid, added: usize, removed: usize) {
+        if let Some(bytes) = self.footprint.fragments.get_mut(&id) {
+            *bytes = (*bytes + added * HISTORY_ENTRY_BYTES)
+                .saturating_sub(removed * HISTORY_ENTRY_BYTES);
+            self.footprint.total = (self.footprint.total + added * HISTORY_ENTRY_BYTES)
+                .saturating_sub(removed * HISTORY_ENTRY_BYTES);
+        }
+    }
+
+    pub(crate) fn track_edge(&mut self, added: Option<&Edge>, removed: Option<&Edge>) {
+        self.footprint.edges += added.map(estimate_edge_bytes).unwrap_or(0);
+        self.footprint.edges = self
+            .footprint
+            .edges
+            .saturating_sub(removed.map(estimate_edge_bytes).unwrap_or(0));
+    }
+
+    pub(crate) fn recount_footprint(&mut self) {
+        self.footprint = ByteFootprint::default();
+        let ids: Vec<Uuid> = self.fragments.keys().copied().collect();
+        for id in ids {
+            let fragment = &self.fragments[&id];
+            let terms = self
+                .tokenizer
+                .terms(&crate::embedding::fragment_text(fragment));
+            let tokens = self.tokenizer.fragment_tokens(fragment);
+            self.track_fragment(id, &terms, &tokens);
+        }
+        self.footprint.edges = self.edges.values().map(estimate_edge_bytes).sum();
+    }
+
+    fn index_bytes(&self, fragment: &MFragment, terms: &[String], tokens: &[Token]) -> usize {
+        let keyword_bytes: usize = content_fields(&fragment.content)
+            .iter()
+            .map(|(_, value)| 2 * (value.len() + POSTING_BYTES) + (value.len() + 2) * POSTING_BYTES)
+            .sum();
+
+        let mut distinct: Vec<&String> = terms.iter().collect();
+        distinct.sort();
+        distinct.dedup();
+        let text_bytes: usize = POSTING_BYTES
+            + distinct
+                .iter()
+                .map(|term| term.len() + POSTING_BYTES)
+                .sum::<usize>();
+
+        let phrase_bytes: usize = tokens.len() * 8
+            + self
+                .phrase_index
+                .documents
+                .get(&fragment.id)
+                .map(|terms| terms.iter().map(|t| 2 * t.len() + POSTING_BYTES).sum())
+                .unwrap_or(0);
+
+        let vector_bytes = if self.vector_index.vectors.contains_key(&fragment.id) {
+            self.vector_index.config.dimensions * 4
+                + POSTING_BYTES * (1 + self.vector_index.config.hash_tables)
+                + self
+                    .vector_index
+                    .documents
+                    .get(&fragment.id)
+                    .map(|doc| doc.words.iter().map(|w| w.len() + POSTING_BYTES).sum())
+                    .unwrap_or(0)
+        } else {
+            0
+        };
+
+        keyword_bytes + text_bytes + phrase_bytes + vector_bytes
+    }
+
+    pub fn enforce_budget(&mut self) -> EvictionReport {
+        let budget = match &self.budget {
+            Some(budget) => budget.clone(),
+            None => {
+                return EvictionReport {
+                    budget_satisfied: true,
+                    ..EvictionReport::default()
+                }
+            }
+        };
+        self.enforce_budget_with(&budget)
+    }
+
+    pub fn enforce_budget_with(&mut self, budget: &MemoryBudget) -> EvictionReport {
+        self.enforce_budget_recording(budget, None)
+    }
+
+    pub(crate) fn enforce_budget_recording(
+        &mut self,
+        budget: &MemoryBudget,
+        mut undo: Option<&mut UndoEntry>,
+    ) -> EvictionReport {
+        let mut report = EvictionReport {
+            bytes_before: self.estimated_bytes(),
+            ..EvictionReport::default()
+        };
+        let now = current_timestamp();
+
+        let mut trimmed = Vec::new();
+        for fragment in self.fragments.values_mut() {
+            let excess = fragment
+                .activation_history
+                .len()
+                .saturating_sub(budget.max_activation_history);
+            if excess > 0 {
+                fragment.activation_history.drain(..excess);
+                report.trimmed_history_entries += excess;
+                trimmed.push((fragment.id, excess));
+            }
+        }
+        for (id, excess) in trimmed {
+            self.track_history(id, 0, excess);
+        }
+
+        if self.co_activation_patterns.len() > budget.max_co_activation_patterns {
+            self.co_activation_patterns.sort_by(|a, b| {
+                b.activation_count.cmp(&a.activation_count).then(
+                    b.last_activated
+                        .partial_cmp(&a.last_activated)
+                        .unwrap_or(std::cmp::Ordering::Equal),
+                )
+            });
+            report.evicted_co_activation_patterns =
+                self.co_activation_patterns.len() - budget.max_co_activation_patterns;
+            self.co_activation_patterns
+                .truncate(budget.max_co_activation_patterns);
+        }
+
+        let mut type_names: Vec<&String> = budget.max_fragments_per_type.keys().collect();
+        type_names.sort();
+        for type_name in type_names {
+            let limit = budget.max_fragments_per_type[type_name];
+            let of_type: Vec<Uuid> = self
+                .fragments
+                .values()
+                .filter(|f| format!("{:?}", f.fragment_type) == *type_name)
+                .map(|f| f.id)
+                .collect();
+            let excess = of_type.len().saturating_sub(limit);
+            let victims = self.rank_eviction_candidates(&of_type, &budget.eviction_policy, now);
+            for (id, score) in victims.into_iter().take(excess) {
+                self.evict_fragment(
+                    id,
+                    score,
+                    EvictionReason::MaxFragmentsPerType,
+                    &mut report,
+                    undo.as_deref_mut(),
+                );
+            }
+        }
+
+        if let Some(max_fragments) = budget.max_fragments {
+            let excess = self.fragments.len().saturating_sub(max_fragments);
+            if excess > 0 {
+                let all: Vec<Uuid> = self.fragments.keys().copied().collect();
+                let victims = self.rank_eviction_candidates(&all, &budget.eviction_policy, now);
+                for (id, score) in victims.into_iter().take(excess) {
+                    self.evict_fragment(
+                        id,
+                        score,
+                        EvictionReason::MaxFragments,
+                        &mut report,
+                        undo.as_deref_mut(),
+                    );
+                }
+            }
+        }
+
+        if let Some(max_edges) = budget.max_edges {
+            let excess = self.edges.len().saturating_sub(max_edges);
+            for key in self.rank_edge_candidates().into_iter().take(excess) {
+                self.evict_edge(
+                    key,
+                    EvictionReason::MaxEdges,
+                    &mut report,
+                    undo.as_deref_mut(),
+                );
+            }
+        }
+
+        if let Some(max_bytes) = budget.max_bytes {
+            let mut current_bytes = self.estimated_bytes();
+            if current_bytes > max_bytes {
+                let all: Vec<Uuid> = self.fragments.keys().copied().collect();
+                let victims = self.rank_eviction_candidates(&all, &budget.eviction_policy, now);
+                for (id, score) in victims {
+                    if current_bytes <= max_bytes {
+                        break;
+                    }
+                    let freed = self.fragment_footprint(id);
+                    self.evict_fragment(
+                        id,
+                        score,
+                        EvictionReason::MaxBytes,
+                        &mut report,
+                        undo.as_deref_mut(),
+                    );
+                    current_bytes = current_bytes.saturating_sub(freed);
+                }
+            }
+            if current_bytes > max_bytes {
+                for key in self.rank_edge_candidates() {
+                    if current_bytes <= max_bytes {
+                        break;
+                    }
+                    let freed = self.edges.get(&key).map(estimate_edge_bytes).unwrap_or(0);
+                    self.evict_edge(
+                        key,
+                        EvictionReason::MaxBytes,
+                        &mut report,
+                        undo.as_deref_mut(),
+                    );
+                    current_bytes = current_bytes.saturating_sub(freed);
+                }
+            }
+        }
+
+        report.bytes_after = self.estimated_bytes();
+        report.budget_satisfied = self.within_budget(budget);
+        if !report.evicted_fragments.is_empty()
+            || !report.evicted_edges.is_empty()
+            || report.trimmed_history_entries > 0
+            || report.evicted_co_activation_patterns > 0
+        {
+            self.emit(|| MemoryEvent::BudgetEnforced {
+                report: report.clone(),
+            });
+        }
+        report
+    }
+
+    pub fn within_budget(&self, budget: &MemoryBudget) -> bool {
+        let mut type_counts: HashMap<String, usize> = HashMap::new();
+        for fragment in self.fragments.values() {
+            *type_counts
+                .entry(format!("{:?}", fragment.fragment_type))
+                .or_insert(0) += 1;
+        }
+
+        let types_ok = budget
+            .max_fragments_per_type
+            .iter()
+            .all(|(type_name, limit)| type_counts.get(type_name).copied().unwrap_or(0) <= *limit);
+        let fragments_ok = budget
+            .max_fragments
+            .is_none_or(|max| self.fragments.len() <= max);
+        let edges_ok = budget.max_edges.is_none_or(|max| self.edges.len() <= max);
+        let bytes_ok = budget
+            .max_bytes
+            .is_none_or(|max| self.estimated_bytes() <= max);
+
+        types_ok && fragments_ok && edges_ok && bytes_ok
+    }
+
+    fn rank_eviction_candidates(
+        &self,
+        ids: &[Uuid],
+        policy: &EvictionPolicy,
+        now: f64,
+    ) -> Vec<(Uuid, f64)> {
+        let mut ranked: Vec<(Uuid, f64)> = ids
+            .iter()
+            .filter_map(|id| self.fragments.get(id))
+            .filter(|f| f.reinforcement_count == 0)
+            .map(|f| (f.id, eviction_score(f, policy, now)))
+            .collect();
+
+        ranked.sort_by(|a, b| {
+            a.1.partial_cmp(&b.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.0.cmp(&b.0))
+        });
+        ranked
+    }
+
+    fn rank_edge_candidates(&self) -> Vec<(Uuid, Uuid)> {
+        let protected: HashSet<Uuid> = self
+            .fragments
+            .values()
+            .filter(|f| f.reinforcement_count > 0)
+            .map(|f| f.id)
+            .collect();
+
+        let mut ranked: Vec<(&(Uuid, Uuid), &Edge)> = self
+            .edges
+            .iter()
+            .filter(|((from_id, to_id), _)| {
+                !(protected.contains(from_id) && protected.contains(to_id))
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| {
+            a.1.strength
+                .partial_cmp(&b.1.strength)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.0.cmp(b.0))
+        });
+        ranked.into_iter().map(|(key, _)| *key).collect()
+    }
+
+    fn fragment_footprint(&self, id: Uuid) -> usize {
+        let fragment_bytes = self.footprint.fragments.get(&id).copied().unwrap_or(0);
+        let edge_bytes: usize = self
+            .edges
+            .iter()
+            .filter(|((from_id, to_id), _)| *from_id == id || *to_id == id)
+            .map(|(_, edge)| estimate_edge_bytes(edge))
+            .sum();
+        fragment_bytes + edge_bytes
+    }
+
+    fn evict_fragment(
+        &mut self,
+        id: Uuid,
+        policy_score: f64,
+        reason: EvictionReason,
+        report: &mut EvictionReport,
+        undo: Option<&mut UndoEntry>,
+    ) {
+        if let Some(entry) = undo {
+            self.record_eviction(entry, id);
+        }
+        let orphaned: Vec<EvictedEdge> = self
+            .edges
+            .values()
+            .filter(|e| e.from_fragment == id || e.to_fragment == id)
+            .map(|e| EvictedEdge {
+                from_fragment: e.from_fragment,
+                to_fragment: e.to_fragment,
+                edge_type: e.edge_type.clone(),
+                reason: EvictionReason::OrphanedEdge,
+            })
+            .collect();
+
+        if let Some(fragment) = self.remove_fragment(id) {
+            report.evicted_fragments.push(EvictedFragment {
+                id,
+                fragment_type: fragment.fragment_type,
+                reason,
+                policy_score,
+            });
+            report.evicted_edges.extend(orphaned);
+        }
+    }
+
+    fn evict_edge(
+        &mut self,
+        key: (Uuid, Uuid),
+        reason: EvictionReason,
+        report: &mut EvictionReport,
+        undo: Option<&mut UndoEntry>,
+    ) {
+        if let Some(entry) = undo {
+            self.record_edge_eviction(entry, key);
+        }
+        if let Some(edge) = self.edges.remove(&key) {
+            self.touch();
+            self.track_edge(None, Some(&edge));
+            report.evicted_edges.push(EvictedEdge {
+                from_fragment: edge.from_fragment,
+                to_fragment: edge.to_fragment,
+                edge_type: edge.edge_type,
+                reason,
+            });
+        }
+    }
+}
+
+pub fn eviction_score(fragment: &MFragment, policy: &EvictionPolicy, now: f64) -> f64 {
+    match policy {
+        EvictionPolicy::LowestSalience => fragment.salience,
+        EvictionPolicy::LeastRecentlyUsed => fragment.last_activated.max(fragment.created_at),
+        EvictionPolicy::LowestBaseLevelActivation => base_level_activation(fragment, now),
+    }
+}
+
+pub fn base_level_activation(fragment: &MFragment, now: f64) -> f64 {
+    let presentations: Vec<f64> = if fragment.activation_history.is_empty() {
+        vec![fragment.created_at]
+    } else {
+        fragment.activation_history.clone()
+    };
+
+    let sum: f64 = presentations
+        .iter()
+        .map(|&t| (now - t).max(1.0).powf(-BASE_LEVEL_DECAY))
+        .sum();
+
+    sum.ln()
+}
+
+fn estimate_fragment_bytes(fragment: &MFragment) -> usize {
+    rmp_serde::to_vec(fragment).map(|b| b.len()).unwrap_or(0)
+}
+
+fn estimate_edge_bytes(edge: &Edge) -> usize {
+    rmp_serde::to_vec(edge).map(|b| b.len()).unwrap_or(0)
+}
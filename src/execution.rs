@@ -29,17 +29,26 @@ pub fn execute_eeg_with_context(
 
         match &node.content {
             NodeContent::Fragment { fragment_id, .. } => {
-                if let Some(fragment) = memory.fragments.get(fragment_id) {
-                    let outcome = interpret_fragment(fragment);
-                    fragment_outcomes.push(outcome.clone());
-                    if outcome.outcome_type == OutcomeType::Success {
-                        reinforcement_signals.push(ReinforcementSignal {
-                            fragment_id: *fragment_id,
-                            signal_type: SignalType::Positive,
-                            strength: 0.8,
-                            reason: "Successful execution".to_string(),
-                        });
-                    }
+                run_fragment(
+                    memory,
+                    *fragment_id,
+                    &mut fragment_outcomes,
+                    &mut reinforcement_signals,
+                );
+            }
+            NodeContent::FusedChain {
+                node_ids,
+                fragment_ids,
+            } => {
+                execution_trace
+                    .extend(node_ids.iter().copied().filter(|id| *id != current_node_id));
+                for fragment_id in fragment_ids {
+                    run_fragment(
+                        memory,
+                        *fragment_id,
+                        &mut fragment_outcomes,
+                        &mut reinforcement_signals,
+                    );
                 }
             }
             NodeContent::GapFill { .. } => {}
@@ -110,6 +119,26 @@ pub fn execute_eeg_with_context(
     }
 }
 
+fn run_fragment(
+    memory: &crate::types::MemoryGraph,
+    fragment_id: uuid::Uuid,
+    fragment_outcomes: &mut Vec<Outcome>,
+    reinforcement_signals: &mut Vec<ReinforcementSignal>,
+) {
+    if let Some(fragment) = memory.fragments.get(&fragment_id) {
+        let outcome = interpret_fragment(fragment);
+        fragment_outcomes.push(outcome.clone());
+        if outcome.outcome_type == OutcomeType::Success {
+            reinforcement_signals.push(ReinforcementSignal {
+                fragment_id,
+                signal_type: SignalType::Positive,
+                strength: 0.8,
+                reason: "Successful execution".to_string(),
+            });
+        }
+    }
+}
+
 fn interpret_fragment(fragment: &MFragment) -> Outcome {
     match &fragment.content {
         FragmentContent::EntityRelation {
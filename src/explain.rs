@@ -0,0 +1,263 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::embedding::fragment_text;
+use crate::passes::PassManager;
+use crate::types::*;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Default)]
+pub(crate) struct ActivationTrace {
+    pub(crate) goal_patterns: Vec<String>,
+    sources: HashMap<Uuid, Vec<MatchSource>>,
+    scores: HashMap<Uuid, ScoreBreakdown>,
+    decisions: HashMap<Uuid, ActivationDecision>,
+    parents: HashMap<Uuid, Uuid>,
+}
+
+impl ActivationTrace {
+    pub(crate) fn source(&mut self, id: Uuid, source: MatchSource) {
+        let sources = self.sources.entry(id).or_default();
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+    }
+
+    pub(crate) fn score(&mut self, id: Uuid, breakdown: ScoreBreakdown) {
+        self.scores.insert(id, breakdown);
+    }
+
+    pub(crate) fn decide(&mut self, id: Uuid, decision: ActivationDecision) {
+        self.decisions.insert(id, decision);
+    }
+
+    pub(crate) fn decide_if_new(&mut self, id: Uuid, decision: ActivationDecision) {
+        self.decisions.entry(id).or_insert(decision);
+    }
+
+    pub(crate) fn spread(&mut self, from: Uuid, to: Uuid) {
+        self.parents.insert(to, from);
+        self.decisions.insert(to, ActivationDecision::Spread);
+    }
+
+    fn spread_path(&self, id: Uuid) -> Vec<Uuid> {
+        let mut path = vec![id];
+        let mut current = id;
+        while let Some(&parent) = self.parents.get(&current) {
+            if path.contains(&parent) {
+                break;
+            }
+            path.push(parent);
+            current = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    pub(crate) fn finish(
+        self,
+        context: &ContextVector,
+        activated: &HashSet<Uuid>,
+        memory: &MemoryGraph,
+    ) -> ActivationExplanation {
+        let mut fragments: Vec<FragmentExplanation> = self
+            .decisions
+            .iter()
+            .filter_map(|(&id, decision)| {
+                let fragment = memory.fragments.get(&id)?;
+                let spread_path = match decision {
+                    ActivationDecision::Spread => self.spread_path(id),
+                    _ => Vec::new(),
+                };
+                Some(FragmentExplanation {
+                    fragment_id: id,
+                    label: fragment_text(fragment),
+                    activated: activated.contains(&id),
+                    decision: decision.clone(),
+                    score: self.scores.get(&id).cloned(),
+                    sources: self.sources.get(&id).cloned().unwrap_or_default(),
+                    spread_path,
+                })
+            })
+            .collect();
+
+        fragments.sort_by(|a, b| {
+            let total = |e: &FragmentExplanation| e.score.as_ref().map(|s| s.total);
+            b.activated
+                .cmp(&a.activated)
+                .then(
+                    total(b)
+                        .partial_cmp(&total(a))
+                        .unwrap_or(std::cmp::Ordering::Equal),
+                )
+                .then(a.fragment_id.cmp(&b.fragment_id))
+        });
+
+        ActivationExplanation {
+            goal_patterns: self.goal_patterns,
+            confidence_threshold: context.confidence_threshold,
+            max_fragments: context.max_fragments,
+            fragments,
+        }
+    }
+}
+
+impl ActivationExplanation {
+    pub fn fragment(&self, id: Uuid) -> Option<&FragmentExplanation> {
+        self.fragments.iter().find(|entry| entry.fragment_id == id)
+    }
+
+    pub fn activated(&self) -> Vec<Uuid> {
+        self.fragments
+            .iter()
+            .filter(|entry| entry.activated)
+            .map(|entry| entry.fragment_id)
+            .collect()
+    }
+}
+
+pub(crate) fn compiled_fragments(eeg: &EEG) -> Vec<Uuid> {
+    let mut ids: Vec<Uuid> = eeg
+        .nodes
+        .values()
+        .flat_map(|node| match &node.content {
+            NodeContent::Fragment { fragment_id, .. } => vec![*fragment_id],
+            NodeContent::FusedChain { fragment_ids, .. } => fragment_ids.clone(),
+            _ => Vec::new(),
+        })
+        .collect();
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+pub fn compile_thought_explained(
+    context: &ContextVector,
+    memory: &mut MemoryGraph,
+) -> (EEG, CompilationExplanation) {
+    PassManager::default().compile_explained(context, memory)
+}
+
+impl fmt::Display for MatchSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchSource::Goal { pattern } => write!(f, "goal \"{}\"", pattern),
+            MatchSource::GoalAffinity { pattern } => write!(f, "goal affinity \"{}\"", pattern),
+            MatchSource::Domain { domain } => write!(f, "domain \"{}\"", domain),
+            MatchSource::Keyword {
+                term,
+                key,
+                exact,
+                weight,
+            } => write!(
+                f,
+                "keyword \"{}\" -> \"{}\" ({}, {:.2})",
+                term,
+                key,
+                if *exact { "exact" } else { "fuzzy" },
+                weight
+            ),
+            MatchSource::Semantic { similarity } => write!(f, "semantic {:.2}", similarity),
+            MatchSource::Text { score } => write!(f, "text {:.2}", score),
+            MatchSource::Phrase { phrase } => write!(f, "phrase \"{}\"", phrase),
+            MatchSource::Recent { prime } => write!(f, "recent {:.2}", prime),
+            MatchSource::Required => write!(f, "must include"),
+            MatchSource::Edge { from, edge_type } => {
+                write!(f, "{:?} edge from {}", edge_type, from)
+            }
+        }
+    }
+}
+
+impl fmt::Display for ActivationDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActivationDecision::Selected { rank } => write!(f, "selected, rank {}", rank + 1),
+            ActivationDecision::OutOfBudget {
+                rank,
+                max_fragments,
+            } => write!(f, "cut, rank {} > max {}", rank + 1, max_fragments),
+            ActivationDecision::BelowConfidence {
+                confidence,
+                threshold,
+            } => write!(f, "below confidence, {:.2} < {:.2}", confidence, threshold),
+            ActivationDecision::Excluded => write!(f, "excluded"),
+            ActivationDecision::Required => write!(f, "required"),
+            ActivationDecision::Spread => write!(f, "spread"),
+        }
+    }
+}
+
+impl fmt::Display for ActivationExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "goal patterns: {}", self.goal_patterns.join(", "))?;
+        writeln!(
+            f,
+            "confidence threshold: {:.2}, max fragments: {}",
+            self.confidence_threshold, self.max_fragments
+        )?;
+        for entry in &self.fragments {
+            writeln!(
+                f,
+                "{} {} \"{}\": {}",
+                if entry.activated { "+" } else { "-" },
+                entry.fragment_id,
+                entry.label,
+                entry.decision
+            )?;
+            if let Some(s) = &entry.score {
+                writeln!(
+                    f,
+                    "    score {:.3} = relevance {:.3} x match {:.2} x boost {:.2} + prior {:.2}",
+                    s.total,
+                    s.relevance,
+                    s.match_weight,
+                    1.0 + s.domain_boost + s.goal_affinity_boost + s.prime_boost + s.focus_boost,
+                    s.prior
+                )?;
+                writeln!(
+                    f,
+                    "    relevance: text {:.2}, confidence {:.2}, recency {:.2}, salience {:.2}, emotion {:.2}, reinforcement {:.2}",
+                    s.text_match, s.confidence, s.recency, s.salience, s.emotion, s.reinforcement
+                )?;
+                writeln!(
+                    f,
+                    "    boost: domain {:.2}, goal {:.2}, prime {:.2}, focus {:.2}",
+                    s.domain_boost, s.goal_affinity_boost, s.prime_boost, s.focus_boost
+                )?;
+            }
+            if !entry.sources.is_empty() {
+                let sources: Vec<String> = entry.sources.iter().map(|s| s.to_string()).collect();
+                writeln!(f, "    matched: {}", sources.join("; "))?;
+            }
+            if entry.spread_path.len() > 1 {
+                let path: Vec<String> = entry.spread_path.iter().map(|id| id.to_string()).collect();
+                writeln!(f, "    path: {}", path.join(" -> "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CompilationExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.activation)?;
+        for pass in &self.passes {
+            writeln!(
+                f,
+                "pass {}: {} -> {} nodes",
+                pass.name, pass.nodes_before, pass.nodes_after
+            )?;
+        }
+        writeln!(f, "compiled fragments: {}", self.compiled.len())?;
+        for id in &self.dropped {
+            let label = self
+                .activation
+                .fragment(*id)
+                .map(|entry| entry.label.as_str())
+                .unwrap_or_default();
+            writeln!(f, "dropped by compiler: {} \"{}\"", id, label)?;
+        }
+        Ok(())
+    }
+}
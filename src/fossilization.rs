@@ -434,34 +434,25 @@ pub fn find_applicable_module<'a>(
     context: &ContextVector,
     modules: &'a [CompiledModule],
 ) -> Option<&'a CompiledModule> {
+    find_applicable_module_with_similarity(context, modules, &ContextSimilarity::default())
+}
+
+pub fn find_applicable_module_with_similarity<'a>(
+    context: &ContextVector,
+    modules: &'a [CompiledModule],
+    similarity: &ContextSimilarity,
+) -> Option<&'a CompiledModule> {
+    let mut best: Option<(&CompiledModule, f64)> = None;
     for module in modules {
-        if module_matches_context(module, context) {
-            return Some(module);
+        if !similarity.matches_pattern(&module.activation_condition, context) {
+            continue;
+        }
+        let score = similarity.pattern_similarity(&module.activation_condition, context);
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((module, score));
         }
     }
-    None
-}
-
-fn module_matches_context(module: &CompiledModule, context: &ContextVector) -> bool {
-    let goal_lower = context.goal.description.to_lowercase();
-
-    let matches_goal = module
-        .activation_condition
-        .goal_patterns
-        .iter()
-        .any(|pattern| goal_lower.contains(pattern))
-        || module.activation_condition.goal_patterns.is_empty();
-
-    let matches_domain = module
-        .activation_condition
-        .domain_hints
-        .contains(&context.domain_hint.domain)
-        || module.activation_condition.domain_hints.is_empty();
-
-    let matches_confidence =
-        context.confidence_threshold >= module.activation_condition.confidence_threshold;
-
-    matches_goal && matches_domain && matches_confidence
+    best.map(|(module, _)| module)
 }
 
 pub fn execute_compiled_module(module: &CompiledModule, context: &ContextVector) -> Outcome {
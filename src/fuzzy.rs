@@ -0,0 +1,220 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::normalize::{fold_case, normalize_keyword};
+use crate::types::*;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+impl TrigramIndex {
+    pub fn new(config: TrigramIndexConfig) -> Self {
+        TrigramIndex {
+            config,
+            ..TrigramIndex::default()
+        }
+    }
+
+    pub fn insert(&mut self, key: &str) {
+        let compact = compact_keyword(key);
+        if compact.is_empty() {
+            return;
+        }
+
+        let variants = self.keys.entry(compact.clone()).or_default();
+        let is_new = variants.is_empty();
+        variants.insert(key.to_string());
+
+        if is_new {
+            for trigram in trigrams(&compact) {
+                self.trigrams
+                    .entry(trigram)
+                    .or_default()
+                    .insert(compact.clone());
+            }
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        let compact = compact_keyword(key);
+        let emptied = match self.keys.get_mut(&compact) {
+            Some(variants) => {
+                variants.remove(key);
+                variants.is_empty()
+            }
+            None => return,
+        };
+
+        if emptied {
+            self.keys.remove(&compact);
+            for trigram in trigrams(&compact) {
+                if let Some(keys) = self.trigrams.get_mut(&trigram) {
+                    keys.remove(&compact);
+                    if keys.is_empty() {
+                        self.trigrams.remove(&trigram);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn rebuild<'a>(&mut self, keys: impl IntoIterator<Item = &'a String>) {
+        self.trigrams.clear();
+        self.keys.clear();
+        for key in keys {
+            self.insert(key);
+        }
+    }
+
+    pub fn matches(&self, term: &str) -> Vec<KeywordMatch> {
+        let compact = compact_keyword(term);
+        if compact.is_empty() {
+            return Vec::new();
+        }
+
+        let mut ranked: Vec<(String, f64, bool)> = Vec::new();
+        if self.keys.contains_key(&compact) {
+            ranked.push((compact.clone(), 1.0, true));
+        }
+
+        let length = compact.chars().count();
+        if self.config.enabled && length >= self.config.min_term_length {
+            let query_trigrams = trigrams(&compact);
+            let mut shared: HashMap<&String, usize> = HashMap::new();
+            for trigram in &query_trigrams {
+                if let Some(keys) = self.trigrams.get(trigram) {
+                    for key in keys {
+                        *shared.entry(key).or_insert(0) += 1;
+                    }
+                }
+            }
+
+            let allowed_edits = self.config.max_edit_distance.min((length / 4).max(1));
+            let mut fuzzy: Vec<(String, f64, bool)> = shared
+                .into_iter()
+                .filter(|(key, _)| **key != compact)
+                .filter_map(|(key, common)| {
+                    let key_trigrams = trigrams(key).len();
+                    let jaccard =
+                        common as f64 / (query_trigrams.len() + key_trigrams - common) as f64;
+                    let key_length = key.chars().count();
+                    let distance = if length.abs_diff(key_length) <= allowed_edits {
+                        edit_distance(&compact, key)
+                    } else {
+                        usize::MAX
+                    };
+                    let edit_similarity = if distance == usize::MAX {
+                        0.0
+                    } else {
+                        1.0 - distance as f64 / length.max(key_length) as f64
+                    };
+
+                    (jaccard >= self.config.min_similarity || distance <= allowed_edits)
+                        .then(|| (key.clone(), jaccard.max(edit_similarity), false))
+                })
+                .collect();
+
+            fuzzy.sort_by(|a, b| {
+                b.1.partial_cmp(&a.1)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(a.0.cmp(&b.0))
+            });
+            fuzzy.truncate(self.config.max_matches);
+            ranked.extend(fuzzy);
+        }
+
+        let mut matches = Vec::new();
+        for (compact_key, similarity, exact) in ranked {
+            let mut variants: Vec<&String> = self.keys[&compact_key].iter().collect();
+            variants.sort();
+            for key in variants {
+                matches.push(KeywordMatch {
+                    key: key.clone(),
+                    similarity,
+                    exact,
+                });
+            }
+        }
+        matches
+    }
+}
+
+pub fn compact_keyword(keyword: &str) -> String {
+    keyword
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+pub fn trigrams(word: &str) -> HashSet<String> {
+    let padded: Vec<char> = format!("  {} ", word).chars().collect();
+    padded
+        .windows(3)
+        .map(|window| window.iter().collect())
+        .collect()
+}
+
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let substitution = previous[j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            current[j] = substitution.min(previous[j] + 1).min(current[j - 1] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+impl MemoryGraph {
+    pub fn keyword_matches(&self, term: &str) -> Vec<KeywordMatch> {
+        let normalized = normalize_keyword(term);
+        let mut matches = self.keyword_index.matches(&normalized);
+
+        let folded = fold_case(term);
+        if folded != normalized {
+            for candidate in self.keyword_index.matches(&folded) {
+                match matches.iter_mut().find(|m| m.key == candidate.key) {
+                    Some(existing) => {
+                        existing.exact |= candidate.exact;
+                        existing.similarity = existing.similarity.max(candidate.similarity);
+                    }
+                    None => matches.push(candidate),
+                }
+            }
+        }
+
+        matches
+    }
+
+    pub fn keyword_weight(&self, keyword_match: &KeywordMatch) -> f64 {
+        if keyword_match.exact {
+            1.0
+        } else {
+            keyword_match.similarity * self.keyword_index.config.fuzzy_weight
+        }
+    }
+
+    pub fn lookup_keyword_fuzzy(&self, term: &str) -> HashMap<Uuid, f64> {
+        let mut weights: HashMap<Uuid, f64> = HashMap::new();
+        for keyword_match in self.keyword_matches(term) {
+            let weight = self.keyword_weight(&keyword_match);
+            if let Some(ids) = self.activation_index.by_keyword.get(&keyword_match.key) {
+                for id in ids {
+                    let entry = weights.entry(*id).or_insert(weight);
+                    *entry = entry.max(weight);
+                }
+            }
+        }
+        weights
+    }
+
+    pub fn rebuild_keyword_index(&mut self) {
+        self.keyword_index
+            .rebuild(self.activation_index.by_keyword.keys());
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::context::RELATION_FIELDS;
+use crate::normalize::{fold_case, normalize_keyword};
+use crate::types::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+use uuid::Uuid;
+
+const MAX_BRIDGE_DEPTH: usize = 3;
+
+const RELATION_TYPES: &[(&str, FragmentType)] = &[
+    ("cause", FragmentType::CausalRule),
+    ("lead_to", FragmentType::CausalRule),
+    ("result_in", FragmentType::CausalRule),
+    ("trigger", FragmentType::CausalRule),
+    ("prevent", FragmentType::CausalRule),
+    ("happen", FragmentType::TemporalEvent),
+    ("before", FragmentType::TemporalEvent),
+    ("after", FragmentType::TemporalEvent),
+    ("located_in", FragmentType::SpatialRelation),
+    ("near", FragmentType::SpatialRelation),
+    ("is_a", FragmentType::HierarchicalRelation),
+    ("part_of", FragmentType::HierarchicalRelation),
+    ("kind_of", FragmentType::HierarchicalRelation),
+    ("own", FragmentType::OwnershipRelation),
+    ("belong_to", FragmentType::OwnershipRelation),
+    ("know", FragmentType::SocialRelation),
+    ("friend_of", FragmentType::SocialRelation),
+    ("become", FragmentType::StateTransition),
+    ("change_to", FragmentType::StateTransition),
+    ("can", FragmentType::Capability),
+    ("able_to", FragmentType::Capability),
+    ("believe", FragmentType::Belief),
+    ("prefer", FragmentType::Preference),
+    ("achieve", FragmentType::GoalStrategy),
+];
+
+fn relation_fragment_types(relation: &str) -> Vec<FragmentType> {
+    let relation = normalize_keyword(relation);
+    let mut types: Vec<FragmentType> = Vec::new();
+    for (name, fragment_type) in RELATION_TYPES {
+        if normalize_keyword(name) == relation && !types.contains(fragment_type) {
+            types.push(fragment_type.clone());
+        }
+    }
+    types
+}
+
+impl GoalRequirement {
+    pub fn from_context(context: &ContextVector) -> Vec<GoalRequirement> {
+        let window = &context.attention_window;
+        let mut requirements: Vec<GoalRequirement> = window
+            .focus_relations
+            .iter()
+            .map(|relation| GoalRequirement::Relation(fold_case(relation.trim())))
+            .chain(
+                window
+                    .focus_entities
+                    .iter()
+                    .map(|entity| GoalRequirement::Entity(fold_case(entity.trim()))),
+            )
+            .filter(|requirement| !requirement.term().is_empty())
+            .collect();
+        requirements.sort();
+        requirements.dedup();
+        requirements
+    }
+
+    pub fn term(&self) -> &str {
+        match self {
+            GoalRequirement::Relation(term) | GoalRequirement::Entity(term) => term,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            GoalRequirement::Relation(relation) => format!("relation '{}'", relation),
+            GoalRequirement::Entity(entity) => format!("entity '{}'", entity),
+        }
+    }
+}
+
+impl MemoryGraph {
+    pub fn satisfies(&self, id: Uuid, requirement: &GoalRequirement) -> bool {
+        match requirement {
+            GoalRequirement::Relation(relation) => {
+                RELATION_FIELDS
+                    .iter()
+                    .any(|field| self.matches_pattern(id, &format!("{}={}", field, relation)))
+                    || self.fragments.get(&id).is_some_and(|fragment| {
+                        relation_fragment_types(relation).contains(&fragment.fragment_type)
+                    })
+            }
+            GoalRequirement::Entity(entity) => self.matches_pattern(id, entity),
+        }
+    }
+
+    pub fn bridge_gap(
+        &self,
+        requirement: &GoalRequirement,
+        from: &[Uuid],
+        skip: &HashSet<Uuid>,
+        context: &ContextVector,
+    ) -> Option<Vec<Uuid>> {
+        self.bridge_gap_with(requirement, from, skip, context, &self.bridge_neighbors())
+    }
+
+    pub(crate) fn bridge_gap_with(
+        &self,
+        requirement: &GoalRequirement,
+        from: &[Uuid],
+        skip: &HashSet<Uuid>,
+        context: &ContextVector,
+        neighbors: &HashMap<Uuid, Vec<(Uuid, f64)>>,
+    ) -> Option<Vec<Uuid>> {
+        self.bridge_through_edges(requirement, from, skip, context, neighbors)
+            .or_else(|| self.bridge_through_index(requirement, skip, context))
+    }
+
+    fn bridgeable(&self, id: Uuid, skip: &HashSet<Uuid>, context: &ContextVector) -> bool {
+        !skip.contains(&id)
+            && !self.is_excluded(id, context)
+            && self
+                .fragments
+                .get(&id)
+                .is_some_and(|fragment| fragment.confidence >= context.confidence_threshold)
+    }
+
+    pub(crate) fn bridge_neighbors(&self) -> HashMap<Uuid, Vec<(Uuid, f64)>> {
+        let mut neighbors: HashMap<Uuid, Vec<(Uuid, f64)>> = HashMap::new();
+        for ((from_id, to_id), edge) in &self.edges {
+            neighbors
+                .entry(*from_id)
+                .or_default()
+                .push((*to_id, edge.strength));
+            neighbors
+                .entry(*to_id)
+                .or_default()
+                .push((*from_id, edge.strength));
+        }
+        for links in neighbors.values_mut() {
+            links.sort_by(|a, b| {
+                b.1.partial_cmp(&a.1)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(a.0.cmp(&b.0))
+            });
+        }
+        neighbors
+    }
+
+    fn bridge_through_edges(
+        &self,
+        requirement: &GoalRequirement,
+        from: &[Uuid],
+        skip: &HashSet<Uuid>,
+        context: &ContextVector,
+        neighbors: &HashMap<Uuid, Vec<(Uuid, f64)>>,
+    ) -> Option<Vec<Uuid>> {
+        let mut starts = from.to_vec();
+        starts.sort();
+        let mut previous: HashMap<Uuid, Option<Uuid>> =
+            starts.iter().map(|&id| (id, None)).collect();
+        let mut queue: VecDeque<(Uuid, usize)> = starts.iter().map(|&id| (id, 0)).collect();
+
+        while let Some((current, depth)) = queue.pop_front() {
+            if depth >= MAX_BRIDGE_DEPTH {
+                continue;
+            }
+            for &(next, _) in neighbors.get(&current).into_iter().flatten() {
+                if previous.contains_key(&next) || !self.bridgeable(next, skip, context) {
+                    continue;
+                }
+                previous.insert(next, Some(current));
+                if self.satisfies(next, requirement) {
+                    let mut path = vec![next];
+                    let mut cursor = current;
+                    while let Some(Some(parent)) = previous.get(&cursor) {
+                        path.push(cursor);
+                        cursor = *parent;
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back((next, depth + 1));
+            }
+        }
+
+        None
+    }
+
+    fn bridge_through_index(
+        &self,
+        requirement: &GoalRequirement,
+        skip: &HashSet<Uuid>,
+        context: &ContextVector,
+    ) -> Option<Vec<Uuid>> {
+        let terms = self.tokenizer.terms(requirement.term());
+        self.text_index
+            .containing_all(&terms)
+            .into_iter()
+            .filter(|id| self.bridgeable(*id, skip, context))
+            .filter(|id| self.satisfies(*id, requirement))
+            .filter_map(|id| self.fragments.get(&id))
+            .max_by(|a, b| {
+                a.confidence
+                    .partial_cmp(&b.confidence)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(b.id.cmp(&a.id))
+            })
+            .map(|fragment| vec![fragment.id])
+    }
+}
@@ -0,0 +1,759 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::memory::content_fields;
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use uuid::Uuid;
+
+const FRAGMENT_TYPES: [FragmentType; 17] = [
+    FragmentType::EntityRelation,
+    FragmentType::CausalRule,
+    FragmentType::GoalStrategy,
+    FragmentType::Constraint,
+    FragmentType::Preference,
+    FragmentType::ContextSignature,
+    FragmentType::PersonalFact,
+    FragmentType::TemporalEvent,
+    FragmentType::SpatialRelation,
+    FragmentType::QuantitativeFact,
+    FragmentType::HierarchicalRelation,
+    FragmentType::SocialRelation,
+    FragmentType::OwnershipRelation,
+    FragmentType::StateTransition,
+    FragmentType::Capability,
+    FragmentType::Belief,
+    FragmentType::SemanticAtom,
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CompareOp {
+    Eq,
+    NotEq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Contains,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum QueryValue {
+    Text(String),
+    Number(f64),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryCondition {
+    pub field: String,
+    pub op: CompareOp,
+    pub value: QueryValue,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FragmentPattern {
+    pub fragment_type: Option<FragmentType>,
+    pub about: Vec<String>,
+    pub conditions: Vec<QueryCondition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LinkDirection {
+    Outgoing,
+    Incoming,
+    Either,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkClause {
+    pub edge_type: Option<EdgeType>,
+    pub direction: LinkDirection,
+    pub target: FragmentPattern,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphQuery {
+    pub root: FragmentPattern,
+    pub links: Vec<LinkClause>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphQueryError {
+    UnexpectedEnd {
+        expected: String,
+    },
+    UnexpectedToken {
+        position: usize,
+        found: String,
+        expected: String,
+    },
+    UnknownFragmentType(String),
+    UnknownEdgeType(String),
+    InvalidNumber(String),
+    UnterminatedString {
+        position: usize,
+    },
+}
+
+impl fmt::Display for GraphQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphQueryError::UnexpectedEnd { expected } => {
+                write!(f, "Unexpected end of query, expected {}", expected)
+            }
+            GraphQueryError::UnexpectedToken {
+                position,
+                found,
+                expected,
+            } => write!(
+                f,
+                "Unexpected '{}' at position {}, expected {}",
+                found, position, expected
+            ),
+            GraphQueryError::UnknownFragmentType(name) => {
+                write!(f, "Unknown fragment type: {}", name)
+            }
+            GraphQueryError::UnknownEdgeType(name) => write!(f, "Unknown edge type: {}", name),
+            GraphQueryError::InvalidNumber(text) => write!(f, "Invalid number: {}", text),
+            GraphQueryError::UnterminatedString { position } => {
+                write!(f, "Unterminated string starting at position {}", position)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GraphQueryError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Text(String),
+    Number(f64),
+    Op(CompareOp),
+    Star,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "{}", word),
+            Token::Text(text) => write!(f, "\"{}\"", text),
+            Token::Number(number) => write!(f, "{}", number),
+            Token::Op(op) => write!(f, "{:?}", op),
+            Token::Star => write!(f, "*"),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, GraphQueryError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        if c.is_whitespace() || c == ',' {
+            i += 1;
+        } else if c == '"' || c == '\'' {
+            i += 1;
+            let mut text = String::new();
+            while i < chars.len() && chars[i] != c {
+                text.push(chars[i]);
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err(GraphQueryError::UnterminatedString { position: start });
+            }
+            i += 1;
+            tokens.push((start, Token::Text(text)));
+        } else if c == '*' {
+            i += 1;
+            tokens.push((start, Token::Star));
+        } else if "=!<>~".contains(c) {
+            let next = chars.get(i + 1).copied();
+            let (op, width) = match (c, next) {
+                ('=', Some('=')) => (CompareOp::Eq, 2),
+                ('=', _) => (CompareOp::Eq, 1),
+                ('!', Some('=')) => (CompareOp::NotEq, 2),
+                ('>', Some('=')) => (CompareOp::Gte, 2),
+                ('>', _) => (CompareOp::Gt, 1),
+                ('<', Some('=')) => (CompareOp::Lte, 2),
+                ('<', _) => (CompareOp::Lt, 1),
+                ('~', _) => (CompareOp::Contains, 1),
+                _ => {
+                    return Err(GraphQueryError::UnexpectedToken {
+                        position: start,
+                        found: c.to_string(),
+                        expected: "comparison operator".to_string(),
+                    })
+                }
+            };
+            i += width;
+            tokens.push((start, Token::Op(op)));
+        } else if c.is_ascii_digit()
+            || ((c == '-' || c == '.') && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit()))
+        {
+            let mut text = String::new();
+            text.push(c);
+            i += 1;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '.' || chars[i] == '_')
+            {
+                text.push(chars[i]);
+                i += 1;
+            }
+            match parse_number(&text) {
+                Ok(number) => tokens.push((start, Token::Number(number))),
+                Err(_) if c.is_ascii_digit() && !text.contains('.') => {
+                    tokens.push((start, Token::Word(text)))
+                }
+                Err(e) => return Err(e),
+            }
+        } else if c.is_alphanumeric() || c == '_' {
+            let mut word = String::new();
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                word.push(chars[i]);
+                i += 1;
+            }
+            tokens.push((start, Token::Word(word)));
+        } else {
+            return Err(GraphQueryError::UnexpectedToken {
+                position: start,
+                found: c.to_string(),
+                expected: "query token".to_string(),
+            });
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_number(text: &str) -> Result<f64, GraphQueryError> {
+    let (digits, multiplier) = match text.chars().last() {
+        Some('s') => (&text[..text.len() - 1], 1.0),
+        Some('m') => (&text[..text.len() - 1], 60.0),
+        Some('h') => (&text[..text.len() - 1], 3600.0),
+        Some('d') => (&text[..text.len() - 1], 86400.0),
+        Some('w') => (&text[..text.len() - 1], 604800.0),
+        _ => (text, 1.0),
+    };
+
+    digits
+        .parse::<f64>()
+        .map(|n| n * multiplier)
+        .map_err(|_| GraphQueryError::InvalidNumber(text.to_string()))
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, t)| t)
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword))
+    }
+
+    fn next(&mut self, expected: &str) -> Result<(usize, Token), GraphQueryError> {
+        let token = self.tokens.get(self.position).cloned().ok_or_else(|| {
+            GraphQueryError::UnexpectedEnd {
+                expected: expected.to_string(),
+            }
+        })?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn unexpected(position: usize, token: &Token, expected: &str) -> GraphQueryError {
+        GraphQueryError::UnexpectedToken {
+            position,
+            found: token.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    fn parse_query(&mut self) -> Result<GraphQuery, GraphQueryError> {
+        if self.peek_keyword("FIND") || self.peek_keyword("MATCH") {
+            self.position += 1;
+        }
+
+        let root = self.parse_pattern()?;
+        let mut links = Vec::new();
+        let mut limit = None;
+
+        while self.peek().is_some() {
+            if self.peek_keyword("LINKED") {
+                self.position += 1;
+                links.push(self.parse_link()?);
+            } else if self.peek_keyword("LIMIT") {
+                self.position += 1;
+                let (position, token) = self.next("limit")?;
+                match token {
+                    Token::Number(n) if n >= 0.0 => limit = Some(n as usize),
+                    other => return Err(Self::unexpected(position, &other, "limit")),
+                }
+            } else {
+                let (position, token) = self.next("LINKED or LIMIT")?;
+                return Err(Self::unexpected(position, &token, "LINKED or LIMIT"));
+            }
+        }
+
+        Ok(GraphQuery { root, links, limit })
+    }
+
+    fn parse_link(&mut self) -> Result<LinkClause, GraphQueryError> {
+        let mut edge_type = None;
+        if let Some(Token::Word(word)) = self.peek() {
+            let word = word.to_lowercase();
+            if !matches!(word.as_str(), "to" | "from" | "with") {
+                edge_type = parse_edge_type(&word)?;
+                self.position += 1;
+            }
+        }
+
+        let (position, token) = self.next("TO, FROM or WITH")?;
+        let direction = match &token {
+            Token::Word(w) if w.eq_ignore_ascii_case("to") => LinkDirection::Outgoing,
+            Token::Word(w) if w.eq_ignore_ascii_case("from") => LinkDirection::Incoming,
+            Token::Word(w) if w.eq_ignore_ascii_case("with") => LinkDirection::Either,
+            _ => return Err(Self::unexpected(position, &token, "TO, FROM or WITH")),
+        };
+
+        Ok(LinkClause {
+            edge_type,
+            direction,
+            target: self.parse_pattern()?,
+        })
+    }
+
+    fn parse_pattern(&mut self) -> Result<FragmentPattern, GraphQueryError> {
+        let (position, token) = self.next("fragment type")?;
+        let fragment_type = match &token {
+            Token::Star => None,
+            Token::Word(w) if w.eq_ignore_ascii_case("any") => None,
+            Token::Word(w) => Some(parse_fragment_type(w)?),
+            _ => return Err(Self::unexpected(position, &token, "fragment type")),
+        };
+
+        let mut pattern = FragmentPattern {
+            fragment_type,
+            about: Vec::new(),
+            conditions: Vec::new(),
+        };
+
+        loop {
+            if self.peek_keyword("ABOUT") {
+                self.position += 1;
+                pattern.about.push(self.parse_text("ABOUT value")?);
+            } else if self.peek_keyword("WHERE") || self.peek_keyword("AND") {
+                self.position += 1;
+                if self.peek_keyword("ABOUT") {
+                    continue;
+                }
+                pattern.conditions.push(self.parse_condition()?);
+            } else {
+                break;
+            }
+        }
+
+        Ok(pattern)
+    }
+
+    fn parse_text(&mut self, expected: &str) -> Result<String, GraphQueryError> {
+        let (position, token) = self.next(expected)?;
+        match token {
+            Token::Text(text) | Token::Word(text) => Ok(text),
+            Token::Number(n) => Ok(n.to_string()),
+            other => Err(Self::unexpected(position, &other, expected)),
+        }
+    }
+
+    fn parse_condition(&mut self) -> Result<QueryCondition, GraphQueryError> {
+        let (position, token) = self.next("field name")?;
+        let field = match token {
+            Token::Word(word) => word.to_lowercase(),
+            other => return Err(Self::unexpected(position, &other, "field name")),
+        };
+
+        let (position, token) = self.next("comparison operator")?;
+        let op = match token {
+            Token::Op(op) => op,
+            Token::Word(w) if w.eq_ignore_ascii_case("contains") => CompareOp::Contains,
+            other => return Err(Self::unexpected(position, &other, "comparison operator")),
+        };
+
+        let (position, token) = self.next("value")?;
+        let value = match token {
+            Token::Number(n) => QueryValue::Number(n),
+            Token::Text(text) | Token::Word(text) => QueryValue::Text(text),
+            other => return Err(Self::unexpected(position, &other, "value")),
+        };
+
+        Ok(QueryCondition { field, op, value })
+    }
+}
+
+fn parse_fragment_type(name: &str) -> Result<FragmentType, GraphQueryError> {
+    let wanted = name.replace('_', "").to_lowercase();
+    FRAGMENT_TYPES
+        .iter()
+        .find(|t| format!("{:?}", t).to_lowercase() == wanted)
+        .cloned()
+        .ok_or_else(|| GraphQueryError::UnknownFragmentType(name.to_string()))
+}
+
+fn parse_edge_type(name: &str) -> Result<Option<EdgeType>, GraphQueryError> {
+    match name {
+        "causal" | "causally" => Ok(Some(EdgeType::Causal)),
+        "temporal" | "temporally" => Ok(Some(EdgeType::Temporal)),
+        "semantic" | "semantically" => Ok(Some(EdgeType::Semantic)),
+        "contextual" | "contextually" => Ok(Some(EdgeType::Contextual)),
+        "any" => Ok(None),
+        _ => Err(GraphQueryError::UnknownEdgeType(name.to_string())),
+    }
+}
+
+pub fn parse_query(input: &str) -> Result<GraphQuery, GraphQueryError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        position: 0,
+    };
+    parser.parse_query()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AccessPath {
+    KeywordLookup(Vec<String>),
+    TypeScan(FragmentType),
+    FullScan,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternPlan {
+    pub pattern: FragmentPattern,
+    pub access_path: AccessPath,
+    pub estimated_rows: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkPlan {
+    pub edge_type: Option<EdgeType>,
+    pub direction: LinkDirection,
+    pub target: PatternPlan,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryPlan {
+    pub root: PatternPlan,
+    pub links: Vec<LinkPlan>,
+    pub limit: Option<usize>,
+}
+
+impl fmt::Display for QueryPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_pattern_plan(f, "root", &self.root)?;
+        for (i, link) in self.links.iter().enumerate() {
+            let edge = link
+                .edge_type
+                .as_ref()
+                .map(|e| format!("{:?}", e))
+                .unwrap_or_else(|| "Any".to_string());
+            writeln!(
+                f,
+                "link {}: {:?} {} edges, hash join",
+                i + 1,
+                link.direction,
+                edge
+            )?;
+            write_pattern_plan(f, "  target", &link.target)?;
+        }
+        if let Some(limit) = self.limit {
+            writeln!(f, "limit: {}", limit)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_pattern_plan(f: &mut fmt::Formatter<'_>, label: &str, plan: &PatternPlan) -> fmt::Result {
+    let access = match &plan.access_path {
+        AccessPath::KeywordLookup(keys) => format!("index lookup {:?}", keys),
+        AccessPath::TypeScan(t) => format!("type scan {:?}", t),
+        AccessPath::FullScan => "full scan".to_string(),
+    };
+    writeln!(
+        f,
+        "{}: {} (~{} rows), {} filter(s)",
+        label,
+        access,
+        plan.estimated_rows,
+        plan.pattern.conditions.len()
+    )
+}
+
+pub fn plan_query(query: &GraphQuery, memory: &MemoryGraph) -> QueryPlan {
+    QueryPlan {
+        root: plan_pattern(&query.root, memory),
+        links: query
+            .links
+            .iter()
+            .map(|link| LinkPlan {
+                edge_type: link.edge_type.clone(),
+                direction: link.direction.clone(),
+                target: plan_pattern(&link.target, memory),
+            })
+            .collect(),
+        limit: query.limit,
+    }
+}
+
+fn plan_pattern(pattern: &FragmentPattern, memory: &MemoryGraph) -> PatternPlan {
+    if !pattern.about.is_empty() {
+        let estimated_rows = pattern
+            .about
+            .iter()
+            .map(|key| memory.lookup_keyword(key).len())
+            .min()
+            .unwrap_or(0);
+        return PatternPlan {
+            pattern: pattern.clone(),
+            access_path: AccessPath::KeywordLookup(pattern.about.clone()),
+            estimated_rows,
+        };
+    }
+
+    match &pattern.fragment_type {
+        Some(fragment_type) => PatternPlan {
+            pattern: pattern.clone(),
+            access_path: AccessPath::TypeScan(fragment_type.clone()),
+            estimated_rows: memory
+                .activation_index
+                .by_type
+                .get(fragment_type)
+                .map_or(0, |ids| ids.len()),
+        },
+        None => PatternPlan {
+            pattern: pattern.clone(),
+            access_path: AccessPath::FullScan,
+            estimated_rows: memory.fragments.len(),
+        },
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryMatch {
+    pub fragment_id: Uuid,
+    pub linked: Vec<Vec<Uuid>>,
+}
+
+pub fn execute_plan(plan: &QueryPlan, memory: &MemoryGraph) -> Vec<QueryMatch> {
+    let now = current_timestamp();
+    let roots = evaluate_pattern(&plan.root, memory, now);
+
+    let mut joined: HashMap<Uuid, Vec<Vec<Uuid>>> = roots
+        .iter()
+        .map(|id| (*id, vec![Vec::new(); plan.links.len()]))
+        .collect();
+
+    for (link_index, link) in plan.links.iter().enumerate() {
+        let targets = evaluate_pattern(&link.target, memory, now);
+
+        for edge in memory.edges.values() {
+            if link
+                .edge_type
+                .as_ref()
+                .is_some_and(|t| *t != edge.edge_type)
+            {
+                continue;
+            }
+            let forward = (edge.from_fragment, edge.to_fragment);
+            let backward = (edge.to_fragment, edge.from_fragment);
+            let pairs = match link.direction {
+                LinkDirection::Outgoing => vec![forward],
+                LinkDirection::Incoming => vec![backward],
+                LinkDirection::Either => vec![forward, backward],
+            };
+            for (root_id, target_id) in pairs {
+                if !targets.contains(&target_id) {
+                    continue;
+                }
+                if let Some(links) = joined.get_mut(&root_id) {
+                    if !links[link_index].contains(&target_id) {
+                        links[link_index].push(target_id);
+                    }
+                }
+            }
+        }
+
+        joined.retain(|_, links| !links[link_index].is_empty());
+    }
+
+    let mut matches: Vec<QueryMatch> = joined
+        .into_iter()
+        .map(|(fragment_id, mut linked)| {
+            for ids in &mut linked {
+                ids.sort();
+            }
+            QueryMatch {
+                fragment_id,
+                linked,
+            }
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        let a_conf = memory.fragments.get(&a.fragment_id).map(|f| f.confidence);
+        let b_conf = memory.fragments.get(&b.fragment_id).map(|f| f.confidence);
+        b_conf
+            .partial_cmp(&a_conf)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.fragment_id.cmp(&b.fragment_id))
+    });
+
+    if let Some(limit) = plan.limit {
+        matches.truncate(limit);
+    }
+
+    matches
+}
+
+fn evaluate_pattern(plan: &PatternPlan, memory: &MemoryGraph, now: f64) -> HashSet<Uuid> {
+    let candidates: Vec<Uuid> = match &plan.access_path {
+        AccessPath::KeywordLookup(keys) => {
+            let mut sets = keys.iter().map(|key| memory.lookup_keyword(key));
+            let first = sets.next().unwrap_or_default();
+            sets.fold(first, |acc, set| acc.intersection(&set).copied().collect())
+                .into_iter()
+                .collect()
+        }
+        AccessPath::TypeScan(fragment_type) => {
+            memory.lookup_type(fragment_type).into_iter().collect()
+        }
+        AccessPath::FullScan => memory.fragments.keys().copied().collect(),
+    };
+
+    candidates
+        .into_iter()
+        .filter(|id| {
+            memory
+                .fragments
+                .get(id)
+                .is_some_and(|f| pattern_matches(&plan.pattern, f, now))
+        })
+        .collect()
+}
+
+fn pattern_matches(pattern: &FragmentPattern, fragment: &MFragment, now: f64) -> bool {
+    if let Some(fragment_type) = &pattern.fragment_type {
+        if fragment.fragment_type != *fragment_type {
+            return false;
+        }
+    }
+
+    let fields = content_fields(&fragment.content);
+    pattern
+        .conditions
+        .iter()
+        .all(|condition| condition_matches(condition, fragment, &fields, now))
+}
+
+fn condition_matches(
+    condition: &QueryCondition,
+    fragment: &MFragment,
+    fields: &[(String, String)],
+    now: f64,
+) -> bool {
+    let builtin = match condition.field.as_str() {
+        "confidence" => Some(fragment.confidence),
+        "salience" => Some(fragment.salience),
+        "emotional_tag" => Some(fragment.emotional_tag),
+        "reinforcement_count" | "reinforcements" => Some(fragment.reinforcement_count as f64),
+        "created_at" => Some(fragment.created_at),
+        "last_activated" => Some(fragment.last_activated),
+        "age" => Some(now - fragment.created_at),
+        "idle" => Some(now - fragment.last_activated.max(fragment.created_at)),
+        "activations" => Some(fragment.activation_history.len() as f64),
+        _ => None,
+    };
+
+    if let Some(actual) = builtin {
+        return compare_number(actual, &condition.op, &condition.value);
+    }
+
+    let values: Vec<&String> = fields
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case(&condition.field))
+        .map(|(_, value)| value)
+        .collect();
+
+    if condition.op == CompareOp::NotEq {
+        return values
+            .iter()
+            .all(|value| !compare_text(value, &CompareOp::Eq, &condition.value));
+    }
+
+    values
+        .iter()
+        .any(|value| compare_text(value, &condition.op, &condition.value))
+}
+
+fn compare_number(actual: f64, op: &CompareOp, expected: &QueryValue) -> bool {
+    let expected = match expected {
+        QueryValue::Number(n) => *n,
+        QueryValue::Text(text) => match text.parse::<f64>() {
+            Ok(n) => n,
+            Err(_) => return false,
+        },
+    };
+
+    match op {
+        CompareOp::Eq => (actual - expected).abs() < f64::EPSILON,
+        CompareOp::NotEq => (actual - expected).abs() >= f64::EPSILON,
+        CompareOp::Gt => actual > expected,
+        CompareOp::Gte => actual >= expected,
+        CompareOp::Lt => actual < expected,
+        CompareOp::Lte => actual <= expected,
+        CompareOp::Contains => false,
+    }
+}
+
+fn compare_text(actual: &str, op: &CompareOp, expected: &QueryValue) -> bool {
+    if let (Ok(number), QueryValue::Number(_)) = (actual.parse::<f64>(), expected) {
+        return compare_number(number, op, expected);
+    }
+
+    let actual = actual.to_lowercase();
+    let expected = match expected {
+        QueryValue::Text(text) => text.to_lowercase(),
+        QueryValue::Number(n) => n.to_string(),
+    };
+
+    match op {
+        CompareOp::Eq => actual == expected,
+        CompareOp::NotEq => actual != expected,
+        CompareOp::Gt => actual > expected,
+        CompareOp::Gte => actual >= expected,
+        CompareOp::Lt => actual < expected,
+        CompareOp::Lte => actual <= expected,
+        CompareOp::Contains => actual.contains(&expected),
+    }
+}
+
+impl MemoryGraph {
+    pub fn query(&self, input: &str) -> Result<Vec<QueryMatch>, GraphQueryError> {
+        let query = parse_query(input)?;
+        let plan = plan_query(&query, self);
+        Ok(execute_plan(&plan, self))
+    }
+
+    pub fn explain_query(&self, input: &str) -> Result<QueryPlan, GraphQueryError> {
+        let query = parse_query(input)?;
+        Ok(plan_query(&query, self))
+    }
+}
@@ -205,3 +205,11 @@ pub fn ingest_conversation_enhanced(text: &str) -> SemanticEvent {
         source_context: HashMap::new(),
     }
 }
+
+pub fn ingest_conversation_in_domain(text: &str, domain: &str) -> SemanticEvent {
+    let mut event = ingest_conversation_enhanced(text);
+    event
+        .source_context
+        .insert("domain".to_string(), domain.to_string());
+    event
+}
@@ -419,14 +419,7 @@ impl IntentClassifier {
         let mut candidates = HashSet::new();
 
         for pattern in &intent.content_patterns {
-            if let Some(fragments) = memory.activation_index.by_keyword.get(pattern) {
-                candidates.extend(fragments);
-            }
-
-            let pattern_lower = pattern.to_lowercase();
-            if let Some(fragments) = memory.activation_index.by_keyword.get(&pattern_lower) {
-                candidates.extend(fragments);
-            }
+            candidates.extend(memory.lookup_keyword(pattern));
         }
 
         candidates.into_iter().collect()
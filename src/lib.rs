@@ -1,31 +1,68 @@
 // Copyright (c) 2026 Nolan Taft
 pub mod ai_agent;
+pub mod analytics;
+pub mod attention;
+pub mod bm25;
+pub mod compile_cache;
 pub mod compiler;
+pub mod conflicts;
 pub mod context;
+pub mod costs;
 pub mod distillation;
+pub mod domains;
+pub mod embedding;
+pub mod eviction;
 pub mod execution;
+pub mod explain;
 pub mod fossilization;
+pub mod fuzzy;
+pub mod gaps;
+pub mod graph_query;
 pub mod ingestion;
 pub mod intent;
 pub mod linter;
 pub mod llm_integration;
 pub mod memory;
+pub mod normalize;
+pub mod observer;
+pub mod optimizer;
+pub mod passes;
+pub mod profiles;
 pub mod query_expansion;
 pub mod response_builder;
+pub mod similarity;
 pub mod storage;
+pub mod tokenizer;
+pub mod transaction;
 pub mod types;
+pub mod working_memory;
 
 pub use ingestion::*;
 
 pub use ai_agent::*;
+pub use analytics::*;
+pub use compile_cache::*;
 pub use compiler::*;
+pub use conflicts::*;
 pub use context::*;
 pub use distillation::*;
+pub use domains::*;
+pub use embedding::*;
+pub use eviction::*;
 pub use execution::*;
+pub use explain::*;
 pub use fossilization::*;
+pub use fuzzy::*;
+pub use graph_query::*;
 pub use linter::*;
 pub use llm_integration::*;
+pub use normalize::*;
+pub use optimizer::*;
+pub use passes::*;
+pub use profiles::*;
 pub use storage::*;
+pub use tokenizer::*;
+pub use transaction::*;
 pub use types::*;
 
 pub use ingestion::extractors;
@@ -1,9 +1,17 @@
 // Copyright (c) 2026 Nolan Taft
 use crate::types::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 pub fn run_linter(input: LinterInput, config: LinterConfig) -> PatternReport {
+    run_linter_with_similarity(input, config, &ContextSimilarity::default())
+}
+
+pub fn run_linter_with_similarity(
+    input: LinterInput,
+    config: LinterConfig,
+    similarity: &ContextSimilarity,
+) -> PatternReport {
     let mut report = PatternReport {
         repeated_paths: Vec::new(),
         stable_branches: Vec::new(),
@@ -22,9 +30,12 @@ pub fn run_linter(input: LinterInput, config: LinterConfig) -> PatternReport {
     } else {
         input.execution_traces
     };
+    let trace_contexts: Vec<&ContextVector> = traces.iter().map(|trace| &trace.context).collect();
+    let clusters = similarity.cluster(&trace_contexts);
 
     report.repeated_paths = detect_repeated_paths(
         &traces,
+        &clusters,
         &input.execution_results,
         config.min_occurrences,
         config.min_path_length,
@@ -32,6 +43,7 @@ pub fn run_linter(input: LinterInput, config: LinterConfig) -> PatternReport {
 
     report.stable_branches = detect_stable_branches(
         &traces,
+        &clusters,
         &input.compiled_eegs,
         config.min_occurrences,
         config.min_branch_ratio,
@@ -39,9 +51,11 @@ pub fn run_linter(input: LinterInput, config: LinterConfig) -> PatternReport {
 
     report.invariant_subgraphs = detect_invariant_subgraphs(
         &traces,
+        &clusters,
         &input.compiled_eegs,
         config.min_occurrences,
         config.min_context_variance,
+        similarity,
     );
 
     report.high_confidence_outcomes = detect_high_confidence_outcomes(
@@ -50,31 +64,34 @@ pub fn run_linter(input: LinterInput, config: LinterConfig) -> PatternReport {
         config.min_confidence,
     );
 
-    report.fossilization_candidates = identify_fossilization_candidates(&report, &config);
+    report.fossilization_candidates =
+        identify_fossilization_candidates(&report, &config, similarity);
 
     report
 }
 
 fn detect_repeated_paths(
     traces: &[ExecutionTrace],
+    clusters: &[usize],
     results: &[ExecutionResult],
     min_occurrences: usize,
     min_path_length: usize,
 ) -> Vec<PathPattern> {
-    let mut path_counts: HashMap<Vec<Uuid>, usize> = HashMap::new();
-    let mut path_contexts: HashMap<Vec<Uuid>, Vec<ContextVector>> = HashMap::new();
-    let mut path_results: HashMap<Vec<Uuid>, Vec<&ExecutionResult>> = HashMap::new();
+    let mut path_counts: HashMap<(Vec<Uuid>, usize), usize> = HashMap::new();
+    let mut path_contexts: HashMap<(Vec<Uuid>, usize), Vec<ContextVector>> = HashMap::new();
+    let mut path_results: HashMap<(Vec<Uuid>, usize), Vec<&ExecutionResult>> = HashMap::new();
 
-    for trace in traces {
+    for (trace, &cluster) in traces.iter().zip(clusters) {
         let paths = extract_subpaths(&trace.node_sequence, min_path_length);
 
         for path in paths {
+            let key = (path, cluster);
             path_counts
-                .entry(path.clone())
+                .entry(key.clone())
                 .and_modify(|c| *c += 1)
                 .or_insert(1);
             path_contexts
-                .entry(path.clone())
+                .entry(key.clone())
                 .or_insert_with(Vec::new)
                 .push(trace.context.clone());
 
@@ -83,7 +100,7 @@ fn detect_repeated_paths(
                 .find(|r| r.execution_trace == trace.node_sequence)
             {
                 path_results
-                    .entry(path.clone())
+                    .entry(key)
                     .or_insert_with(Vec::new)
                     .push(result);
             }
@@ -91,10 +108,11 @@ fn detect_repeated_paths(
     }
 
     let mut patterns = Vec::new();
-    for (path, count) in path_counts {
+    for (key, count) in path_counts {
         if count >= min_occurrences {
-            let contexts = path_contexts.get(&path).cloned().unwrap_or_default();
-            let results_for_path = path_results.get(&path).cloned().unwrap_or_default();
+            let contexts = path_contexts.get(&key).cloned().unwrap_or_default();
+            let results_for_path = path_results.get(&key).cloned().unwrap_or_default();
+            let path = key.0;
 
             let average_confidence = calculate_path_confidence(&path, results_for_path.clone());
             let success_rate = calculate_success_rate(&results_for_path);
@@ -150,31 +168,32 @@ fn calculate_success_rate(results: &[&ExecutionResult]) -> f64 {
 
 fn detect_stable_branches(
     traces: &[ExecutionTrace],
+    clusters: &[usize],
     eegs: &[EEG],
     min_occurrences: usize,
     min_branch_ratio: f64,
 ) -> Vec<BranchPattern> {
-    let mut decision_stats: HashMap<Uuid, HashMap<Uuid, usize>> = HashMap::new();
-    let mut decision_contexts: HashMap<Uuid, Vec<ContextVector>> = HashMap::new();
+    let mut decision_stats: HashMap<(Uuid, usize), HashMap<Uuid, usize>> = HashMap::new();
+    let mut decision_contexts: HashMap<(Uuid, usize), Vec<ContextVector>> = HashMap::new();
 
-    for trace in traces {
+    for (trace, &cluster) in traces.iter().zip(clusters) {
         for (decision_node_id, chosen_branch) in &trace.branch_decisions {
             decision_stats
-                .entry(*decision_node_id)
+                .entry((*decision_node_id, cluster))
                 .or_insert_with(HashMap::new)
                 .entry(*chosen_branch)
                 .and_modify(|c| *c += 1)
                 .or_insert(1);
 
             decision_contexts
-                .entry(*decision_node_id)
+                .entry((*decision_node_id, cluster))
                 .or_insert_with(Vec::new)
                 .push(trace.context.clone());
         }
     }
 
     let mut patterns = Vec::new();
-    for (decision_node_id, branch_counts) in decision_stats {
+    for ((decision_node_id, cluster), branch_counts) in decision_stats {
         let total_decisions: usize = branch_counts.values().sum();
 
         if total_decisions < min_occurrences {
@@ -192,12 +211,16 @@ fn detect_stable_branches(
 
             if branch_ratio >= min_branch_ratio {
                 let contexts = decision_contexts
-                    .get(&decision_node_id)
+                    .get(&(decision_node_id, cluster))
                     .cloned()
                     .unwrap_or_default();
 
                 let mut confidences = Vec::new();
-                for trace in traces {
+                for (trace, _) in traces
+                    .iter()
+                    .zip(clusters)
+                    .filter(|(_, &trace_cluster)| trace_cluster == cluster)
+                {
                     if let Some(&chosen) = trace.branch_decisions.get(&decision_node_id) {
                         if chosen == dominant {
                             if let Some(eeg) = eegs
@@ -232,38 +255,44 @@ fn detect_stable_branches(
 
 fn detect_invariant_subgraphs(
     traces: &[ExecutionTrace],
+    clusters: &[usize],
     eegs: &[EEG],
     min_occurrences: usize,
     max_context_variance: f64,
+    similarity: &ContextSimilarity,
 ) -> Vec<SubgraphPattern> {
-    let mut subgraph_counts: HashMap<Vec<Uuid>, usize> = HashMap::new();
-    let mut subgraph_contexts: HashMap<Vec<Uuid>, Vec<ContextVector>> = HashMap::new();
+    let mut subgraph_counts: HashMap<(Vec<Uuid>, usize), usize> = HashMap::new();
+    let mut subgraph_contexts: HashMap<(Vec<Uuid>, usize), Vec<ContextVector>> = HashMap::new();
 
-    for trace in traces {
-        let subgraph = trace.node_sequence.clone();
+    for (trace, &cluster) in traces.iter().zip(clusters) {
+        let key = (trace.node_sequence.clone(), cluster);
         subgraph_counts
-            .entry(subgraph.clone())
+            .entry(key.clone())
             .and_modify(|c| *c += 1)
             .or_insert(1);
         subgraph_contexts
-            .entry(subgraph)
+            .entry(key)
             .or_insert_with(Vec::new)
             .push(trace.context.clone());
     }
 
     let mut patterns = Vec::new();
-    for (subgraph_nodes, count) in subgraph_counts {
+    for ((subgraph_nodes, cluster), count) in subgraph_counts {
         if count >= min_occurrences {
             let contexts = subgraph_contexts
-                .get(&subgraph_nodes)
+                .get(&(subgraph_nodes.clone(), cluster))
                 .cloned()
                 .unwrap_or_default();
 
-            let context_variance = calculate_context_variance(&contexts);
+            let context_variance = similarity.variance(&contexts);
 
             if context_variance <= max_context_variance {
                 let mut confidences = Vec::new();
-                for trace in traces {
+                for (trace, _) in traces
+                    .iter()
+                    .zip(clusters)
+                    .filter(|(_, &trace_cluster)| trace_cluster == cluster)
+                {
                     if trace.node_sequence == subgraph_nodes {
                         if let Some(eeg) = eegs
                             .iter()
@@ -294,20 +323,6 @@ fn detect_invariant_subgraphs(
     patterns
 }
 
-fn calculate_context_variance(contexts: &[ContextVector]) -> f64 {
-    if contexts.len() < 2 {
-        return 0.0;
-    }
-
-    let goals: Vec<&str> = contexts
-        .iter()
-        .map(|c| c.goal.description.as_str())
-        .collect();
-    let unique_goals: HashSet<&str> = goals.iter().copied().collect();
-
-    1.0 - (unique_goals.len() as f64 / contexts.len() as f64)
-}
-
 fn detect_high_confidence_outcomes(
     results: &[ExecutionResult],
     min_occurrences: usize,
@@ -373,6 +388,7 @@ fn detect_high_confidence_outcomes(
 fn identify_fossilization_candidates(
     report: &PatternReport,
     config: &LinterConfig,
+    similarity: &ContextSimilarity,
 ) -> Vec<FossilizationCandidate> {
     let mut candidates = Vec::new();
 
@@ -380,7 +396,7 @@ fn identify_fossilization_candidates(
         if path_pattern.occurrence_count >= config.min_occurrences
             && path_pattern.average_confidence >= config.min_confidence
         {
-            let context_variance = calculate_context_variance(&path_pattern.contexts);
+            let context_variance = similarity.variance(&path_pattern.contexts);
             let reward_correlation = path_pattern.success_rate;
             let estimated_speedup = estimate_speedup(path_pattern.occurrence_count);
 
@@ -414,7 +430,7 @@ fn identify_fossilization_candidates(
         if branch_pattern.branch_ratio >= config.min_branch_ratio
             && branch_pattern.average_confidence >= config.min_confidence
         {
-            let context_variance = calculate_context_variance(&branch_pattern.contexts);
+            let context_variance = similarity.variance(&branch_pattern.contexts);
             let reward_correlation = branch_pattern.branch_ratio;
             let estimated_speedup = 1.5;
 
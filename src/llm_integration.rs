@@ -1,8 +1,9 @@
 // Copyright (c) 2026 Nolan Taft
-use crate::compiler::*;
+use crate::conflicts::*;
 use crate::context::*;
 use crate::distillation::*;
 use crate::execution::*;
+use crate::passes::*;
 use crate::types::*;
 use uuid::Uuid;
 
@@ -156,6 +157,11 @@ pub enum LLMError {
 pub struct CMCAgent {
     memory: MemoryGraph,
     llm: Box<dyn LLMProvider>,
+    context_generator: Box<dyn ContextGenerator>,
+    passes: PassManager<'static>,
+    cache: CompilationCache,
+    working_memory: WorkingMemory,
+    profile: Option<ContextProfile>,
     conversation_history: Vec<ConversationTurn>,
     debug: bool,
 }
@@ -168,6 +174,7 @@ pub struct ConversationTurn {
     pub execution_result: Option<ExecutionResult>,
     pub response: String,
     pub timestamp: f64,
+    pub undoable: bool,
 }
 
 impl CMCAgent {
@@ -175,6 +182,11 @@ impl CMCAgent {
         CMCAgent {
             memory: MemoryGraph::new(),
             llm,
+            context_generator: Box::new(HeuristicContextGenerator),
+            passes: PassManager::default(),
+            cache: CompilationCache::default(),
+            working_memory: WorkingMemory::default(),
+            profile: None,
             conversation_history: Vec::new(),
             debug: false,
         }
@@ -184,6 +196,11 @@ impl CMCAgent {
         CMCAgent {
             memory,
             llm,
+            context_generator: Box::new(HeuristicContextGenerator),
+            passes: PassManager::default(),
+            cache: CompilationCache::default(),
+            working_memory: WorkingMemory::default(),
+            profile: None,
             conversation_history: Vec::new(),
             debug: false,
         }
@@ -193,6 +210,70 @@ impl CMCAgent {
         self.debug = enabled;
     }
 
+    pub fn set_context_generator(&mut self, generator: Box<dyn ContextGenerator>) {
+        self.context_generator = generator;
+    }
+
+    pub fn set_conflict_resolver(&mut self, resolver: Box<dyn ConflictResolver>) {
+        self.passes
+            .replace_pass(Box::new(ConflictResolutionPass::new(resolver)));
+        self.cache.clear();
+    }
+
+    pub fn set_cost_model(&mut self, cost_model: CostModel) {
+        self.passes
+            .replace_pass(Box::new(PruningPass { cost_model }));
+        self.cache.clear();
+    }
+
+    pub fn passes(&self) -> &PassManager<'static> {
+        &self.passes
+    }
+
+    pub fn passes_mut(&mut self) -> &mut PassManager<'static> {
+        self.cache.clear();
+        &mut self.passes
+    }
+
+    pub fn compilation_cache(&self) -> &CompilationCache {
+        &self.cache
+    }
+
+    pub fn compilation_cache_mut(&mut self) -> &mut CompilationCache {
+        &mut self.cache
+    }
+
+    fn compile(&mut self, context: &ContextVector) -> EEG {
+        let passes = &mut self.passes;
+        self.cache
+            .get_or_compile(context, &mut self.memory, |context, memory| {
+                passes.compile(context, memory)
+            })
+    }
+
+    pub fn set_profile(&mut self, profile: Option<ContextProfile>) {
+        self.profile = profile;
+    }
+
+    pub fn profile(&self) -> Option<&ContextProfile> {
+        self.profile.as_ref()
+    }
+
+    fn context_for(&self, goal: &str, domain: &str) -> ContextVector {
+        match &self.profile {
+            Some(profile) => profile.generate(
+                self.context_generator.as_ref(),
+                goal,
+                domain,
+                0.3,
+                &self.memory,
+            ),
+            None => self
+                .context_generator
+                .generate(goal, domain, 0.3, &self.memory),
+        }
+    }
+
     pub fn process(&mut self, user_input: &str) -> Result<String, LLMError> {
         let semantic_event = match self.llm.extract_semantics(user_input) {
             Ok(event) => {
@@ -281,6 +362,8 @@ impl CMCAgent {
             }
         }
 
+        let mut stored_ids: Vec<Uuid> = Vec::new();
+        let mut undoable = false;
         if !is_query && !is_greeting {
             let edges =
                 crate::distillation::create_edges_from_relationships(&semantic_event, &fragments);
@@ -300,45 +383,60 @@ impl CMCAgent {
                     fragments.len()
                 );
             }
-            for fragment in &fragments {
-                let fragment_edges = edges_by_fragment
-                    .get(&fragment.id)
-                    .cloned()
-                    .unwrap_or_default();
-
-                if self.debug {
-                    match &fragment.content {
-                        FragmentContent::PersonalFact {
-                            person,
-                            fact_type,
-                            value,
-                            ..
-                        } => {
-                            eprintln!(
-                                "Storing PersonalFact: person={}, fact_type={}, value={}",
-                                person, fact_type, value
-                            );
-                        }
-                        FragmentContent::OwnershipRelation { owner, owned, .. } => {
-                            eprintln!(
-                                "Storing OwnershipRelation: owner={}, owned={}",
-                                owner, owned
-                            );
-                        }
-                        FragmentContent::SemanticAtom {
-                            atom_type, content, ..
-                        } => {
-                            if content.contains_key("ownership_marker") {
-                                eprintln!("Storing SemanticAtom with ownership_marker: type={:?}, content={:?}", 
-                                    atom_type, content);
+            let debug = self.debug;
+            let source = IngestSource::from_event(&semantic_event);
+            let stored = self.memory.transaction(|tx| {
+                for fragment in &fragments {
+                    let fragment_edges = edges_by_fragment
+                        .get(&fragment.id)
+                        .cloned()
+                        .unwrap_or_default();
+
+                    if debug {
+                        match &fragment.content {
+                            FragmentContent::PersonalFact {
+                                person,
+                                fact_type,
+                                value,
+                                ..
+                            } => {
+                                eprintln!(
+                                    "Storing PersonalFact: person={}, fact_type={}, value={}",
+                                    person, fact_type, value
+                                );
+                            }
+                            FragmentContent::OwnershipRelation { owner, owned, .. } => {
+                                eprintln!(
+                                    "Storing OwnershipRelation: owner={}, owned={}",
+                                    owner, owned
+                                );
                             }
+                            FragmentContent::SemanticAtom {
+                                atom_type, content, ..
+                            } => {
+                                if content.contains_key("ownership_marker") {
+                                    eprintln!("Storing SemanticAtom with ownership_marker: type={:?}, content={:?}", 
+                                        atom_type, content);
+                                }
+                            }
+                            _ => {}
                         }
-                        _ => {}
                     }
-                }
 
-                self.memory
-                    .insert_fragment(fragment.clone(), fragment_edges);
+                    tx.insert_fragment_from(fragment.clone(), fragment_edges, &source);
+                }
+                Ok(fragments.iter().map(|fragment| fragment.id).collect::<Vec<Uuid>>())
+            });
+            match stored {
+                Ok(ids) => {
+                    undoable = !ids.is_empty();
+                    stored_ids = ids;
+                }
+                Err(error) => {
+                    if self.debug {
+                        eprintln!("\n [DEBUG] Rolled back fragment storage: {}", error);
+                    }
+                }
             }
         } else {
             if self.debug {
@@ -357,7 +455,7 @@ impl CMCAgent {
         }
 
         if is_query {
-            let mut context = generate_context(&intent.pattern, "general", 0.3);
+            let mut context = self.context_for(&intent.pattern, "general");
             Self::extract_keywords_from_atoms(&semantic_event, &mut context);
 
             self.add_query_keywords(user_input, &mut context);
@@ -377,10 +475,13 @@ impl CMCAgent {
                     }
                 }
             }
+            self.working_memory.prime(&mut context);
 
-            let eeg = compile_thought(&context, &mut self.memory);
+            let eeg = self.compile(&context);
 
             let execution_result = execute_eeg(&eeg, &mut self.memory);
+            self.working_memory
+                .record_turn(execution_result.execution_trace.iter().copied());
 
             if self.debug {
                 eprintln!("\n [DEBUG] CMCA Execution Result:");
@@ -456,13 +557,32 @@ impl CMCAgent {
                     &execution_result.execution_trace,
                     success,
                 );
-                self.memory
-                    .record_co_activation(&execution_result.execution_trace);
             }
 
-            for signal in &execution_result.reinforcement_signals {
-                self.memory
-                    .reinforce_fragment(signal.fragment_id, &execution_result.outcome);
+            let learned = self.memory.transaction(|tx| {
+                let trace: Vec<Uuid> = execution_result
+                    .execution_trace
+                    .iter()
+                    .copied()
+                    .filter(|id| tx.memory().fragments.contains_key(id))
+                    .collect();
+                if trace.len() >= 2 {
+                    tx.record_co_activation(&trace);
+                }
+                for signal in &execution_result.reinforcement_signals {
+                    if tx.memory().fragments.contains_key(&signal.fragment_id) {
+                        tx.reinforce_fragment(signal.fragment_id, &execution_result.outcome);
+                    }
+                }
+                Ok(!tx.staged().is_empty())
+            });
+            match learned {
+                Ok(staged) => undoable = staged,
+                Err(error) => {
+                    if self.debug {
+                        eprintln!("\n [DEBUG] Rolled back reinforcement: {}", error);
+                    }
+                }
             }
 
             self.conversation_history.push(ConversationTurn {
@@ -472,15 +592,23 @@ impl CMCAgent {
                 execution_result: Some(execution_result),
                 response: response.clone(),
                 timestamp: current_timestamp(),
+                undoable,
             });
 
             Ok(response)
         } else {
-            let mut context = generate_context("statement", "general", 0.3);
+            let mut context = self.context_for("statement", "general");
             Self::extract_keywords_from_atoms(&semantic_event, &mut context);
+            self.working_memory.prime(&mut context);
 
-            let eeg = compile_thought(&context, &mut self.memory);
+            let eeg = self.compile(&context);
             let execution_result = execute_eeg(&eeg, &mut self.memory);
+            self.working_memory.record_turn(
+                stored_ids
+                    .iter()
+                    .chain(execution_result.execution_trace.iter())
+                    .copied(),
+            );
 
             if self.debug {
                 eprintln!("\n [DEBUG] CMCA Execution Result (Statement):");
@@ -520,6 +648,7 @@ impl CMCAgent {
                 execution_result: Some(execution_result),
                 response: response.clone(),
                 timestamp: current_timestamp(),
+                undoable,
             });
 
             Ok(response)
@@ -534,31 +663,16 @@ impl CMCAgent {
             context.domain_hint.tags.insert(keyword);
         }
 
-        use std::collections::HashSet;
-        let keywords: HashSet<String> = user_input
-            .to_lowercase()
-            .split_whitespace()
-            .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_string())
-            .filter(|w| w.len() >= 2)
-            .collect();
-
-        for keyword in keywords {
-            context.domain_hint.tags.insert(keyword.clone());
-
-            if keyword.contains(' ') {
-                context.domain_hint.tags.insert(keyword.replace(' ', "_"));
-                context.domain_hint.tags.insert(keyword.replace(' ', "-"));
-            }
+        let tokenizer = &self.memory.tokenizer;
+        for token in tokenizer.tokens(user_input) {
+            context.domain_hint.tags.insert(token.text);
         }
-
-        let phrase = user_input
-            .to_lowercase()
-            .split_whitespace()
-            .filter(|w| w.len() >= 2)
-            .collect::<Vec<_>>()
-            .join("");
-        if phrase.len() >= 4 {
-            context.domain_hint.tags.insert(phrase);
+        for phrase in tokenizer.phrases(user_input) {
+            context
+                .domain_hint
+                .tags
+                .insert(phrase.text.replace(' ', "_"));
+            context.domain_hint.tags.insert(phrase.text);
         }
     }
 
@@ -586,11 +700,16 @@ impl CMCAgent {
     }
 
     pub fn query_memory(&mut self, goal: &str, domain: &str) -> ExecutionResult {
-        let context = generate_context(goal, domain, 0.3);
-        let eeg = compile_thought(&context, &mut self.memory);
+        let context = self.context_for(goal, domain);
+        let eeg = self.compile(&context);
         execute_eeg(&eeg, &mut self.memory)
     }
 
+    pub fn explain_compilation(&mut self, goal: &str, domain: &str) -> CompilationExplanation {
+        let context = self.context_for(goal, domain);
+        self.passes.compile_explained(&context, &mut self.memory).1
+    }
+
     pub fn memory_stats(&self) -> MemoryStats {
         MemoryStats {
             fragments: self.memory.fragments.len(),
@@ -611,6 +730,39 @@ impl CMCAgent {
     pub fn conversation_history(&self) -> &[ConversationTurn] {
         &self.conversation_history
     }
+
+    pub fn undo_turns(&mut self, turns: usize) -> usize {
+        let mut undone = 0;
+        while undone < turns {
+            let turn = match self.conversation_history.pop() {
+                Some(turn) => turn,
+                None => break,
+            };
+            if turn.undoable {
+                self.memory.undo(1);
+            }
+            self.working_memory.turns.pop_back();
+            undone += 1;
+        }
+        self.working_memory.retain_present(&self.memory);
+        undone
+    }
+
+    pub fn memory(&self) -> &MemoryGraph {
+        &self.memory
+    }
+
+    pub fn working_memory(&self) -> &WorkingMemory {
+        &self.working_memory
+    }
+
+    pub fn working_memory_mut(&mut self) -> &mut WorkingMemory {
+        &mut self.working_memory
+    }
+
+    pub fn clear_working_memory(&mut self) {
+        self.working_memory.clear();
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -4,6 +4,7 @@ use std::io::{self, Write};
 use std::path::Path;
 
 const DEFAULT_MEMORY_FILE: &str = "memory.cmca";
+const DEFAULT_PROFILES_FILE: &str = "profiles.toml";
 
 fn main() {
     println!("CMCA CLI - Contextual Memory Compilation Architecture");
@@ -25,6 +26,18 @@ fn main() {
         }
     };
     let mut last_eeg: Option<EEG> = None;
+    let mut cache = CompilationCache::default();
+    let mut context_generator: Box<dyn ContextGenerator> = Box::new(HeuristicContextGenerator);
+    let mut profiles = match ContextProfiles::load(DEFAULT_PROFILES_FILE) {
+        Ok(loaded) => loaded,
+        Err(ProfileError::IoError(e)) if e.kind() == io::ErrorKind::NotFound => {
+            ContextProfiles::default()
+        }
+        Err(e) => {
+            eprintln!("Error loading {}: {}", DEFAULT_PROFILES_FILE, e);
+            std::process::exit(1);
+        }
+    };
 
     loop {
         print!("cmca> ");
@@ -165,14 +178,51 @@ fn main() {
                 }
             }
             "compile" => {
-                if parts.len() < 3 {
-                    println!("Usage: compile <goal> <domain>");
+                let mut args: Vec<&str> = input.split_whitespace().skip(1).collect();
+                let mut profile = None;
+                let mut explain = false;
+                let mut failed = false;
+                while !failed {
+                    match args.first().copied() {
+                        Some("--explain") => {
+                            explain = true;
+                            args.remove(0);
+                        }
+                        Some("--profile") if args.len() >= 2 => {
+                            match profiles.get(args[1]) {
+                                Ok(found) => profile = Some(found),
+                                Err(e) => {
+                                    println!("{}", e);
+                                    failed = true;
+                                }
+                            }
+                            args.drain(..2);
+                        }
+                        _ => break,
+                    }
+                }
+                if failed {
                     continue;
                 }
-                let goal = parts[1];
-                let domain = parts[2];
-                let context = generate_context(goal, domain, 0.2);
-                let eeg = compile_thought(&context, &mut memory);
+                if args.len() != 2 {
+                    println!("Usage: compile [--explain] [--profile <name>] <goal> <domain>");
+                    continue;
+                }
+                let goal = args[0];
+                let domain = args[1];
+                let context = match profile {
+                    Some(profile) => {
+                        profile.generate(context_generator.as_ref(), goal, domain, 0.2, &memory)
+                    }
+                    None => context_generator.generate(goal, domain, 0.2, &memory),
+                };
+                let eeg = if explain {
+                    let (eeg, explanation) = compile_thought_explained(&context, &mut memory);
+                    print!("Compilation Explanation:\n{}", explanation);
+                    eeg
+                } else {
+                    compile_thought_cached(&context, &mut memory, &mut cache)
+                };
                 last_eeg = Some(eeg.clone());
 
                 println!("Compiled EEG:");
@@ -180,6 +230,33 @@ fn main() {
                 println!("Edges: {}", eeg.edges.len());
                 println!("Confidence: {:.2}", eeg.metadata.confidence_score);
             }
+            "generator" => match parts.get(1).copied() {
+                Some("heuristic") => {
+                    context_generator = Box::new(HeuristicContextGenerator);
+                    println!("Using heuristic context generator");
+                }
+                Some("vocabulary") => {
+                    context_generator = Box::new(VocabularyContextGenerator::default());
+                    println!("Using vocabulary context generator");
+                }
+                _ => println!("Usage: generator <heuristic|vocabulary>"),
+            },
+            "profiles" => {
+                if parts.len() >= 2 {
+                    match ContextProfiles::load(parts[1]) {
+                        Ok(loaded) => profiles = loaded,
+                        Err(e) => {
+                            println!("Error loading profiles: {}", e);
+                            continue;
+                        }
+                    }
+                }
+                if profiles.profiles.is_empty() {
+                    println!("No context profiles loaded");
+                } else {
+                    println!("Context profiles: {}", profiles.names().join(", "));
+                }
+            }
             "execute" => {
                 if let Some(ref eeg) = last_eeg {
                     let result = execute_eeg(eeg, &mut memory);
@@ -196,11 +273,83 @@ fn main() {
                     println!("No EEG compiled. Use 'compile' first.");
                 }
             }
+            "query" => {
+                if parts.len() < 2 {
+                    println!("Usage: query [explain] <FIND ...>");
+                    continue;
+                }
+                let mut text = parts[1..].join(" ");
+                let explain = text.to_lowercase().starts_with("explain ");
+                if explain {
+                    text = text["explain ".len()..].to_string();
+                }
+
+                if explain {
+                    match memory.explain_query(&text) {
+                        Ok(plan) => print!("Query Plan:\n{}", plan),
+                        Err(e) => {
+                            println!("Query error: {}", e);
+                            continue;
+                        }
+                    }
+                }
+
+                match memory.query(&text) {
+                    Ok(matches) => {
+                        println!("Matches ({}):", matches.len());
+                        for m in &matches {
+                            if let Some(fragment) = memory.fragments.get(&m.fragment_id) {
+                                let fields: Vec<String> =
+                                    c_mer::memory::content_fields(&fragment.content)
+                                        .iter()
+                                        .map(|(k, v)| format!("{}={}", k, v))
+                                        .collect();
+                                println!(
+                                    "{}: {:?} [{}] (conf: {:.2}, sal: {:.2})",
+                                    m.fragment_id,
+                                    fragment.fragment_type,
+                                    fields.join(", "),
+                                    fragment.confidence,
+                                    fragment.salience
+                                );
+                            }
+                            for (i, linked) in m.linked.iter().enumerate() {
+                                println!("  link {}: {:?}", i + 1, linked);
+                            }
+                        }
+                    }
+                    Err(e) => println!("Query error: {}", e),
+                }
+            }
             "memory" => {
                 println!("Memory Stats:");
                 println!("Fragments: {}", memory.fragments.len());
                 println!("Edges: {}", memory.edges.len());
                 println!("Version: {}", memory.version);
+
+                let analytics = analyze_graph(&memory);
+                println!("Components: {}", analytics.components.len());
+                if let Some(largest) = analytics.components.first() {
+                    println!("Largest component: {} fragments", largest.len());
+                }
+                println!("Isolated fragments: {}", analytics.isolated.len());
+                println!("Communities: {}", analytics.communities.len());
+                let hubs = analytics.top_hubs(3);
+                if !hubs.is_empty() {
+                    println!("Top hubs:");
+                    for (id, rank) in hubs {
+                        let degree = analytics.degree_centrality.get(&id).copied().unwrap_or(0.0);
+                        let fragment_type = memory
+                            .fragments
+                            .get(&id)
+                            .map(|f| format!("{:?}", f.fragment_type))
+                            .unwrap_or_default();
+                        println!(
+                            "{}: {} (pagerank: {:.3}, degree: {:.3})",
+                            id, fragment_type, rank, degree
+                        );
+                    }
+                }
             }
             "fragments" => {
                 println!("Fragments ({}):", memory.fragments.len());
@@ -225,12 +374,45 @@ fn main() {
                     }
                     println!("Edges:");
                     for edge in &eeg.edges {
-                        println!("{} -> {}", edge.from_node, edge.to_node);
+                        println!(
+                            "{} -> {} ({:?}, {:.2})",
+                            edge.from_node, edge.to_node, edge.edge_type, edge.weight
+                        );
                     }
                 } else {
                     println!("No EEG compiled. Use 'compile' first.");
                 }
             }
+            "optimize" => {
+                if let Some(ref mut eeg) = last_eeg {
+                    let report = optimize_eeg(eeg, &memory);
+                    println!("Optimized EEG:");
+                    println!("Nodes: {} -> {}", report.nodes_before, report.nodes_after);
+                    println!("Dead branches removed: {}", report.dead_branches_removed);
+                    println!("Unreachable nodes removed: {}", report.unreachable_removed);
+                    println!("Nodes merged: {}", report.nodes_merged);
+                    println!("Nodes fused: {}", report.nodes_fused);
+                } else {
+                    println!("No EEG compiled. Use 'compile' first.");
+                }
+            }
+            "cache" => {
+                if parts.get(1) == Some(&"clear") {
+                    cache.clear();
+                    println!("Compilation cache cleared");
+                    continue;
+                }
+                let stats = cache.stats();
+                println!(
+                    "Compilation cache: {}/{} entries",
+                    cache.len(),
+                    cache.capacity
+                );
+                println!("Hits: {}", stats.hits);
+                println!("Misses: {}", stats.misses);
+                println!("Invalidations: {}", stats.invalidations);
+                println!("Evictions: {}", stats.evictions);
+            }
             "save" => {
                 let path = if parts.len() >= 2 {
                     parts[1]
@@ -264,11 +446,19 @@ fn main() {
             "help" => {
                 println!("Commands:");
                 println!("ingest <text>     - Ingest conversation/experience");
-                println!("compile <goal> <domain> - Compile thought for goal");
+                println!("compile [--explain] [--profile <name>] <goal> <domain> - Compile thought for goal");
+                println!("generator <heuristic|vocabulary> - Choose how compile builds context");
+                println!(
+                    "profiles [path]   - Load or list context profiles (default: {})",
+                    DEFAULT_PROFILES_FILE
+                );
                 println!("execute           - Execute last compiled EEG");
                 println!("memory            - Show memory statistics");
                 println!("fragments         - List all fragments");
+                println!("query [explain] <FIND ...> - Query memory graph (e.g. FIND PersonalFact ABOUT \"alice\" WHERE confidence > 0.8 LINKED causal TO ANY ABOUT \"coffee\")");
                 println!("eeg               - Show last compiled EEG structure");
+                println!("optimize          - Optimize last compiled EEG");
+                println!("cache [clear]     - Show or clear compilation cache statistics");
                 println!(
                     "save [path]       - Save memory to file (default: {})",
                     DEFAULT_MEMORY_FILE
@@ -1,29 +1,17 @@
 // Copyright (c) 2026 Nolan Taft
+use crate::compile_cache::next_revision;
+use crate::domains::normalize_domain_terms;
+use crate::embedding::fragment_text;
+use crate::explain::ActivationTrace;
 use crate::intent::Intent;
+use crate::normalize::{fold_case, normalize_keyword, NORMALIZATION_VERSION};
 use crate::storage::{load_memory, save_memory, Result as StorageResult};
+use crate::tokenizer::phrases_of;
 use crate::types::*;
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use uuid::Uuid;
 
-fn stem_keyword(word: &str) -> String {
-    let word = word.to_lowercase();
-
-    if word.ends_with("ing") && word.len() > 5 {
-        return word[..word.len() - 3].to_string();
-    }
-    if word.ends_with("ed") && word.len() > 4 {
-        return word[..word.len() - 2].to_string();
-    }
-    if word.ends_with("es") && word.len() > 4 {
-        return word[..word.len() - 2].to_string();
-    }
-    if word.ends_with("s") && word.len() > 3 {
-        return word[..word.len() - 1].to_string();
-    }
-    word
-}
-
 impl MemoryGraph {
     pub fn new() -> Self {
         MemoryGraph {
@@ -33,6 +21,22 @@ impl MemoryGraph {
             compiled_modules: Vec::new(),
             co_activation_patterns: Vec::new(),
             version: 1,
+            normalization_version: NORMALIZATION_VERSION,
+            budget: None,
+            activation_priors: HashMap::new(),
+            vector_index: VectorIndex::default(),
+            keyword_index: TrigramIndex::default(),
+            text_index: Bm25Index::default(),
+            domain_classifier: DomainClassifierConfig::default(),
+            tokenizer: Tokenizer::default(),
+            phrase_index: PhraseIndex::default(),
+            attention: AttentionConfig::default(),
+            fragment_sources: HashMap::new(),
+            observers: MemoryObservers::default(),
+            undo_log: UndoLog::default(),
+            revision: next_revision(),
+            footprint: ByteFootprint::default(),
+            domain_terms: normalize_domain_terms(&DomainClassifierConfig::default()),
         }
     }
 
@@ -40,6 +44,7 @@ impl MemoryGraph {
         if fragment_ids.len() < 2 {
             return;
         }
+        self.touch();
 
         let mut fragment_ids_sorted = fragment_ids.to_vec();
         fragment_ids_sorted.sort();
@@ -99,7 +104,13 @@ impl MemoryGraph {
     }
 
     pub fn add_compiled_module(&mut self, module: CompiledModule) {
+        self.emit(|| MemoryEvent::ModuleAdded {
+            module_id: module.id,
+            module_type: module.module_type.clone(),
+            confidence: module.confidence,
+        });
         self.compiled_modules.push(module);
+        self.touch();
     }
 
     pub fn get_compiled_modules(&self) -> &[CompiledModule] {
@@ -107,102 +118,386 @@ impl MemoryGraph {
     }
 
     pub fn insert_fragment(&mut self, fragment: MFragment, fragment_edges: Vec<Edge>) {
+        self.insert_fragment_from(fragment, fragment_edges, &IngestSource::default());
+    }
+
+    pub fn insert_fragment_from(
+        &mut self,
+        fragment: MFragment,
+        fragment_edges: Vec<Edge>,
+        source: &IngestSource,
+    ) {
         let fragment_id = fragment.id;
-        self.fragments.insert(fragment_id, fragment.clone());
+        self.touch();
+        let replaced = self
+            .fragments
+            .insert(fragment_id, fragment.clone())
+            .is_some();
+        self.emit(|| {
+            if replaced {
+                MemoryEvent::FragmentUpdated {
+                    fragment: fragment.clone(),
+                }
+            } else {
+                MemoryEvent::FragmentInserted {
+                    fragment: fragment.clone(),
+                }
+            }
+        });
 
+        if let Some(label) = source.context.get("source") {
+            self.fragment_sources.insert(fragment_id, label.clone());
+        }
         self.update_activation_index(&fragment);
+        self.classify_fragment(&fragment, source);
+        let text = fragment_text(&fragment);
+        self.vector_index.insert(fragment_id, &text);
+        let terms = self.tokenizer.terms(&text);
+        self.text_index.insert_terms(fragment_id, &terms);
+        let tokens = self.tokenizer.fragment_tokens(&fragment);
+        self.phrase_index.insert(fragment_id, &tokens);
+        self.track_fragment(fragment_id, &terms, &tokens);
 
         for edge in fragment_edges {
-            let key = (edge.from_fragment, edge.to_fragment);
-            self.edges.insert(key, edge);
+            self.add_edge(edge);
+        }
+
+        if self.budget.is_some() {
+            self.enforce_budget();
+        }
+    }
+
+    pub fn remove_fragment(&mut self, id: Uuid) -> Option<MFragment> {
+        let fragment = self.fragments.remove(&id)?;
+        self.touch();
+        self.untrack_fragment(id);
+        self.activation_priors.remove(&id);
+        self.fragment_sources.remove(&id);
+        self.vector_index.remove(id);
+        self.text_index.remove(id);
+        self.phrase_index.remove(id);
+
+        for index in [
+            &mut self.activation_index.by_goal,
+            &mut self.activation_index.by_domain,
+        ] {
+            index.retain(|_, ids| {
+                ids.remove(&id);
+                !ids.is_empty()
+            });
         }
+        self.activation_index.by_type.retain(|_, ids| {
+            ids.remove(&id);
+            !ids.is_empty()
+        });
+
+        let mut emptied_keywords = Vec::new();
+        self.activation_index.by_keyword.retain(|key, ids| {
+            ids.remove(&id);
+            if ids.is_empty() {
+                emptied_keywords.push(key.clone());
+            }
+            !ids.is_empty()
+        });
+        for key in emptied_keywords {
+            self.keyword_index.remove(&key);
+        }
+
+        let removed_edges: Vec<(Uuid, Uuid)> = self
+            .edges
+            .keys()
+            .filter(|(from_id, to_id)| *from_id == id || *to_id == id)
+            .copied()
+            .collect();
+        for key in removed_edges {
+            self.remove_edge(key);
+        }
+
+        for pattern in &mut self.co_activation_patterns {
+            pattern
+                .fragment_ids
+                .retain(|fragment_id| *fragment_id != id);
+        }
+        self.co_activation_patterns
+            .retain(|pattern| pattern.fragment_ids.len() >= 2);
+
+        self.emit(|| MemoryEvent::FragmentRemoved { id });
+        Some(fragment)
+    }
+
+    pub fn add_edge(&mut self, edge: Edge) {
+        self.touch();
+        self.emit(|| MemoryEvent::EdgeAdded { edge: edge.clone() });
+        self.track_edge(Some(&edge), None);
+        if let Some(replaced) = self
+            .edges
+            .insert((edge.from_fragment, edge.to_fragment), edge)
+        {
+            self.track_edge(None, Some(&replaced));
+        }
+    }
+
+    pub fn remove_edge(&mut self, key: (Uuid, Uuid)) -> Option<Edge> {
+        let edge = self.edges.remove(&key)?;
+        self.touch();
+        self.track_edge(None, Some(&edge));
+        self.emit(|| MemoryEvent::EdgeRemoved {
+            from_fragment: key.0,
+            to_fragment: key.1,
+        });
+        Some(edge)
     }
 
     pub fn activate_fragments(&mut self, context: &ContextVector) -> HashSet<Uuid> {
+        self.activate(context, None)
+    }
+
+    pub fn activate_fragments_explained(
+        &mut self,
+        context: &ContextVector,
+    ) -> (HashSet<Uuid>, ActivationExplanation) {
+        let mut trace = ActivationTrace::default();
+        let activated = self.activate(context, Some(&mut trace));
+        let explanation = trace.finish(context, &activated, self);
+        (activated, explanation)
+    }
+
+    fn activate(
+        &mut self,
+        context: &ContextVector,
+        mut trace: Option<&mut ActivationTrace>,
+    ) -> HashSet<Uuid> {
         let mut candidates = HashSet::new();
+        let mut goal_affine = HashSet::new();
 
         let goal_patterns = extract_goal_patterns(&context.goal.description);
         for pattern in &goal_patterns {
             if let Some(fragments) = self.activation_index.by_goal.get(pattern) {
-                candidates.extend(fragments);
-            }
-
-            let pattern_lower = pattern.to_lowercase();
-            if let Some(fragments) = self.activation_index.by_goal.get(&pattern_lower) {
-                candidates.extend(fragments);
+                for id in fragments {
+                    let direct = self
+                        .fragments
+                        .get(id)
+                        .is_some_and(|f| f.fragment_type == FragmentType::GoalStrategy);
+                    if let Some(trace) = trace.as_deref_mut() {
+                        let pattern = pattern.clone();
+                        trace.source(
+                            *id,
+                            if direct {
+                                MatchSource::Goal { pattern }
+                            } else {
+                                MatchSource::GoalAffinity { pattern }
+                            },
+                        );
+                    }
+                    if direct {
+                        candidates.insert(*id);
+                    } else {
+                        goal_affine.insert(*id);
+                    }
+                }
             }
         }
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.goal_patterns = goal_patterns.clone();
+        }
 
-        if let Some(fragments) = self
+        let in_domain = self
             .activation_index
             .by_domain
-            .get(&context.domain_hint.domain)
+            .get(&fold_case(&context.domain_hint.domain))
+            .cloned()
+            .unwrap_or_default();
+
+        let mut match_weights: HashMap<Uuid, f64> =
+            candidates.iter().map(|id| (*id, 1.0)).collect();
+
+        for term in context
+            .domain_hint
+            .tags
+            .iter()
+            .chain(goal_patterns.iter())
+            .filter(|term| !self.tokenizer.is_stop_word(term))
         {
-            candidates.extend(fragments);
+            if let Some(trace) = trace.as_deref_mut() {
+                for keyword_match in self.keyword_matches(term) {
+                    let weight = self.keyword_weight(&keyword_match);
+                    if let Some(ids) = self.activation_index.by_keyword.get(&keyword_match.key) {
+                        for id in ids {
+                            trace.source(
+                                *id,
+                                MatchSource::Keyword {
+                                    term: term.clone(),
+                                    key: keyword_match.key.clone(),
+                                    exact: keyword_match.exact,
+                                    weight,
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+            for (id, weight) in self.lookup_keyword_fuzzy(term) {
+                let entry = match_weights.entry(id).or_insert(weight);
+                *entry = entry.max(weight);
+            }
         }
-
-        for tag in &context.domain_hint.tags {
-            if let Some(fragments) = self.activation_index.by_keyword.get(tag) {
-                candidates.extend(fragments);
+        let semantic_terms: Vec<String> = context
+            .goal
+            .description
+            .split_whitespace()
+            .map(|w| w.to_string())
+            .chain(context.domain_hint.tags.iter().cloned())
+            .filter(|term| !self.tokenizer.is_stop_word(term))
+            .collect();
+        for (id, similarity) in self.vector_index.search_terms(&semantic_terms) {
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.source(id, MatchSource::Semantic { similarity });
             }
+            match_weights
+                .entry(id)
+                .or_insert(similarity * self.vector_index.config.activation_weight);
+        }
 
-            let tag_lower = tag.to_lowercase();
-            if let Some(fragments) = self.activation_index.by_keyword.get(&tag_lower) {
-                candidates.extend(fragments);
+        let mut text_scores: Vec<(Uuid, f64)> = self
+            .text_index
+            .score_terms(&self.tokenizer.query_terms(context))
+            .into_iter()
+            .collect();
+        text_scores.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.0.cmp(&b.0))
+        });
+        text_scores.truncate(self.text_index.config.max_candidates);
+        let max_text_score = text_scores.first().map(|(_, s)| *s).unwrap_or(0.0);
+        let text_scores: HashMap<Uuid, f64> = text_scores
+            .into_iter()
+            .map(|(id, score)| (id, score / max_text_score))
+            .collect();
+        for (id, score) in &text_scores {
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.source(*id, MatchSource::Text { score: *score });
             }
-
-            let normalized_underscore = tag_lower.replace(' ', "_");
-            if normalized_underscore != tag_lower {
-                if let Some(fragments) =
-                    self.activation_index.by_keyword.get(&normalized_underscore)
-                {
-                    candidates.extend(fragments);
+            let weight = match_weights.entry(*id).or_insert(0.0);
+            *weight = weight.max(*score);
+        }
+        if let Some(trace) = trace.as_deref_mut() {
+            for phrase in self.tokenizer.query_phrases(context) {
+                let text: Vec<&str> = phrase.iter().map(|token| token.text.as_str()).collect();
+                for id in self.phrase_index.find_tokens(&phrase) {
+                    trace.source(
+                        id,
+                        MatchSource::Phrase {
+                            phrase: text.join(" "),
+                        },
+                    );
                 }
             }
-            let normalized_hyphen = tag_lower.replace(' ', "-");
-            if normalized_hyphen != tag_lower && normalized_hyphen != normalized_underscore {
-                if let Some(fragments) = self.activation_index.by_keyword.get(&normalized_hyphen) {
-                    candidates.extend(fragments);
+        }
+        for id in self.phrase_matches(context) {
+            match_weights.insert(id, 1.0);
+        }
+        for id in &context.recent_activations {
+            let prime = context.activation_primes.get(id).copied().unwrap_or(1.0);
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.source(*id, MatchSource::Recent { prime });
+            }
+            let entry = match_weights.entry(*id).or_insert(prime);
+            *entry = entry.max(prime.min(1.0));
+        }
+
+        let mut scored: Vec<(Uuid, f64)> = Vec::new();
+        for (&id, &weight) in &match_weights {
+            let fragment = match self.fragments.get(&id) {
+                Some(fragment) => fragment,
+                None => continue,
+            };
+            if self.is_excluded(id, context) {
+                if let Some(trace) = trace.as_deref_mut() {
+                    trace.decide(id, ActivationDecision::Excluded);
                 }
+                continue;
             }
-            let normalized_nospace = tag_lower.replace(' ', "");
-            if normalized_nospace != tag_lower && normalized_nospace.len() >= 4 {
-                if let Some(fragments) = self.activation_index.by_keyword.get(&normalized_nospace) {
-                    candidates.extend(fragments);
+            if fragment.confidence < context.confidence_threshold {
+                if let Some(trace) = trace.as_deref_mut() {
+                    trace.decide(
+                        id,
+                        ActivationDecision::BelowConfidence {
+                            confidence: fragment.confidence,
+                            threshold: context.confidence_threshold,
+                        },
+                    );
                 }
+                continue;
             }
-        }
 
-        for pattern in &goal_patterns {
-            let pattern_lower = pattern.to_lowercase();
-            if let Some(fragments) = self.activation_index.by_keyword.get(&pattern_lower) {
-                candidates.extend(fragments);
+            let text_match = text_scores.get(&id).copied().unwrap_or(0.0);
+            let mut breakdown = score_components(
+                fragment,
+                context,
+                text_match * self.text_index.config.weight,
+            );
+            breakdown.match_weight = weight;
+            if in_domain.contains(&id) {
+                breakdown.domain_boost = self.domain_classifier.activation_boost;
             }
-        }
-
-        let mut scored: Vec<(Uuid, f64)> = candidates
-            .iter()
-            .filter_map(|&id| {
-                if let Some(fragment) = self.fragments.get(&id) {
-                    if fragment.confidence >= context.confidence_threshold {
-                        let score = calculate_relevance_score(fragment, context);
-                        Some((id, score))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
+            if goal_affine.contains(&id) {
+                breakdown.goal_affinity_boost = self.domain_classifier.goal_affinity_boost;
+            }
+            breakdown.prime_boost = context.activation_primes.get(&id).copied().unwrap_or(0.0);
+            breakdown.focus_boost = self.focus_boost(id, context);
+            breakdown.prior = self.activation_priors.get(&id).copied().unwrap_or(0.0);
+            let boost = 1.0
+                + breakdown.domain_boost
+                + breakdown.goal_affinity_boost
+                + breakdown.prime_boost
+                + breakdown.focus_boost;
+            breakdown.total = breakdown.relevance * weight * boost + breakdown.prior;
+            scored.push((id, breakdown.total));
+
+            if let Some(trace) = trace.as_deref_mut() {
+                if in_domain.contains(&id) {
+                    trace.source(
+                        id,
+                        MatchSource::Domain {
+                            domain: fold_case(&context.domain_hint.domain),
+                        },
+                    );
                 }
-            })
-            .collect();
+                trace.score(id, breakdown);
+            }
+        }
 
         scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
 
+        if let Some(trace) = trace.as_deref_mut() {
+            for (rank, (id, _)) in scored.iter().enumerate() {
+                let decision = if rank < context.max_fragments {
+                    ActivationDecision::Selected { rank }
+                } else {
+                    ActivationDecision::OutOfBudget {
+                        rank,
+                        max_fragments: context.max_fragments,
+                    }
+                };
+                trace.decide(*id, decision);
+            }
+        }
+
         let mut activated: HashSet<Uuid> = scored
             .iter()
             .take(context.max_fragments)
             .map(|(id, _)| *id)
             .collect();
+        for id in self.required_fragments(context) {
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.source(id, MatchSource::Required);
+                if !activated.contains(&id) {
+                    trace.decide(id, ActivationDecision::Required);
+                }
+            }
+            activated.insert(id);
+        }
 
         let mut to_explore: Vec<Uuid> = activated.iter().cloned().collect();
         let mut explored = HashSet::new();
@@ -220,36 +515,69 @@ impl MemoryGraph {
             explored.insert(current);
 
             for ((from_id, to_id), edge) in &self.edges {
-                if *from_id == current && !activated.contains(to_id) {
-                    if let Some(fragment) = self.fragments.get(to_id) {
-                        if fragment.confidence >= context.confidence_threshold {
-                            activated.insert(*to_id);
-                            to_explore.push(*to_id);
-                        }
-                    }
-                } else if *to_id == current && !activated.contains(from_id) {
-                    if let Some(fragment) = self.fragments.get(from_id) {
-                        if fragment.confidence >= context.confidence_threshold {
-                            activated.insert(*from_id);
-                            to_explore.push(*from_id);
-                        }
+                let neighbor = if *from_id == current {
+                    *to_id
+                } else if *to_id == current {
+                    *from_id
+                } else {
+                    continue;
+                };
+                if activated.contains(&neighbor) {
+                    continue;
+                }
+                let fragment = match self.fragments.get(&neighbor) {
+                    Some(fragment) => fragment,
+                    None => continue,
+                };
+                let confident = fragment.confidence >= context.confidence_threshold;
+                let eligible = confident && !self.is_excluded(neighbor, context);
+
+                if let Some(trace) = trace.as_deref_mut() {
+                    trace.source(
+                        neighbor,
+                        MatchSource::Edge {
+                            from: current,
+                            edge_type: edge.edge_type.clone(),
+                        },
+                    );
+                    if eligible {
+                        trace.spread(current, neighbor);
+                    } else if confident {
+                        trace.decide_if_new(neighbor, ActivationDecision::Excluded);
+                    } else {
+                        trace.decide_if_new(
+                            neighbor,
+                            ActivationDecision::BelowConfidence {
+                                confidence: fragment.confidence,
+                                threshold: context.confidence_threshold,
+                            },
+                        );
                     }
                 }
+                if eligible {
+                    activated.insert(neighbor);
+                    to_explore.push(neighbor);
+                }
             }
         }
 
+        self.record_activation(&activated.iter().copied().collect::<Vec<_>>());
+        activated
+    }
+
+    pub(crate) fn record_activation(&mut self, ids: &[Uuid]) {
         let now = current_timestamp();
-        for &id in &activated {
+        for &id in ids {
             if let Some(fragment) = self.fragments.get_mut(&id) {
                 fragment.last_activated = now;
                 fragment.activation_history.push(now);
+                self.track_history(id, 1, 0);
             }
         }
-
-        activated
     }
 
     pub fn reinforce_fragment(&mut self, id: Uuid, outcome: &Outcome) {
+        self.touch();
         if let Some(fragment) = self.fragments.get_mut(&id) {
             match outcome.outcome_type {
                 OutcomeType::Success => {
@@ -261,6 +589,12 @@ impl MemoryGraph {
                 }
                 _ => {}
             }
+            self.emit(|| MemoryEvent::FragmentReinforced {
+                id,
+                outcome_type: outcome.outcome_type.clone(),
+                confidence: self.fragments[&id].confidence,
+                reinforcement_count: self.fragments[&id].reinforcement_count,
+            });
         }
 
         for ((from_id, to_id), edge) in &mut self.edges {
@@ -274,6 +608,7 @@ impl MemoryGraph {
     }
 
     pub fn decay_memory(&mut self, delta_time: f64) {
+        self.touch();
         let mut to_remove = Vec::new();
 
         for (id, fragment) in &mut self.fragments {
@@ -302,8 +637,20 @@ impl MemoryGraph {
             }
         }
 
+        if !self.observers.is_empty() {
+            let mut decayed: Vec<&MFragment> = self.fragments.values().collect();
+            decayed.sort_by_key(|fragment| fragment.id);
+            for fragment in decayed {
+                self.observers.notify(&MemoryEvent::FragmentDecayed {
+                    id: fragment.id,
+                    salience: fragment.salience,
+                    confidence: fragment.confidence,
+                });
+            }
+        }
+
         for id in to_remove {
-            self.fragments.remove(&id);
+            self.remove_fragment(id);
         }
 
         let mut edges_to_remove = Vec::new();
@@ -315,47 +662,44 @@ impl MemoryGraph {
         }
 
         for key in edges_to_remove {
-            self.edges.remove(&key);
+            self.remove_edge(key);
+        }
+    }
+
+    fn index_keyword(&mut self, keyword: String, id: Uuid) {
+        let tokens = self.tokenizer.tokens(&keyword);
+        let mut keys = vec![normalize_keyword(&keyword)];
+        keys.extend(tokens.iter().map(|token| token.term.clone()));
+        keys.extend(
+            phrases_of(&tokens, self.tokenizer.max_phrase_length)
+                .into_iter()
+                .map(|phrase| phrase.term),
+        );
+
+        for key in keys {
+            if key.is_empty() || self.tokenizer.is_stop_word(&key) {
+                continue;
+            }
+            self.keyword_index.insert(&key);
+            self.activation_index
+                .by_keyword
+                .entry(key)
+                .or_insert_with(HashSet::new)
+                .insert(id);
         }
     }
 
-    fn update_activation_index(&mut self, fragment: &MFragment) {
+    pub(crate) fn update_activation_index(&mut self, fragment: &MFragment) {
+        self.index_type(fragment);
         match &fragment.content {
             FragmentContent::EntityRelation { entity, .. } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(entity.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(entity.to_lowercase())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(entity.clone(), fragment.id);
             }
             FragmentContent::CausalRule {
                 condition, outcome, ..
             } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(condition.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(condition.to_lowercase())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(outcome.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(outcome.to_lowercase())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(condition.clone(), fragment.id);
+                self.index_keyword(outcome.clone(), fragment.id);
             }
             FragmentContent::GoalStrategy { goal, .. } => {
                 let patterns = extract_goal_patterns(goal);
@@ -373,38 +717,11 @@ impl MemoryGraph {
                 value,
                 ..
             } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(person.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(person.to_lowercase())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-
-                self.activation_index
-                    .by_keyword
-                    .entry(fact_type.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(fact_type.to_lowercase())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-
-                self.activation_index
-                    .by_keyword
-                    .entry(value.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(value.to_lowercase())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(person.clone(), fragment.id);
+
+                self.index_keyword(fact_type.clone(), fragment.id);
+
+                self.index_keyword(value.clone(), fragment.id);
 
                 if fact_type.to_lowercase() == "preference" {}
 
@@ -415,49 +732,21 @@ impl MemoryGraph {
                 time_expression,
                 ..
             } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(event.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(time_expression.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(event.clone(), fragment.id);
+                self.index_keyword(time_expression.clone(), fragment.id);
             }
             FragmentContent::SpatialRelation {
                 entity, location, ..
             } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(entity.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(location.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(entity.clone(), fragment.id);
+                self.index_keyword(location.clone(), fragment.id);
             }
             FragmentContent::QuantitativeFact { entity, .. } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(entity.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(entity.clone(), fragment.id);
             }
             FragmentContent::HierarchicalRelation { parent, child, .. } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(parent.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(child.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(parent.clone(), fragment.id);
+                self.index_keyword(child.clone(), fragment.id);
             }
             FragmentContent::SocialRelation {
                 person1,
@@ -465,154 +754,342 @@ impl MemoryGraph {
                 relation_type,
                 ..
             } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(person1.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(person2.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(relation_type.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(person1.clone(), fragment.id);
+                self.index_keyword(person2.clone(), fragment.id);
+                self.index_keyword(relation_type.clone(), fragment.id);
             }
             FragmentContent::OwnershipRelation { owner, owned, .. } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(owner.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(owned.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(owner.clone(), fragment.id);
+                self.index_keyword(owned.clone(), fragment.id);
             }
             FragmentContent::StateTransition {
                 entity, to_state, ..
             } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(entity.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(to_state.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(entity.clone(), fragment.id);
+                self.index_keyword(to_state.clone(), fragment.id);
             }
             FragmentContent::Capability {
                 entity, capability, ..
             } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(entity.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(capability.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(entity.clone(), fragment.id);
+                self.index_keyword(capability.clone(), fragment.id);
             }
             FragmentContent::Belief { entity, belief, .. } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(entity.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(belief.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(entity.clone(), fragment.id);
+                self.index_keyword(belief.clone(), fragment.id);
             }
             FragmentContent::Preference {
                 preference,
                 context,
                 ..
             } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(preference.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(context.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(preference.clone(), fragment.id);
+                self.index_keyword(context.clone(), fragment.id);
             }
             FragmentContent::Constraint {
                 constraint,
                 context,
                 ..
             } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(constraint.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
-                self.activation_index
-                    .by_keyword
-                    .entry(context.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(constraint.clone(), fragment.id);
+                self.index_keyword(context.clone(), fragment.id);
             }
             FragmentContent::ContextSignature { pattern, .. } => {
-                self.activation_index
-                    .by_keyword
-                    .entry(pattern.clone())
-                    .or_insert_with(HashSet::new)
-                    .insert(fragment.id);
+                self.index_keyword(pattern.clone(), fragment.id);
             }
             FragmentContent::SemanticAtom { content, .. } => {
                 for (key, value) in content {
-                    self.activation_index
-                        .by_keyword
-                        .entry(key.clone())
-                        .or_insert_with(HashSet::new)
-                        .insert(fragment.id);
-                    self.activation_index
-                        .by_keyword
-                        .entry(key.to_lowercase())
-                        .or_insert_with(HashSet::new)
-                        .insert(fragment.id);
+                    self.index_keyword(key.clone(), fragment.id);
 
                     if value.len() >= 2 {
-                        self.activation_index
-                            .by_keyword
-                            .entry(value.clone())
-                            .or_insert_with(HashSet::new)
-                            .insert(fragment.id);
-                        self.activation_index
-                            .by_keyword
-                            .entry(value.to_lowercase())
-                            .or_insert_with(HashSet::new)
-                            .insert(fragment.id);
+                        self.index_keyword(value.clone(), fragment.id);
                     }
                 }
             }
         }
     }
 
+    pub(crate) fn index_type(&mut self, fragment: &MFragment) {
+        self.activation_index.by_type.retain(|fragment_type, ids| {
+            if *fragment_type != fragment.fragment_type {
+                ids.remove(&fragment.id);
+            }
+            !ids.is_empty()
+        });
+        self.activation_index
+            .by_type
+            .entry(fragment.fragment_type.clone())
+            .or_default()
+            .insert(fragment.id);
+    }
+
+    pub fn lookup_type(&self, fragment_type: &FragmentType) -> HashSet<Uuid> {
+        self.activation_index
+            .by_type
+            .get(fragment_type)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn lookup_keyword(&self, keyword: &str) -> HashSet<Uuid> {
+        self.activation_index
+            .by_keyword
+            .get(&normalize_keyword(keyword))
+            .cloned()
+            .unwrap_or_default()
+    }
+
     pub fn save(&self, path: impl AsRef<Path>) -> StorageResult<()> {
         save_memory(self, path.as_ref())
     }
 
     pub fn load(path: impl AsRef<Path>) -> StorageResult<Self> {
-        load_memory(path.as_ref())
+        let mut memory = load_memory(path.as_ref())?;
+        memory.domain_terms = normalize_domain_terms(&memory.domain_classifier);
+        if memory.normalization_version < NORMALIZATION_VERSION {
+            memory.migrate_activation_index();
+        }
+        if memory.vector_index.len() < memory.fragments.len()
+            || memory.vector_index.documents.len() < memory.vector_index.len()
+        {
+            memory.rebuild_vector_index();
+        }
+        if memory.text_index.len() < memory.fragments.len() {
+            memory.rebuild_text_index();
+        }
+        if memory.phrase_index.len() < memory.fragments.len() {
+            memory.rebuild_phrase_index();
+        }
+        if memory.activation_index.by_domain.is_empty() && !memory.fragments.is_empty() {
+            memory.reclassify_domains();
+        }
+        if memory.activation_index.by_type.is_empty() && !memory.fragments.is_empty() {
+            let fragments: Vec<MFragment> = memory.fragments.values().cloned().collect();
+            for fragment in &fragments {
+                memory.index_type(fragment);
+            }
+        }
+        if memory.keyword_index.keys.is_empty() && !memory.activation_index.by_keyword.is_empty() {
+            memory.rebuild_keyword_index();
+        }
+        memory.recount_footprint();
+        Ok(memory)
+    }
+}
+
+pub fn content_fields(content: &FragmentContent) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = Vec::new();
+    let mut push = |name: &str, value: &str| fields.push((name.to_string(), value.to_string()));
+
+    match content {
+        FragmentContent::EntityRelation {
+            entity,
+            relation,
+            target,
+        } => {
+            push("entity", entity);
+            push("relation", relation);
+            push("target", target);
+        }
+        FragmentContent::CausalRule {
+            condition, outcome, ..
+        } => {
+            push("condition", condition);
+            push("outcome", outcome);
+        }
+        FragmentContent::GoalStrategy {
+            goal,
+            strategy,
+            success_rate,
+        } => {
+            push("goal", goal);
+            push("strategy", strategy);
+            push("success_rate", &success_rate.to_string());
+        }
+        FragmentContent::Constraint {
+            constraint,
+            context,
+            severity,
+        } => {
+            push("constraint", constraint);
+            push("context", context);
+            push("severity", &severity.to_string());
+        }
+        FragmentContent::Preference {
+            preference,
+            weight,
+            context,
+        } => {
+            push("preference", preference);
+            push("weight", &weight.to_string());
+            push("context", context);
+        }
+        FragmentContent::ContextSignature { pattern, .. } => {
+            push("pattern", pattern);
+        }
+        FragmentContent::PersonalFact {
+            person,
+            fact_type,
+            value,
+            ..
+        } => {
+            push("person", person);
+            push("fact_type", fact_type);
+            push("value", value);
+        }
+        FragmentContent::TemporalEvent {
+            event,
+            time_expression,
+            duration,
+            frequency,
+            ..
+        } => {
+            push("event", event);
+            push("time_expression", time_expression);
+            if let Some(duration) = duration {
+                push("duration", duration);
+            }
+            if let Some(frequency) = frequency {
+                push("frequency", frequency);
+            }
+        }
+        FragmentContent::SpatialRelation {
+            entity,
+            location,
+            relation_type,
+            distance,
+            ..
+        } => {
+            push("entity", entity);
+            push("location", location);
+            push("relation_type", relation_type);
+            if let Some(distance) = distance {
+                push("distance", distance);
+            }
+        }
+        FragmentContent::QuantitativeFact {
+            entity,
+            quantity,
+            unit,
+            comparison,
+            reference,
+            ..
+        } => {
+            push("entity", entity);
+            push("quantity", &quantity.to_string());
+            if let Some(unit) = unit {
+                push("unit", unit);
+            }
+            if let Some(comparison) = comparison {
+                push("comparison", comparison);
+            }
+            if let Some(reference) = reference {
+                push("reference", reference);
+            }
+        }
+        FragmentContent::HierarchicalRelation {
+            parent,
+            child,
+            relation_type,
+            level,
+            ..
+        } => {
+            push("parent", parent);
+            push("child", child);
+            push("relation_type", relation_type);
+            if let Some(level) = level {
+                push("level", &level.to_string());
+            }
+        }
+        FragmentContent::SocialRelation {
+            person1,
+            person2,
+            relation_type,
+            strength,
+            context,
+            ..
+        } => {
+            push("person1", person1);
+            push("person2", person2);
+            push("relation_type", relation_type);
+            push("strength", &strength.to_string());
+            if let Some(context) = context {
+                push("context", context);
+            }
+        }
+        FragmentContent::OwnershipRelation {
+            owner,
+            owned,
+            relation_type,
+            ..
+        } => {
+            push("owner", owner);
+            push("owned", owned);
+            push("relation_type", relation_type);
+        }
+        FragmentContent::StateTransition {
+            entity,
+            from_state,
+            to_state,
+            condition,
+            ..
+        } => {
+            push("entity", entity);
+            push("from_state", from_state);
+            push("to_state", to_state);
+            if let Some(condition) = condition {
+                push("condition", condition);
+            }
+        }
+        FragmentContent::Capability {
+            entity,
+            capability,
+            level,
+            context,
+            ..
+        } => {
+            push("entity", entity);
+            push("capability", capability);
+            if let Some(level) = level {
+                push("level", &level.to_string());
+            }
+            if let Some(context) = context {
+                push("context", context);
+            }
+        }
+        FragmentContent::Belief {
+            entity,
+            belief,
+            evidence,
+            context,
+            ..
+        } => {
+            push("entity", entity);
+            push("belief", belief);
+            if let Some(evidence) = evidence {
+                push("evidence", evidence);
+            }
+            if let Some(context) = context {
+                push("context", context);
+            }
+        }
+        FragmentContent::SemanticAtom {
+            atom_type, content, ..
+        } => {
+            push("atom_type", &format!("{:?}", atom_type));
+            let mut entries: Vec<(&String, &String)> = content.iter().collect();
+            entries.sort();
+            for (key, value) in entries {
+                push(key, value);
+            }
+        }
     }
+
+    fields
 }
 
 fn extract_goal_patterns(goal: &str) -> Vec<String> {
-    let goal_lower = goal.to_lowercase();
+    let goal_lower = fold_case(goal);
     let mut patterns = Vec::new();
 
     if goal_lower.contains("debug") {
@@ -645,7 +1122,10 @@ fn extract_goal_patterns(goal: &str) -> Vec<String> {
         patterns.push("personal".to_string());
     }
 
-    let words: Vec<&str> = goal_lower.split_whitespace().collect();
+    let words: Vec<&str> = goal_lower
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+        .collect();
     for word in &words {
         if word.len() >= 3 {
             patterns.push(word.to_string());
@@ -658,27 +1138,41 @@ fn extract_goal_patterns(goal: &str) -> Vec<String> {
 
     patterns.push(goal_lower);
     patterns
+        .iter()
+        .map(|pattern| normalize_keyword(pattern))
+        .collect()
 }
 
-fn calculate_relevance_score(fragment: &MFragment, context: &ContextVector) -> f64 {
-    let mut score = 0.0;
+fn score_components(
+    fragment: &MFragment,
+    context: &ContextVector,
+    text_match: f64,
+) -> ScoreBreakdown {
     let now = current_timestamp();
-
-    score += fragment.confidence * 0.3;
+    let mut breakdown = ScoreBreakdown {
+        text_match,
+        confidence: fragment.confidence * 0.3,
+        salience: fragment.salience * 0.2,
+        ..ScoreBreakdown::default()
+    };
 
     if fragment.last_activated > 0.0 {
         let recency_hours = (now - fragment.last_activated) / 3600.0;
         let recency_factor = (-recency_hours / 24.0).exp();
-        score += recency_factor * 0.2;
+        breakdown.recency = recency_factor * 0.2;
     }
 
-    score += fragment.salience * 0.2;
-
     let emotion_match = 1.0 - (fragment.emotional_tag - context.emotional_bias.frustration).abs();
-    score += emotion_match * 0.1;
+    breakdown.emotion = emotion_match * 0.1;
 
     let reinforcement_factor = (fragment.reinforcement_count as f64 / 100.0).min(1.0);
-    score += reinforcement_factor * 0.2;
-
-    score
+    breakdown.reinforcement = reinforcement_factor * 0.2;
+
+    breakdown.relevance = breakdown.text_match
+        + breakdown.confidence
+        + breakdown.recency
+        + breakdown.salience
+        + breakdown.emotion
+        + breakdown.reinforcement;
+    breakdown
 }
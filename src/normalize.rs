@@ -0,0 +1,400 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::types::*;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+pub const NORMALIZATION_VERSION: u32 = 1;
+
+const IRREGULAR_FORMS: &[(&str, &str)] = &[
+    ("am", "be"),
+    ("is", "be"),
+    ("are", "be"),
+    ("was", "be"),
+    ("were", "be"),
+    ("been", "be"),
+    ("being", "be"),
+    ("has", "have"),
+    ("had", "have"),
+    ("having", "have"),
+    ("does", "do"),
+    ("did", "do"),
+    ("done", "do"),
+    ("doing", "do"),
+    ("went", "go"),
+    ("gone", "go"),
+    ("goes", "go"),
+    ("ran", "run"),
+    ("ate", "eat"),
+    ("eaten", "eat"),
+    ("saw", "see"),
+    ("seen", "see"),
+    ("said", "say"),
+    ("says", "say"),
+    ("made", "make"),
+    ("took", "take"),
+    ("taken", "take"),
+    ("came", "come"),
+    ("knew", "know"),
+    ("known", "know"),
+    ("got", "get"),
+    ("gotten", "get"),
+    ("gave", "give"),
+    ("given", "give"),
+    ("found", "find"),
+    ("told", "tell"),
+    ("felt", "feel"),
+    ("kept", "keep"),
+    ("left", "leave"),
+    ("thought", "think"),
+    ("bought", "buy"),
+    ("brought", "bring"),
+    ("taught", "teach"),
+    ("caught", "catch"),
+    ("wrote", "write"),
+    ("written", "write"),
+    ("spoke", "speak"),
+    ("spoken", "speak"),
+    ("began", "begin"),
+    ("begun", "begin"),
+    ("drove", "drive"),
+    ("driven", "drive"),
+    ("flew", "fly"),
+    ("flown", "fly"),
+    ("grew", "grow"),
+    ("grown", "grow"),
+    ("met", "meet"),
+    ("paid", "pay"),
+    ("sold", "sell"),
+    ("sent", "send"),
+    ("built", "build"),
+    ("lost", "lose"),
+    ("held", "hold"),
+    ("stood", "stand"),
+    ("understood", "understand"),
+    ("children", "child"),
+    ("people", "person"),
+    ("men", "man"),
+    ("women", "woman"),
+    ("mice", "mouse"),
+    ("geese", "goose"),
+    ("feet", "foot"),
+    ("teeth", "tooth"),
+    ("better", "good"),
+    ("best", "good"),
+    ("worse", "bad"),
+    ("worst", "bad"),
+];
+
+pub fn fold_case(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            'ß' | 'ẞ' => folded.push_str("ss"),
+            'ς' => folded.push('σ'),
+            'ſ' => folded.push('s'),
+            'ﬀ' => folded.push_str("ff"),
+            'ﬁ' => folded.push_str("fi"),
+            'ﬂ' => folded.push_str("fl"),
+            'ﬃ' => folded.push_str("ffi"),
+            'ﬄ' => folded.push_str("ffl"),
+            'ﬅ' | 'ﬆ' => folded.push_str("st"),
+            _ => folded.extend(c.to_lowercase()),
+        }
+    }
+    folded
+}
+
+pub fn lemmatize(word: &str) -> Option<&'static str> {
+    IRREGULAR_FORMS
+        .iter()
+        .find(|(form, _)| *form == word)
+        .map(|(_, lemma)| *lemma)
+}
+
+pub fn normalize_term(word: &str) -> String {
+    let folded = fold_case(word);
+    if let Some(lemma) = lemmatize(&folded) {
+        return lemma.to_string();
+    }
+    if folded.len() <= 2 || !folded.chars().all(|c| c.is_ascii_lowercase()) {
+        return folded;
+    }
+    porter_stem(&folded)
+}
+
+pub fn normalize_keyword(keyword: &str) -> String {
+    let mut normalized = String::with_capacity(keyword.len());
+    let mut word = String::new();
+
+    for c in keyword.trim().chars() {
+        if c.is_alphanumeric() {
+            word.push(c);
+        } else {
+            if !word.is_empty() {
+                normalized.push_str(&normalize_term(&word));
+                word.clear();
+            }
+            normalized.push_str(&fold_case(&c.to_string()));
+        }
+    }
+    if !word.is_empty() {
+        normalized.push_str(&normalize_term(&word));
+    }
+
+    normalized
+}
+
+pub fn normalize_terms(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(normalize_term)
+        .collect()
+}
+
+pub fn porter_stem(word: &str) -> String {
+    let mut stemmer = Stemmer {
+        b: word.as_bytes().to_vec(),
+        k: 0,
+    };
+    if stemmer.b.len() <= 2 {
+        return word.to_string();
+    }
+    stemmer.step1ab();
+    stemmer.step1c();
+    stemmer.step2();
+    stemmer.step3();
+    stemmer.step4();
+    stemmer.step5();
+    String::from_utf8(stemmer.b).unwrap_or_else(|_| word.to_string())
+}
+
+struct Stemmer {
+    b: Vec<u8>,
+    k: usize,
+}
+
+impl Stemmer {
+    fn is_consonant(&self, i: usize) -> bool {
+        match self.b[i] {
+            b'a' | b'e' | b'i' | b'o' | b'u' => false,
+            b'y' => i == 0 || !self.is_consonant(i - 1),
+            _ => true,
+        }
+    }
+
+    fn measure(&self) -> usize {
+        let mut n = 0;
+        let mut i = 0;
+        while i < self.k && self.is_consonant(i) {
+            i += 1;
+        }
+        while i < self.k {
+            while i < self.k && !self.is_consonant(i) {
+                i += 1;
+            }
+            if i >= self.k {
+                break;
+            }
+            n += 1;
+            while i < self.k && self.is_consonant(i) {
+                i += 1;
+            }
+        }
+        n
+    }
+
+    fn stem_has_vowel(&self) -> bool {
+        (0..self.k).any(|i| !self.is_consonant(i))
+    }
+
+    fn double_consonant(&self, end: usize) -> bool {
+        end >= 1 && self.b[end] == self.b[end - 1] && self.is_consonant(end)
+    }
+
+    fn cvc(&self, end: usize) -> bool {
+        if end < 2
+            || !self.is_consonant(end)
+            || self.is_consonant(end - 1)
+            || !self.is_consonant(end - 2)
+        {
+            return false;
+        }
+        !matches!(self.b[end], b'w' | b'x' | b'y')
+    }
+
+    fn ends(&mut self, suffix: &str) -> bool {
+        let suffix = suffix.as_bytes();
+        if suffix.len() > self.b.len() || !self.b.ends_with(suffix) {
+            return false;
+        }
+        self.k = self.b.len() - suffix.len();
+        true
+    }
+
+    fn set_to(&mut self, replacement: &str) {
+        self.b.truncate(self.k);
+        self.b.extend_from_slice(replacement.as_bytes());
+    }
+
+    fn replace_if_measured(&mut self, replacement: &str) {
+        if self.measure() > 0 {
+            self.set_to(replacement);
+        }
+    }
+
+    fn step1ab(&mut self) {
+        if self.b.ends_with(b"s") {
+            if self.ends("sses") {
+                self.set_to("ss");
+            } else if self.ends("ies") {
+                self.set_to("i");
+            } else if !self.b.ends_with(b"ss") {
+                self.b.pop();
+            }
+        }
+
+        if self.ends("eed") {
+            if self.measure() > 0 {
+                self.set_to("ee");
+            }
+        } else if (self.ends("ed") || self.ends("ing")) && self.stem_has_vowel() {
+            self.b.truncate(self.k);
+            if self.ends("at") {
+                self.set_to("ate");
+            } else if self.ends("bl") {
+                self.set_to("ble");
+            } else if self.ends("iz") {
+                self.set_to("ize");
+            } else if self.double_consonant(self.b.len() - 1) {
+                if !matches!(self.b[self.b.len() - 1], b'l' | b's' | b'z') {
+                    self.b.pop();
+                }
+            } else {
+                self.k = self.b.len();
+                if self.measure() == 1 && self.cvc(self.b.len() - 1) {
+                    self.b.push(b'e');
+                }
+            }
+        }
+    }
+
+    fn step1c(&mut self) {
+        if self.ends("y") && self.stem_has_vowel() {
+            self.set_to("i");
+        }
+    }
+
+    fn step2(&mut self) {
+        const RULES: &[(&str, &str)] = &[
+            ("ational", "ate"),
+            ("tional", "tion"),
+            ("enci", "ence"),
+            ("anci", "ance"),
+            ("izer", "ize"),
+            ("bli", "ble"),
+            ("alli", "al"),
+            ("entli", "ent"),
+            ("eli", "e"),
+            ("ousli", "ous"),
+            ("ization", "ize"),
+            ("ation", "ate"),
+            ("ator", "ate"),
+            ("alism", "al"),
+            ("iveness", "ive"),
+            ("fulness", "ful"),
+            ("ousness", "ous"),
+            ("aliti", "al"),
+            ("iviti", "ive"),
+            ("biliti", "ble"),
+            ("logi", "log"),
+        ];
+        self.apply_rules(RULES);
+    }
+
+    fn step3(&mut self) {
+        const RULES: &[(&str, &str)] = &[
+            ("icate", "ic"),
+            ("ative", ""),
+            ("alize", "al"),
+            ("iciti", "ic"),
+            ("ical", "ic"),
+            ("ful", ""),
+            ("ness", ""),
+        ];
+        self.apply_rules(RULES);
+    }
+
+    fn apply_rules(&mut self, rules: &[(&str, &str)]) {
+        for (suffix, replacement) in rules {
+            if self.ends(suffix) {
+                self.replace_if_measured(replacement);
+                return;
+            }
+        }
+    }
+
+    fn step4(&mut self) {
+        const SUFFIXES: &[&str] = &[
+            "al", "ance", "ence", "er", "ic", "able", "ible", "ant", "ement", "ment", "ent", "ion",
+            "ou", "ism", "ate", "iti", "ous", "ive", "ize",
+        ];
+        let mut longest: Option<&str> = None;
+        for suffix in SUFFIXES {
+            if self.b.ends_with(suffix.as_bytes()) && longest.is_none_or(|l| suffix.len() > l.len())
+            {
+                longest = Some(suffix);
+            }
+        }
+
+        if let Some(suffix) = longest {
+            self.ends(suffix);
+            if suffix == "ion" && !(self.k > 0 && matches!(self.b[self.k - 1], b's' | b't')) {
+                return;
+            }
+            if self.measure() > 1 {
+                self.b.truncate(self.k);
+            }
+        }
+    }
+
+    fn step5(&mut self) {
+        if self.ends("e") {
+            let m = self.measure();
+            if m > 1 || (m == 1 && !self.cvc(self.k.saturating_sub(1)) && self.k > 0) {
+                self.b.truncate(self.k);
+            }
+        }
+
+        let last = self.b.len() - 1;
+        self.k = self.b.len();
+        if self.b[last] == b'l' && self.double_consonant(last) && self.measure() > 1 {
+            self.b.pop();
+        }
+    }
+}
+
+impl MemoryGraph {
+    pub fn migrate_activation_index(&mut self) {
+        for index in [
+            &mut self.activation_index.by_goal,
+            &mut self.activation_index.by_keyword,
+        ] {
+            let mut migrated: HashMap<String, HashSet<Uuid>> = HashMap::new();
+            for (key, ids) in index.drain() {
+                migrated
+                    .entry(normalize_keyword(&key))
+                    .or_default()
+                    .extend(ids);
+            }
+            *index = migrated;
+        }
+
+        let fragments: Vec<MFragment> = self.fragments.values().cloned().collect();
+        for fragment in &fragments {
+            self.update_activation_index(fragment);
+        }
+        self.rebuild_keyword_index();
+        self.rebuild_text_index();
+        self.normalization_version = NORMALIZATION_VERSION;
+    }
+}
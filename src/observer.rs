@@ -0,0 +1,50 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::types::*;
+use std::sync::Arc;
+
+impl MemoryObservers {
+    pub fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    pub fn subscribe(&mut self, hook: MemoryHook) -> u64 {
+        self.next_id += 1;
+        self.hooks.push((self.next_id, hook));
+        self.next_id
+    }
+
+    pub fn unsubscribe(&mut self, subscription: u64) -> bool {
+        let before = self.hooks.len();
+        self.hooks.retain(|(id, _)| *id != subscription);
+        self.hooks.len() != before
+    }
+
+    pub fn notify(&self, event: &MemoryEvent) {
+        for (_, hook) in &self.hooks {
+            hook(event);
+        }
+    }
+}
+
+impl MemoryGraph {
+    pub fn subscribe<F>(&mut self, hook: F) -> u64
+    where
+        F: Fn(&MemoryEvent) + Send + Sync + 'static,
+    {
+        self.observers.subscribe(Arc::new(hook))
+    }
+
+    pub fn unsubscribe(&mut self, subscription: u64) -> bool {
+        self.observers.unsubscribe(subscription)
+    }
+
+    pub(crate) fn emit(&self, event: impl FnOnce() -> MemoryEvent) {
+        if !self.observers.is_empty() {
+            self.observers.notify(&event());
+        }
+    }
+}
@@ -0,0 +1,411 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::types::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+use uuid::Uuid;
+
+pub fn optimize_eeg(eeg: &mut EEG, memory: &MemoryGraph) -> OptimizationReport {
+    optimize_eeg_with_config(eeg, memory, &OptimizerConfig::default())
+}
+
+pub fn optimize_eeg_with_config(
+    eeg: &mut EEG,
+    memory: &MemoryGraph,
+    config: &OptimizerConfig,
+) -> OptimizationReport {
+    let mut report = OptimizationReport {
+        nodes_before: eeg.nodes.len(),
+        ..OptimizationReport::default()
+    };
+
+    if config.eliminate_dead_branches {
+        report.dead_branches_removed = eliminate_dead_branches(eeg);
+    }
+    if config.eliminate_unreachable {
+        report.unreachable_removed = eliminate_unreachable(eeg);
+    }
+    if config.merge_common_subgraphs {
+        report.nodes_merged = merge_common_subgraphs(eeg, memory);
+    }
+    if config.fuse_chains {
+        report.nodes_fused = fuse_chains(eeg);
+    }
+
+    refresh_exit_points(eeg);
+    refresh_metadata(eeg);
+    report.nodes_after = eeg.nodes.len();
+    report
+}
+
+fn successors(eeg: &EEG, id: Uuid) -> Vec<Uuid> {
+    let mut next: Vec<Uuid> = eeg
+        .edges
+        .iter()
+        .filter(|edge| edge.from_node == id)
+        .map(|edge| edge.to_node)
+        .collect();
+    match eeg.nodes.get(&id).map(|node| &node.content) {
+        Some(NodeContent::Decision { branches, .. }) => {
+            next.extend(branches.iter().map(|branch| branch.target_node))
+        }
+        Some(NodeContent::Conflict {
+            selected_fragment: Some(selected),
+            ..
+        }) => next.push(*selected),
+        _ => {}
+    }
+    next
+}
+
+fn reachable_from(eeg: &EEG, start: Uuid) -> HashSet<Uuid> {
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(id) = queue.pop_front() {
+        if !eeg.nodes.contains_key(&id) || !seen.insert(id) {
+            continue;
+        }
+        queue.extend(successors(eeg, id));
+    }
+    seen
+}
+
+fn remove_node(eeg: &mut EEG, id: Uuid) {
+    eeg.nodes.remove(&id);
+    eeg.edges
+        .retain(|edge| edge.from_node != id && edge.to_node != id);
+}
+
+fn eliminate_dead_branches(eeg: &mut EEG) -> usize {
+    let present: HashSet<Uuid> = eeg.nodes.keys().copied().collect();
+    let mut removed = 0;
+    let mut dropped_targets: Vec<(Uuid, Uuid)> = Vec::new();
+
+    for node in eeg.nodes.values_mut() {
+        if let NodeContent::Decision { branches, .. } = &mut node.content {
+            let before = branches.len();
+            let mut kept = 0;
+            branches.retain(|branch| {
+                let live =
+                    present.contains(&branch.target_node) && (kept == 0 || branch.weight > 0.0);
+                if live {
+                    kept += 1;
+                } else {
+                    dropped_targets.push((node.id, branch.target_node));
+                }
+                live
+            });
+            removed += before - branches.len();
+
+            let total: f64 = branches.iter().map(|branch| branch.weight).sum();
+            if total > 0.0 {
+                for branch in branches.iter_mut() {
+                    branch.weight /= total;
+                }
+            }
+        }
+    }
+
+    for (decision, target) in dropped_targets {
+        let kept = match eeg.nodes.get(&decision).map(|node| &node.content) {
+            Some(NodeContent::Decision { branches, .. }) => {
+                branches.iter().any(|branch| branch.target_node == target)
+            }
+            _ => false,
+        };
+        if !kept {
+            eeg.edges
+                .retain(|edge| !(edge.from_node == decision && edge.to_node == target));
+        }
+    }
+    removed
+}
+
+fn eliminate_unreachable(eeg: &mut EEG) -> usize {
+    if !eeg.nodes.contains_key(&eeg.entry_point) {
+        return 0;
+    }
+    let live = reachable_from(eeg, eeg.entry_point);
+    let mut dead: Vec<Uuid> = eeg
+        .nodes
+        .keys()
+        .copied()
+        .filter(|id| !live.contains(id))
+        .collect();
+    dead.sort();
+    for &id in &dead {
+        remove_node(eeg, id);
+    }
+    eeg.edges
+        .retain(|edge| live.contains(&edge.from_node) && live.contains(&edge.to_node));
+    dead.len()
+}
+
+fn outgoing_signature(eeg: &EEG, id: Uuid) -> String {
+    eeg.edges
+        .iter()
+        .filter(|edge| edge.from_node == id)
+        .map(|edge| {
+            format!(
+                "{}|{:?}|{:?}|{}",
+                edge.to_node, edge.edge_type, edge.condition, edge.weight
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn fragment_signature(memory: &MemoryGraph, id: &Uuid) -> String {
+    match memory.fragments.get(id) {
+        Some(fragment) => format!("{:?}|{:?}", fragment.fragment_type, fragment.content),
+        None => id.to_string(),
+    }
+}
+
+fn content_signature(node: &EEGNode, memory: &MemoryGraph) -> String {
+    match &node.content {
+        NodeContent::Fragment { fragment_id, .. } => fragment_signature(memory, fragment_id),
+        NodeContent::FusedChain { fragment_ids, .. } => fragment_ids
+            .iter()
+            .map(|id| fragment_signature(memory, id))
+            .collect::<Vec<_>>()
+            .join(";"),
+        content => format!("{:?}", content),
+    }
+}
+
+fn node_signature(eeg: &EEG, node: &EEGNode, memory: &MemoryGraph) -> String {
+    format!(
+        "{:?}|{}|{}",
+        node.node_type,
+        content_signature(node, memory),
+        outgoing_signature(eeg, node.id)
+    )
+}
+
+fn redirect(eeg: &mut EEG, from: Uuid, to: Uuid) {
+    for edge in eeg.edges.iter_mut() {
+        if edge.to_node == from {
+            edge.to_node = to;
+        }
+    }
+    let mut seen = HashSet::new();
+    eeg.edges.retain(|edge| {
+        seen.insert((
+            edge.from_node,
+            edge.to_node,
+            format!("{:?}", edge.edge_type),
+            edge.condition.clone(),
+        ))
+    });
+
+    for node in eeg.nodes.values_mut() {
+        match &mut node.content {
+            NodeContent::Decision { branches, .. } => {
+                for branch in branches.iter_mut() {
+                    if branch.target_node == from {
+                        branch.target_node = to;
+                    }
+                }
+                let mut merged: Vec<Branch> = Vec::new();
+                for branch in branches.drain(..) {
+                    match merged
+                        .iter_mut()
+                        .find(|b| b.target_node == branch.target_node)
+                    {
+                        Some(existing) => existing.weight += branch.weight,
+                        None => merged.push(branch),
+                    }
+                }
+                *branches = merged;
+            }
+            NodeContent::Conflict {
+                selected_fragment: Some(selected),
+                ..
+            } if *selected == from => *selected = to,
+            _ => {}
+        }
+    }
+
+    if eeg.entry_point == from {
+        eeg.entry_point = to;
+    }
+    for exit in eeg.exit_points.iter_mut() {
+        if *exit == from {
+            *exit = to;
+        }
+    }
+    let mut seen = HashSet::new();
+    eeg.exit_points.retain(|id| seen.insert(*id));
+}
+
+fn merge_common_subgraphs(eeg: &mut EEG, memory: &MemoryGraph) -> usize {
+    let mut merged = 0;
+    loop {
+        let mut ids: Vec<Uuid> = eeg.nodes.keys().copied().collect();
+        ids.sort();
+
+        let mut groups: HashMap<String, Vec<Uuid>> = HashMap::new();
+        for &id in &ids {
+            groups
+                .entry(node_signature(eeg, &eeg.nodes[&id], memory))
+                .or_default()
+                .push(id);
+        }
+
+        let mut candidates: Vec<Vec<Uuid>> = groups
+            .into_values()
+            .filter(|group| group.len() > 1)
+            .collect();
+        candidates.sort();
+
+        let pair = candidates.iter().find_map(|group| {
+            group.iter().enumerate().find_map(|(i, &keep)| {
+                let from_keep = reachable_from(eeg, keep);
+                group[i + 1..]
+                    .iter()
+                    .copied()
+                    .find(|&other| {
+                        !from_keep.contains(&other) && !reachable_from(eeg, other).contains(&keep)
+                    })
+                    .map(|other| (keep, other))
+            })
+        });
+
+        match pair {
+            Some((keep, duplicate)) => {
+                eeg.edges.retain(|edge| edge.from_node != duplicate);
+                redirect(eeg, duplicate, keep);
+                eeg.nodes.remove(&duplicate);
+                merged += 1;
+            }
+            None => return merged,
+        }
+    }
+}
+
+fn chain_members(node: &EEGNode) -> Option<(Vec<Uuid>, Vec<Uuid>)> {
+    match &node.content {
+        NodeContent::Fragment { fragment_id, .. } => Some((vec![node.id], vec![*fragment_id])),
+        NodeContent::FusedChain {
+            node_ids,
+            fragment_ids,
+        } => Some((node_ids.clone(), fragment_ids.clone())),
+        _ => None,
+    }
+}
+
+fn references(eeg: &EEG) -> HashMap<Uuid, usize> {
+    let mut counts: HashMap<Uuid, usize> = HashMap::new();
+    for id in eeg.nodes.keys() {
+        for next in successors(eeg, *id) {
+            *counts.entry(next).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+fn fuse_chains(eeg: &mut EEG) -> usize {
+    let mut fused = 0;
+    let mut ids: Vec<Uuid> = eeg.nodes.keys().copied().collect();
+    ids.sort();
+
+    for id in ids {
+        while let Some(head) = eeg.nodes.get(&id) {
+            let outgoing: Vec<&EEGEdge> = eeg
+                .edges
+                .iter()
+                .filter(|edge| edge.from_node == id)
+                .collect();
+            if outgoing.len() != 1 {
+                break;
+            }
+            let next = outgoing[0].to_node;
+            let tail = match eeg.nodes.get(&next) {
+                Some(node) => node,
+                None => break,
+            };
+            if next == id
+                || next == eeg.entry_point
+                || references(eeg).get(&next).copied().unwrap_or(0) != 1
+            {
+                break;
+            }
+            let ((mut node_ids, mut fragment_ids), (tail_nodes, tail_fragments)) =
+                match (chain_members(head), chain_members(tail)) {
+                    (Some(head), Some(tail)) => (head, tail),
+                    _ => break,
+                };
+
+            let (head_weight, tail_weight) = (node_ids.len() as f64, tail_nodes.len() as f64);
+            let confidence = (head.confidence * head_weight + tail.confidence * tail_weight)
+                / (head_weight + tail_weight);
+            let execution_cost = head.execution_cost + tail.execution_cost;
+            let mut source_fragments = head.source_fragments.clone();
+            source_fragments.extend(tail.source_fragments.iter().copied());
+            node_ids.extend(tail_nodes);
+            fragment_ids.extend(tail_fragments);
+
+            eeg.edges
+                .retain(|edge| !(edge.from_node == id && edge.to_node == next));
+            for edge in eeg.edges.iter_mut() {
+                if edge.from_node == next {
+                    edge.from_node = id;
+                }
+            }
+            eeg.nodes.remove(&next);
+            for exit in eeg.exit_points.iter_mut() {
+                if *exit == next {
+                    *exit = id;
+                }
+            }
+
+            let node = eeg.nodes.get_mut(&id).unwrap();
+            node.node_type = NodeType::FragmentNode;
+            node.content = NodeContent::FusedChain {
+                node_ids,
+                fragment_ids,
+            };
+            node.confidence = confidence;
+            node.execution_cost = execution_cost;
+            node.source_fragments = source_fragments;
+            fused += 1;
+        }
+    }
+    fused
+}
+
+fn refresh_exit_points(eeg: &mut EEG) {
+    let present: HashSet<Uuid> = eeg.nodes.keys().copied().collect();
+    let mut seen = HashSet::new();
+    eeg.exit_points
+        .retain(|id| present.contains(id) && seen.insert(*id));
+    if eeg.exit_points.is_empty() {
+        let mut exits: Vec<Uuid> = eeg
+            .nodes
+            .keys()
+            .copied()
+            .filter(|id| !eeg.edges.iter().any(|edge| edge.from_node == *id))
+            .collect();
+        exits.sort();
+        eeg.exit_points = exits;
+    }
+}
+
+fn refresh_metadata(eeg: &mut EEG) {
+    let weight = |node: &EEGNode| match &node.content {
+        NodeContent::FusedChain { node_ids, .. } => node_ids.len(),
+        _ => 1,
+    };
+    let count: usize = eeg.nodes.values().map(weight).sum();
+
+    eeg.metadata.fragment_count = count;
+    eeg.metadata.estimated_execution_time =
+        eeg.nodes.values().map(|node| node.execution_cost).sum();
+    if count > 0 {
+        eeg.metadata.confidence_score = eeg
+            .nodes
+            .values()
+            .map(|node| node.confidence * weight(node) as f64)
+            .sum::<f64>()
+            / count as f64;
+    }
+}
@@ -0,0 +1,317 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::compiler::*;
+use crate::conflicts::*;
+use crate::explain::compiled_fragments;
+use crate::optimizer::optimize_eeg_with_config;
+use crate::types::*;
+use std::collections::HashSet;
+use std::time::Instant;
+use uuid::Uuid;
+
+pub trait CompilerPass {
+    fn name(&self) -> &str;
+
+    fn run(&self, ir: &mut CompilationIR, context: &ContextVector, memory: &MemoryGraph);
+}
+
+impl CompilationIR {
+    pub fn from_activation(context: &ContextVector, memory: &mut MemoryGraph) -> CompilationIR {
+        let activated = memory.activate_fragments(context);
+        CompilationIR::from_activated(activated, context, memory)
+    }
+
+    pub fn from_activated(
+        activated: HashSet<Uuid>,
+        context: &ContextVector,
+        memory: &MemoryGraph,
+    ) -> CompilationIR {
+        let required: HashSet<Uuid> = memory.required_fragments(context).into_iter().collect();
+
+        let mut ids: Vec<Uuid> = activated
+            .iter()
+            .copied()
+            .filter(|id| memory.fragments.contains_key(id))
+            .collect();
+        ids.sort();
+
+        let nodes = ids
+            .into_iter()
+            .enumerate()
+            .map(|(order, id)| IRNode {
+                id,
+                node_type: NodeType::FragmentNode,
+                order,
+                confidence: memory.fragments[&id].confidence,
+                execution_cost: 1.0,
+                content: None,
+            })
+            .collect();
+
+        CompilationIR {
+            activated,
+            required,
+            nodes,
+        }
+    }
+}
+
+pub struct ConflictResolutionPass<'a> {
+    pub resolver: Box<dyn ConflictResolver + 'a>,
+}
+
+impl<'a> ConflictResolutionPass<'a> {
+    pub fn new(resolver: Box<dyn ConflictResolver + 'a>) -> Self {
+        ConflictResolutionPass { resolver }
+    }
+}
+
+impl CompilerPass for ConflictResolutionPass<'_> {
+    fn name(&self) -> &str {
+        "resolve_conflicts"
+    }
+
+    fn run(&self, ir: &mut CompilationIR, _context: &ContextVector, memory: &MemoryGraph) {
+        ir.nodes = resolve_conflicts(&ir.nodes, memory, &ir.required, self.resolver.as_ref());
+    }
+}
+
+pub struct GapFillingPass;
+
+impl CompilerPass for GapFillingPass {
+    fn name(&self) -> &str {
+        "fill_gaps"
+    }
+
+    fn run(&self, ir: &mut CompilationIR, context: &ContextVector, memory: &MemoryGraph) {
+        ir.nodes = fill_gaps(&ir.nodes, &ir.activated, memory, context);
+    }
+}
+
+pub struct TopologicalOrderPass;
+
+impl CompilerPass for TopologicalOrderPass {
+    fn name(&self) -> &str {
+        "order_fragments"
+    }
+
+    fn run(&self, ir: &mut CompilationIR, _context: &ContextVector, memory: &MemoryGraph) {
+        ir.nodes = order_fragments(&ir.nodes, memory);
+    }
+}
+
+pub struct BranchingPass;
+
+impl CompilerPass for BranchingPass {
+    fn name(&self) -> &str {
+        "add_branching"
+    }
+
+    fn run(&self, ir: &mut CompilationIR, context: &ContextVector, memory: &MemoryGraph) {
+        ir.nodes = add_branching(&ir.nodes, memory, context);
+    }
+}
+
+impl CompilerPass for PruningPass {
+    fn name(&self) -> &str {
+        "prune_resources"
+    }
+
+    fn run(&self, ir: &mut CompilationIR, context: &ContextVector, memory: &MemoryGraph) {
+        ir.nodes = prune_resources(&ir.nodes, context, memory, &ir.required, &self.cost_model);
+    }
+}
+
+pub struct PassManager<'a> {
+    passes: Vec<Box<dyn CompilerPass + 'a>>,
+    disabled: HashSet<String>,
+    optimizer: Option<OptimizerConfig>,
+    stats: Vec<PassStats>,
+}
+
+impl Default for PassManager<'_> {
+    fn default() -> Self {
+        PassManager::standard(Box::new(ConfidenceResolver), CostModel::default())
+    }
+}
+
+impl<'a> PassManager<'a> {
+    pub fn new() -> Self {
+        PassManager {
+            passes: Vec::new(),
+            disabled: HashSet::new(),
+            optimizer: None,
+            stats: Vec::new(),
+        }
+    }
+
+    pub fn standard(resolver: Box<dyn ConflictResolver + 'a>, cost_model: CostModel) -> Self {
+        let mut manager = PassManager::new();
+        manager.add_pass(Box::new(ConflictResolutionPass::new(resolver)));
+        manager.add_pass(Box::new(GapFillingPass));
+        manager.add_pass(Box::new(TopologicalOrderPass));
+        manager.add_pass(Box::new(BranchingPass));
+        manager.add_pass(Box::new(PruningPass { cost_model }));
+        manager
+    }
+
+    pub fn pass_names(&self) -> Vec<&str> {
+        self.passes.iter().map(|pass| pass.name()).collect()
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.passes.iter().position(|pass| pass.name() == name)
+    }
+
+    pub fn add_pass(&mut self, pass: Box<dyn CompilerPass + 'a>) {
+        self.passes.push(pass);
+    }
+
+    pub fn insert_before(&mut self, anchor: &str, pass: Box<dyn CompilerPass + 'a>) -> bool {
+        match self.position(anchor) {
+            Some(index) => {
+                self.passes.insert(index, pass);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn insert_after(&mut self, anchor: &str, pass: Box<dyn CompilerPass + 'a>) -> bool {
+        match self.position(anchor) {
+            Some(index) => {
+                self.passes.insert(index + 1, pass);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn replace_pass(
+        &mut self,
+        pass: Box<dyn CompilerPass + 'a>,
+    ) -> Option<Box<dyn CompilerPass + 'a>> {
+        match self.position(pass.name()) {
+            Some(index) => Some(std::mem::replace(&mut self.passes[index], pass)),
+            None => {
+                self.passes.push(pass);
+                None
+            }
+        }
+    }
+
+    pub fn remove_pass(&mut self, name: &str) -> Option<Box<dyn CompilerPass + 'a>> {
+        self.position(name).map(|index| self.passes.remove(index))
+    }
+
+    pub fn move_pass(&mut self, name: &str, index: usize) -> bool {
+        match self.position(name) {
+            Some(from) => {
+                let pass = self.passes.remove(from);
+                let to = index.min(self.passes.len());
+                self.passes.insert(to, pass);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn disable(&mut self, name: &str) {
+        self.disabled.insert(name.to_string());
+    }
+
+    pub fn enable(&mut self, name: &str) {
+        self.disabled.remove(name);
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.position(name).is_some() && !self.disabled.contains(name)
+    }
+
+    pub fn set_optimizer(&mut self, config: Option<OptimizerConfig>) {
+        self.optimizer = config;
+    }
+
+    pub fn optimizer(&self) -> Option<&OptimizerConfig> {
+        self.optimizer.as_ref()
+    }
+
+    pub fn stats(&self) -> &[PassStats] {
+        &self.stats
+    }
+
+    pub fn run(&mut self, ir: &mut CompilationIR, context: &ContextVector, memory: &MemoryGraph) {
+        self.stats.clear();
+        for pass in &self.passes {
+            if self.disabled.contains(pass.name()) {
+                continue;
+            }
+            let nodes_before = ir.nodes.len();
+            let started = Instant::now();
+            pass.run(ir, context, memory);
+            for (order, node) in ir.nodes.iter_mut().enumerate() {
+                node.order = order;
+            }
+            self.stats.push(PassStats {
+                name: pass.name().to_string(),
+                duration_secs: started.elapsed().as_secs_f64(),
+                nodes_before,
+                nodes_after: ir.nodes.len(),
+            });
+        }
+    }
+
+    pub fn compile(&mut self, context: &ContextVector, memory: &mut MemoryGraph) -> EEG {
+        let ir = CompilationIR::from_activation(context, memory);
+        self.compile_ir(ir, context, memory)
+    }
+
+    pub fn compile_explained(
+        &mut self,
+        context: &ContextVector,
+        memory: &mut MemoryGraph,
+    ) -> (EEG, CompilationExplanation) {
+        let (activated, activation) = memory.activate_fragments_explained(context);
+        let ir = CompilationIR::from_activated(activated.clone(), context, memory);
+        let eeg = self.compile_ir(ir, context, memory);
+
+        let compiled = compiled_fragments(&eeg);
+        let mut dropped: Vec<Uuid> = activated
+            .into_iter()
+            .filter(|id| !compiled.contains(id))
+            .collect();
+        dropped.sort();
+        let explanation = CompilationExplanation {
+            activation,
+            passes: self.stats.clone(),
+            compiled,
+            dropped,
+        };
+        (eeg, explanation)
+    }
+
+    fn compile_ir(
+        &mut self,
+        mut ir: CompilationIR,
+        context: &ContextVector,
+        memory: &MemoryGraph,
+    ) -> EEG {
+        if ir.activated.is_empty() {
+            self.stats.clear();
+            return create_empty_eeg(context);
+        }
+
+        self.run(&mut ir, context, memory);
+        let mut eeg = construct_eeg(&ir.nodes, context, memory);
+        if let Some(config) = &self.optimizer {
+            let started = Instant::now();
+            let report = optimize_eeg_with_config(&mut eeg, memory, config);
+            self.stats.push(PassStats {
+                name: "optimize_eeg".to_string(),
+                duration_secs: started.elapsed().as_secs_f64(),
+                nodes_before: report.nodes_before,
+                nodes_after: report.nodes_after,
+            });
+        }
+        eeg
+    }
+}
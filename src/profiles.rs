@@ -0,0 +1,127 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::context::ContextGenerator;
+use crate::types::*;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum ProfileError {
+    IoError(std::io::Error),
+    ParseError(String),
+    UnknownProfile(String),
+}
+
+impl std::fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProfileError::IoError(e) => write!(f, "IO error: {}", e),
+            ProfileError::ParseError(e) => write!(f, "Profile parse error: {}", e),
+            ProfileError::UnknownProfile(name) => write!(f, "Unknown context profile: {}", name),
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
+
+impl From<std::io::Error> for ProfileError {
+    fn from(err: std::io::Error) -> Self {
+        ProfileError::IoError(err)
+    }
+}
+
+impl ContextProfiles {
+    pub fn from_toml(text: &str) -> Result<Self, ProfileError> {
+        toml::from_str(text).map_err(|e| ProfileError::ParseError(e.to_string()))
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, ProfileError> {
+        serde_json::from_str(text).map_err(|e| ProfileError::ParseError(e.to_string()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ProfileError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::from_json(&text),
+            _ => Self::from_toml(&text),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Result<&ContextProfile, ProfileError> {
+        self.profiles
+            .get(name)
+            .ok_or_else(|| ProfileError::UnknownProfile(name.to_string()))
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.profiles.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+impl ContextProfile {
+    pub fn apply(&self, context: &mut ContextVector) {
+        if let Some(domain) = &self.domain {
+            context.domain_hint.domain = domain.clone();
+            context.attention_window.focus_domains = [domain.clone()].into_iter().collect();
+        }
+        if let Some(threshold) = self.confidence_threshold {
+            context.confidence_threshold = threshold;
+        }
+        if let Some(max_fragments) = self.max_fragments {
+            context.max_fragments = max_fragments;
+        }
+        if let Some(time_pressure) = self.time_pressure {
+            context.time_pressure = time_pressure;
+            context.emotional_bias.urgency = time_pressure;
+        }
+
+        let bias = &self.emotional_bias;
+        let emotional = &mut context.emotional_bias;
+        for (value, field) in [
+            (bias.frustration, &mut emotional.frustration),
+            (bias.curiosity, &mut emotional.curiosity),
+            (bias.confidence, &mut emotional.confidence),
+            (bias.urgency, &mut emotional.urgency),
+            (bias.satisfaction, &mut emotional.satisfaction),
+        ] {
+            if let Some(value) = value {
+                *field = value;
+            }
+        }
+
+        context
+            .domain_hint
+            .tags
+            .extend(self.domain_tags.iter().cloned());
+        context
+            .attention_window
+            .exclusion_patterns
+            .extend(self.exclusions.iter().cloned());
+        context
+            .environmental_constraints
+            .must_include
+            .extend(self.must_include.iter().cloned());
+        context.environmental_constraints.resource_limits.extend(
+            self.resource_limits
+                .iter()
+                .map(|(key, limit)| (key.clone(), *limit)),
+        );
+    }
+
+    pub fn generate(
+        &self,
+        generator: &dyn ContextGenerator,
+        goal: &str,
+        domain: &str,
+        time_pressure: f64,
+        memory: &MemoryGraph,
+    ) -> ContextVector {
+        let domain = self.domain.as_deref().unwrap_or(domain);
+        let time_pressure = self.time_pressure.unwrap_or(time_pressure);
+        let mut context = generator.generate(goal, domain, time_pressure, memory);
+        self.apply(&mut context);
+        context
+    }
+}
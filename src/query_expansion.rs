@@ -1,4 +1,5 @@
 // Copyright (c) 2026 Nolan Taft
+use crate::normalize::normalize_keyword;
 use crate::types::*;
 use std::collections::HashSet;
 use uuid::Uuid;
@@ -17,6 +18,7 @@ impl QueryExpander {
 
         for term in &key_terms {
             expanded.insert(term.clone());
+            expanded.extend(Self::generate_term_variations(term));
         }
 
         let matching_fragments = Self::find_matching_fragments(&key_terms, memory);
@@ -61,23 +63,7 @@ impl QueryExpander {
         let mut fragment_ids = HashSet::new();
 
         for term in terms {
-            if let Some(fragments) = memory.activation_index.by_keyword.get(term) {
-                fragment_ids.extend(fragments);
-            }
-
-            let term_lower = term.to_lowercase();
-            if term_lower != *term {
-                if let Some(fragments) = memory.activation_index.by_keyword.get(&term_lower) {
-                    fragment_ids.extend(fragments);
-                }
-            }
-
-            let variations = Self::generate_term_variations(term);
-            for variation in variations {
-                if let Some(fragments) = memory.activation_index.by_keyword.get(&variation) {
-                    fragment_ids.extend(fragments);
-                }
-            }
+            fragment_ids.extend(memory.lookup_keyword_fuzzy(term).into_keys());
         }
 
         fragment_ids.into_iter().collect()
@@ -203,15 +189,12 @@ impl QueryExpander {
     }
 
     fn generate_term_variations(term: &str) -> Vec<String> {
-        let mut variations = Vec::new();
-
-        if term.contains(' ') {
-            variations.push(term.replace(' ', "_"));
-            variations.push(term.replace(' ', "-"));
-            variations.push(term.replace(' ', ""));
+        let normalized = normalize_keyword(term);
+        if normalized != term {
+            vec![normalized]
+        } else {
+            Vec::new()
         }
-
-        variations
     }
 
     fn generate_compound_variations(terms: &[String], expanded: &mut HashSet<String>) {
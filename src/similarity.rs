@@ -0,0 +1,179 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::normalize::fold_case;
+use crate::types::*;
+use std::collections::HashSet;
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let shared = a.intersection(b).count() as f64;
+    shared / a.union(b).count() as f64
+}
+
+fn folded(values: &HashSet<String>) -> HashSet<String> {
+    values.iter().map(|value| fold_case(value)).collect()
+}
+
+fn goal_terms(description: &str) -> HashSet<String> {
+    Tokenizer::default()
+        .terms(description)
+        .into_iter()
+        .collect()
+}
+
+fn emotional_similarity(a: &EmotionalState, b: &EmotionalState) -> f64 {
+    let distance = (a.frustration - b.frustration).abs()
+        + (a.curiosity - b.curiosity).abs()
+        + (a.confidence - b.confidence).abs()
+        + (a.urgency - b.urgency).abs()
+        + (a.satisfaction - b.satisfaction).abs();
+    (1.0 - distance / 5.0).clamp(0.0, 1.0)
+}
+
+fn attention_similarity(a: &AttentionWindow, b: &AttentionWindow) -> f64 {
+    let pairs = [
+        (&a.focus_entities, &b.focus_entities),
+        (&a.focus_relations, &b.focus_relations),
+        (&a.focus_domains, &b.focus_domains),
+    ];
+    let populated: Vec<f64> = pairs
+        .iter()
+        .filter(|(x, y)| !x.is_empty() || !y.is_empty())
+        .map(|(x, y)| jaccard(&folded(x), &folded(y)))
+        .collect();
+    if populated.is_empty() {
+        1.0
+    } else {
+        populated.iter().sum::<f64>() / populated.len() as f64
+    }
+}
+
+impl ContextSimilarity {
+    fn components(&self, a: &ContextVector, b: &ContextVector) -> Vec<(f64, f64)> {
+        vec![
+            (
+                self.goal_type_weight,
+                if a.goal.goal_type == b.goal.goal_type {
+                    1.0
+                } else {
+                    0.0
+                },
+            ),
+            (
+                self.goal_keyword_weight,
+                jaccard(
+                    &goal_terms(&a.goal.description),
+                    &goal_terms(&b.goal.description),
+                ),
+            ),
+            (
+                self.domain_weight,
+                if fold_case(&a.domain_hint.domain) == fold_case(&b.domain_hint.domain) {
+                    1.0
+                } else {
+                    0.0
+                },
+            ),
+            (
+                self.tag_weight,
+                jaccard(&folded(&a.domain_hint.tags), &folded(&b.domain_hint.tags)),
+            ),
+            (
+                self.emotional_weight,
+                emotional_similarity(&a.emotional_bias, &b.emotional_bias),
+            ),
+            (
+                self.attention_weight,
+                attention_similarity(&a.attention_window, &b.attention_window),
+            ),
+        ]
+    }
+
+    pub fn similarity(&self, a: &ContextVector, b: &ContextVector) -> f64 {
+        weighted(&self.components(a, b))
+    }
+
+    pub fn pattern_similarity(&self, pattern: &ContextPattern, context: &ContextVector) -> f64 {
+        let terms = goal_terms(&context.goal.description);
+        let goal_match = if pattern.goal_patterns.is_empty() {
+            1.0
+        } else {
+            let matched = pattern
+                .goal_patterns
+                .iter()
+                .filter(|goal_pattern| {
+                    let pattern_terms = goal_terms(goal_pattern);
+                    !pattern_terms.is_empty() && pattern_terms.is_subset(&terms)
+                })
+                .count();
+            matched as f64 / pattern.goal_patterns.len() as f64
+        };
+
+        let domain = fold_case(&context.domain_hint.domain);
+        let tags = folded(&context.domain_hint.tags);
+        let domain_match = pattern.domain_hints.is_empty()
+            || pattern.domain_hints.iter().any(|hint| {
+                let hint = fold_case(hint);
+                hint == domain || tags.contains(&hint)
+            });
+
+        weighted(&[
+            (self.goal_keyword_weight, goal_match),
+            (self.domain_weight, if domain_match { 1.0 } else { 0.0 }),
+        ])
+    }
+
+    pub fn matches_pattern(&self, pattern: &ContextPattern, context: &ContextVector) -> bool {
+        context.confidence_threshold >= pattern.confidence_threshold
+            && self.pattern_similarity(pattern, context) >= self.match_threshold
+    }
+
+    pub fn variance(&self, contexts: &[ContextVector]) -> f64 {
+        if contexts.len() < 2 {
+            return 0.0;
+        }
+
+        let mut total = 0.0;
+        let mut pairs = 0;
+        for i in 0..contexts.len() {
+            for j in (i + 1)..contexts.len() {
+                total += self.similarity(&contexts[i], &contexts[j]);
+                pairs += 1;
+            }
+        }
+        1.0 - total / pairs as f64
+    }
+
+    pub fn cluster(&self, contexts: &[&ContextVector]) -> Vec<usize> {
+        let mut leaders: Vec<usize> = Vec::new();
+        let mut assignments = Vec::with_capacity(contexts.len());
+
+        for (index, context) in contexts.iter().enumerate() {
+            let cluster = leaders
+                .iter()
+                .position(|&leader| {
+                    self.similarity(contexts[leader], context) >= self.cluster_threshold
+                })
+                .unwrap_or_else(|| {
+                    leaders.push(index);
+                    leaders.len() - 1
+                });
+            assignments.push(cluster);
+        }
+
+        assignments
+    }
+}
+
+fn weighted(components: &[(f64, f64)]) -> f64 {
+    let total_weight: f64 = components.iter().map(|(weight, _)| weight.max(0.0)).sum();
+    if total_weight <= 0.0 {
+        return 0.0;
+    }
+    components
+        .iter()
+        .map(|(weight, score)| weight.max(0.0) * score)
+        .sum::<f64>()
+        / total_weight
+}
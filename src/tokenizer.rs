@@ -0,0 +1,245 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::memory::content_fields;
+use crate::normalize::{fold_case, normalize_term};
+use crate::types::*;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub const STOP_WORDS: &[&str] = &[
+    "a", "about", "after", "again", "all", "also", "am", "an", "and", "any", "are", "as", "at",
+    "be", "because", "been", "before", "being", "both", "but", "by", "can", "could", "did", "do",
+    "does", "doing", "each", "for", "from", "had", "has", "have", "having", "he", "her", "here",
+    "hers", "him", "his", "how", "i", "if", "in", "into", "is", "it", "its", "just", "me", "mine",
+    "my", "myself", "of", "on", "or", "our", "ours", "she", "should", "so", "some", "such", "than",
+    "that", "the", "their", "theirs", "them", "then", "there", "these", "they", "this", "those",
+    "to", "too", "us", "very", "was", "we", "were", "what", "when", "where", "which", "while",
+    "who", "whom", "why", "will", "with", "would", "you", "your", "yours",
+];
+
+impl Tokenizer {
+    pub fn with_stop_words<'a>(stop_words: impl IntoIterator<Item = &'a str>) -> Self {
+        Tokenizer {
+            stop_words: stop_words.into_iter().map(fold_case).collect(),
+            ..Tokenizer::default()
+        }
+    }
+
+    pub fn is_stop_word(&self, word: &str) -> bool {
+        self.stop_words.contains(&fold_case(word))
+    }
+
+    pub fn tokens(&self, text: &str) -> Vec<Token> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .enumerate()
+            .filter(|(_, word)| {
+                (word.chars().count() >= self.min_token_length
+                    || word.chars().all(|c| c.is_numeric()))
+                    && !self.is_stop_word(word)
+            })
+            .map(|(position, word)| Token {
+                text: fold_case(word),
+                term: normalize_term(word),
+                position,
+            })
+            .collect()
+    }
+
+    pub fn terms(&self, text: &str) -> Vec<String> {
+        self.tokens(text).into_iter().map(|t| t.term).collect()
+    }
+
+    pub fn phrases(&self, text: &str) -> Vec<Token> {
+        phrases_of(&self.tokens(text), self.max_phrase_length)
+    }
+
+    pub fn query_terms(&self, context: &ContextVector) -> Vec<String> {
+        let mut terms = self.terms(&context.goal.description);
+        let mut tags: Vec<&String> = context.domain_hint.tags.iter().collect();
+        tags.sort();
+        for tag in tags {
+            terms.extend(self.terms(tag));
+        }
+        terms
+    }
+
+    pub fn query_phrases(&self, context: &ContextVector) -> Vec<Vec<Token>> {
+        let mut sources: Vec<&str> = context
+            .goal
+            .description
+            .split('"')
+            .skip(1)
+            .step_by(2)
+            .collect();
+        let mut tags: Vec<&String> = context.domain_hint.tags.iter().collect();
+        tags.sort();
+        sources.extend(tags.into_iter().map(|t| t.as_str()));
+
+        let mut phrases: Vec<Vec<Token>> = Vec::new();
+        for source in sources {
+            let tokens = self.tokens(source);
+            if tokens.len() >= 2 && !phrases.contains(&tokens) {
+                phrases.push(tokens);
+            }
+        }
+        phrases
+    }
+
+    pub fn fragment_tokens(&self, fragment: &MFragment) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut offset = 0;
+        for (key, value) in content_fields(&fragment.content) {
+            if key == "atom_type" || value.parse::<f64>().is_ok() {
+                continue;
+            }
+            let field_tokens = self.tokens(&value);
+            let span = value.split(|c: char| !c.is_alphanumeric()).count();
+            tokens.extend(field_tokens.into_iter().map(|token| Token {
+                position: token.position + offset,
+                ..token
+            }));
+            offset += span + 1;
+        }
+        tokens
+    }
+}
+
+pub fn phrases_of(tokens: &[Token], max_phrase_length: usize) -> Vec<Token> {
+    let mut phrases = Vec::new();
+    for start in 0..tokens.len() {
+        for length in 2..=max_phrase_length {
+            let window = match tokens.get(start..start + length) {
+                Some(window) => window,
+                None => break,
+            };
+            if window
+                .windows(2)
+                .any(|pair| pair[1].position != pair[0].position + 1)
+            {
+                break;
+            }
+            phrases.push(Token {
+                text: window
+                    .iter()
+                    .map(|t| t.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                term: window
+                    .iter()
+                    .map(|t| t.term.as_str())
+                    .collect::<Vec<_>>()
+                    .join("_"),
+                position: window[0].position,
+            });
+        }
+    }
+    phrases
+}
+
+impl PhraseIndex {
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    pub fn insert(&mut self, id: Uuid, tokens: &[Token]) {
+        if self.documents.contains_key(&id) {
+            self.remove(id);
+        }
+        let mut terms: Vec<String> = Vec::new();
+        for token in tokens {
+            self.positions
+                .entry(token.term.clone())
+                .or_default()
+                .entry(id)
+                .or_default()
+                .push(token.position);
+            if !terms.contains(&token.term) {
+                terms.push(token.term.clone());
+            }
+        }
+        self.documents.insert(id, terms);
+    }
+
+    pub fn remove(&mut self, id: Uuid) {
+        for term in self.documents.remove(&id).unwrap_or_default() {
+            if let Some(documents) = self.positions.get_mut(&term) {
+                documents.remove(&id);
+                if documents.is_empty() {
+                    self.positions.remove(&term);
+                }
+            }
+        }
+    }
+
+    pub fn find(&self, terms: &[String]) -> HashSet<Uuid> {
+        let offsets: Vec<(&str, usize)> = terms
+            .iter()
+            .enumerate()
+            .map(|(offset, term)| (term.as_str(), offset))
+            .collect();
+        self.find_at(&offsets)
+    }
+
+    pub fn find_tokens(&self, tokens: &[Token]) -> HashSet<Uuid> {
+        let base = tokens.first().map(|t| t.position).unwrap_or(0);
+        let offsets: Vec<(&str, usize)> = tokens
+            .iter()
+            .map(|token| (token.term.as_str(), token.position - base))
+            .collect();
+        self.find_at(&offsets)
+    }
+
+    fn find_at(&self, offsets: &[(&str, usize)]) -> HashSet<Uuid> {
+        let first = match offsets.first().and_then(|(t, _)| self.positions.get(*t)) {
+            Some(first) => first,
+            None => return HashSet::new(),
+        };
+
+        first
+            .iter()
+            .filter(|(id, starts)| {
+                starts.iter().any(|start| {
+                    offsets.iter().skip(1).all(|(term, offset)| {
+                        self.positions
+                            .get(*term)
+                            .and_then(|documents| documents.get(id))
+                            .is_some_and(|positions| positions.contains(&(start + offset)))
+                    })
+                })
+            })
+            .map(|(id, _)| *id)
+            .collect()
+    }
+}
+
+impl MemoryGraph {
+    pub fn phrase_search(&self, phrase: &str) -> HashSet<Uuid> {
+        let tokens = self.tokenizer.tokens(phrase);
+        if tokens.is_empty() {
+            return HashSet::new();
+        }
+        self.phrase_index.find_tokens(&tokens)
+    }
+
+    pub fn rebuild_phrase_index(&mut self) {
+        self.phrase_index = PhraseIndex::default();
+        let mut ids: Vec<Uuid> = self.fragments.keys().copied().collect();
+        ids.sort();
+        for id in ids {
+            let tokens = self.tokenizer.fragment_tokens(&self.fragments[&id]);
+            self.phrase_index.insert(id, &tokens);
+        }
+    }
+
+    pub fn phrase_matches(&self, context: &ContextVector) -> HashSet<Uuid> {
+        self.tokenizer
+            .query_phrases(context)
+            .iter()
+            .flat_map(|phrase| self.phrase_index.find_tokens(phrase))
+            .collect()
+    }
+}
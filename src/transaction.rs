@@ -0,0 +1,321 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::types::*;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransactionError {
+    MissingFragment(Uuid),
+    DanglingEdge {
+        from_fragment: Uuid,
+        to_fragment: Uuid,
+    },
+    Aborted(String),
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionError::MissingFragment(id) => write!(f, "Fragment {} does not exist", id),
+            TransactionError::DanglingEdge {
+                from_fragment,
+                to_fragment,
+            } => write!(
+                f,
+                "Edge {} -> {} points at a missing fragment",
+                from_fragment, to_fragment
+            ),
+            TransactionError::Aborted(reason) => write!(f, "Transaction aborted: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for TransactionError {}
+
+pub struct Transaction<'a> {
+    memory: &'a MemoryGraph,
+    staged: Vec<StagedMutation>,
+}
+
+impl<'a> Transaction<'a> {
+    pub fn memory(&self) -> &MemoryGraph {
+        self.memory
+    }
+
+    pub fn staged(&self) -> &[StagedMutation] {
+        &self.staged
+    }
+
+    pub fn insert_fragment(&mut self, fragment: MFragment, edges: Vec<Edge>) {
+        self.insert_fragment_from(fragment, edges, &IngestSource::default());
+    }
+
+    pub fn insert_fragment_from(
+        &mut self,
+        fragment: MFragment,
+        edges: Vec<Edge>,
+        source: &IngestSource,
+    ) {
+        self.staged.push(StagedMutation::InsertFragment {
+            fragment: Box::new(fragment),
+            edges,
+            source: source.clone(),
+        });
+    }
+
+    pub fn add_edge(&mut self, edge: Edge) {
+        self.staged.push(StagedMutation::AddEdge { edge });
+    }
+
+    pub fn reinforce_fragment(&mut self, id: Uuid, outcome: &Outcome) {
+        self.staged.push(StagedMutation::Reinforce {
+            id,
+            outcome: outcome.clone(),
+        });
+    }
+
+    pub fn remove_fragment(&mut self, id: Uuid) {
+        self.staged.push(StagedMutation::RemoveFragment { id });
+    }
+
+    pub fn record_co_activation(&mut self, fragment_ids: &[Uuid]) {
+        self.staged.push(StagedMutation::RecordCoActivation {
+            fragment_ids: fragment_ids.to_vec(),
+        });
+    }
+
+    pub fn validate(&self) -> Result<(), TransactionError> {
+        validate_mutations(self.memory, &self.staged)
+    }
+}
+
+pub fn validate_mutations(
+    memory: &MemoryGraph,
+    staged: &[StagedMutation],
+) -> Result<(), TransactionError> {
+    let mut overlay: HashMap<Uuid, bool> = HashMap::new();
+    let exists = |overlay: &HashMap<Uuid, bool>, id: &Uuid| {
+        overlay
+            .get(id)
+            .copied()
+            .unwrap_or_else(|| memory.fragments.contains_key(id))
+    };
+    let mut edges: Vec<(Uuid, Uuid)> = Vec::new();
+
+    for mutation in staged {
+        match mutation {
+            StagedMutation::InsertFragment {
+                fragment,
+                edges: fragment_edges,
+                ..
+            } => {
+                overlay.insert(fragment.id, true);
+                edges.extend(
+                    fragment_edges
+                        .iter()
+                        .map(|edge| (edge.from_fragment, edge.to_fragment)),
+                );
+            }
+            StagedMutation::AddEdge { edge } => {
+                edges.push((edge.from_fragment, edge.to_fragment));
+            }
+            StagedMutation::Reinforce { id, .. } => {
+                if !exists(&overlay, id) {
+                    return Err(TransactionError::MissingFragment(*id));
+                }
+            }
+            StagedMutation::RemoveFragment { id } => {
+                if !exists(&overlay, id) {
+                    return Err(TransactionError::MissingFragment(*id));
+                }
+                overlay.insert(*id, false);
+            }
+            StagedMutation::RecordCoActivation { fragment_ids } => {
+                if let Some(id) = fragment_ids.iter().find(|id| !exists(&overlay, id)) {
+                    return Err(TransactionError::MissingFragment(*id));
+                }
+            }
+        }
+    }
+
+    for (from_fragment, to_fragment) in edges {
+        if !exists(&overlay, &from_fragment) || !exists(&overlay, &to_fragment) {
+            return Err(TransactionError::DanglingEdge {
+                from_fragment,
+                to_fragment,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+impl MemoryGraph {
+    pub fn transaction<T, F>(&mut self, build: F) -> Result<T, TransactionError>
+    where
+        F: FnOnce(&mut Transaction) -> Result<T, TransactionError>,
+    {
+        let mut transaction = Transaction {
+            memory: self,
+            staged: Vec::new(),
+        };
+        let value = build(&mut transaction)?;
+        let staged = transaction.staged;
+        self.apply_mutations(staged)?;
+        Ok(value)
+    }
+
+    pub fn apply_mutations(&mut self, staged: Vec<StagedMutation>) -> Result<(), TransactionError> {
+        validate_mutations(self, &staged)?;
+        if staged.is_empty() {
+            return Ok(());
+        }
+
+        let mut entry = self.undo_entry(&staged);
+        let budget = self.budget.take();
+        for mutation in staged {
+            match mutation {
+                StagedMutation::InsertFragment {
+                    fragment,
+                    edges,
+                    source,
+                } => self.insert_fragment_from(*fragment, edges, &source),
+                StagedMutation::AddEdge { edge } => self.add_edge(edge),
+                StagedMutation::Reinforce { id, outcome } => self.reinforce_fragment(id, &outcome),
+                StagedMutation::RemoveFragment { id } => {
+                    self.remove_fragment(id);
+                }
+                StagedMutation::RecordCoActivation { fragment_ids } => {
+                    self.record_co_activation(&fragment_ids);
+                }
+            }
+        }
+        if let Some(budget) = &budget {
+            self.enforce_budget_recording(budget, Some(&mut entry));
+        }
+        self.budget = budget;
+
+        self.undo_log.entries.push_back(entry);
+        while self.undo_log.entries.len() > self.undo_log.capacity {
+            self.undo_log.entries.pop_front();
+        }
+        Ok(())
+    }
+
+    pub fn undo(&mut self, transactions: usize) -> usize {
+        let mut undone = 0;
+        while undone < transactions {
+            let entry = match self.undo_log.entries.pop_back() {
+                Some(entry) => entry,
+                None => break,
+            };
+            self.revert(entry);
+            undone += 1;
+        }
+        undone
+    }
+
+    fn undo_entry(&self, staged: &[StagedMutation]) -> UndoEntry {
+        let mut touched: Vec<Uuid> = Vec::new();
+        let mut edge_keys: HashSet<(Uuid, Uuid)> = HashSet::new();
+        for mutation in staged {
+            match mutation {
+                StagedMutation::InsertFragment {
+                    fragment, edges, ..
+                } => {
+                    touched.push(fragment.id);
+                    edge_keys.extend(edges.iter().map(|e| (e.from_fragment, e.to_fragment)));
+                }
+                StagedMutation::AddEdge { edge } => {
+                    edge_keys.insert((edge.from_fragment, edge.to_fragment));
+                }
+                StagedMutation::Reinforce { id, .. } | StagedMutation::RemoveFragment { id } => {
+                    touched.push(*id);
+                }
+                StagedMutation::RecordCoActivation { .. } => {}
+            }
+        }
+
+        let mut seen = HashSet::new();
+        touched.retain(|id| seen.insert(*id));
+        edge_keys.extend(
+            self.edges
+                .keys()
+                .filter(|(from_id, to_id)| seen.contains(from_id) || seen.contains(to_id)),
+        );
+        let mut edge_keys: Vec<(Uuid, Uuid)> = edge_keys.into_iter().collect();
+        edge_keys.sort();
+
+        UndoEntry {
+            fragments: touched
+                .into_iter()
+                .map(|id| (id, self.snapshot_fragment(id)))
+                .collect(),
+            edges: edge_keys
+                .into_iter()
+                .map(|key| (key, self.edges.get(&key).cloned()))
+                .collect(),
+            co_activation_patterns: self.co_activation_patterns.clone(),
+        }
+    }
+
+    fn snapshot_fragment(&self, id: Uuid) -> Option<FragmentSnapshot> {
+        let fragment = self.fragments.get(&id)?.clone();
+        Some(FragmentSnapshot {
+            fragment,
+            domains: self.fragment_domains(id),
+            source: self.fragment_sources.get(&id).cloned(),
+            prior: self.activation_priors.get(&id).copied(),
+        })
+    }
+
+    pub(crate) fn record_eviction(&self, entry: &mut UndoEntry, id: Uuid) {
+        if entry.fragments.iter().all(|(known, _)| *known != id) {
+            entry.fragments.push((id, self.snapshot_fragment(id)));
+        }
+        let mut keys: Vec<(Uuid, Uuid)> = self
+            .edges
+            .keys()
+            .filter(|(from_id, to_id)| *from_id == id || *to_id == id)
+            .copied()
+            .collect();
+        keys.sort();
+        for key in keys {
+            self.record_edge_eviction(entry, key);
+        }
+    }
+
+    pub(crate) fn record_edge_eviction(&self, entry: &mut UndoEntry, key: (Uuid, Uuid)) {
+        if entry.edges.iter().all(|(known, _)| *known != key) {
+            entry.edges.push((key, self.edges.get(&key).cloned()));
+        }
+    }
+
+    fn revert(&mut self, entry: UndoEntry) {
+        let budget = self.budget.take();
+        for (id, prior) in entry.fragments.into_iter().rev() {
+            self.remove_fragment(id);
+            if let Some(snapshot) = prior {
+                self.insert_fragment(snapshot.fragment, Vec::new());
+                self.restore_domains(id, &snapshot.domains);
+                if let Some(source) = snapshot.source {
+                    self.fragment_sources.insert(id, source);
+                }
+                if let Some(prior) = snapshot.prior {
+                    self.activation_priors.insert(id, prior);
+                }
+            }
+        }
+        for (key, prior) in entry.edges {
+            match prior {
+                Some(edge) => self.add_edge(edge),
+                None => {
+                    self.remove_edge(key);
+                }
+            }
+        }
+        self.co_activation_patterns = entry.co_activation_patterns;
+        self.budget = budget;
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) 2026 Nolan Taft
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::OnceLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
@@ -91,7 +92,7 @@ pub struct SemanticEvent {
     pub source_context: HashMap<String, String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FragmentType {
     EntityRelation,
     CausalRule,
@@ -262,6 +263,8 @@ pub struct ActivationIndex {
     pub by_goal: HashMap<String, HashSet<Uuid>>,
     pub by_domain: HashMap<String, HashSet<Uuid>>,
     pub by_keyword: HashMap<String, HashSet<Uuid>>,
+    #[serde(default)]
+    pub by_type: HashMap<FragmentType, HashSet<Uuid>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -273,6 +276,540 @@ pub struct CoActivationPattern {
     pub formatting_pattern: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EvictionPolicy {
+    LowestSalience,
+    LeastRecentlyUsed,
+    LowestBaseLevelActivation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryBudget {
+    pub max_fragments: Option<usize>,
+    pub max_edges: Option<usize>,
+    pub max_bytes: Option<usize>,
+    pub max_fragments_per_type: HashMap<String, usize>,
+    pub max_activation_history: usize,
+    pub max_co_activation_patterns: usize,
+    pub eviction_policy: EvictionPolicy,
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        MemoryBudget {
+            max_fragments: None,
+            max_edges: None,
+            max_bytes: None,
+            max_fragments_per_type: HashMap::new(),
+            max_activation_history: 100,
+            max_co_activation_patterns: 1000,
+            eviction_policy: EvictionPolicy::LowestSalience,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EvictionReason {
+    MaxFragments,
+    MaxFragmentsPerType,
+    MaxEdges,
+    MaxBytes,
+    OrphanedEdge,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvictedFragment {
+    pub id: Uuid,
+    pub fragment_type: FragmentType,
+    pub reason: EvictionReason,
+    pub policy_score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvictedEdge {
+    pub from_fragment: Uuid,
+    pub to_fragment: Uuid,
+    pub edge_type: EdgeType,
+    pub reason: EvictionReason,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EvictionReport {
+    pub evicted_fragments: Vec<EvictedFragment>,
+    pub evicted_edges: Vec<EvictedEdge>,
+    pub trimmed_history_entries: usize,
+    pub evicted_co_activation_patterns: usize,
+    pub bytes_before: usize,
+    pub bytes_after: usize,
+    pub budget_satisfied: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ByteFootprint {
+    pub(crate) fragments: HashMap<Uuid, usize>,
+    pub(crate) edges: usize,
+    pub(crate) total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VectorIndexConfig {
+    pub enabled: bool,
+    pub dimensions: usize,
+    pub hash_tables: usize,
+    pub hash_bits: usize,
+    pub min_ngram: usize,
+    pub max_ngram: usize,
+    pub min_similarity: f64,
+    pub max_candidates: usize,
+    pub activation_weight: f64,
+    pub exact_search_limit: usize,
+    pub seed: u64,
+    pub max_context_words: usize,
+    pub synonym_weight: f32,
+    pub synonyms: HashMap<String, String>,
+}
+
+impl Default for VectorIndexConfig {
+    fn default() -> Self {
+        VectorIndexConfig {
+            enabled: true,
+            dimensions: 256,
+            hash_tables: 8,
+            hash_bits: 8,
+            min_ngram: 3,
+            max_ngram: 4,
+            min_similarity: 0.3,
+            max_candidates: 20,
+            activation_weight: 0.8,
+            exact_search_limit: 512,
+            seed: 0x5eed_c0de,
+            max_context_words: 20_000,
+            synonym_weight: 2.0,
+            synonyms: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VectorIndex {
+    pub config: VectorIndexConfig,
+    pub vectors: HashMap<Uuid, Vec<f32>>,
+    pub buckets: Vec<HashMap<u64, Vec<Uuid>>>,
+    pub word_contexts: HashMap<String, Vec<f32>>,
+    #[serde(default)]
+    pub documents: HashMap<Uuid, DocumentWords>,
+    #[serde(default)]
+    pub word_documents: HashMap<String, usize>,
+    #[serde(skip)]
+    pub(crate) hyperplanes: OnceLock<Hyperplanes>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DocumentWords {
+    pub words: Vec<String>,
+    pub learned: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Hyperplanes {
+    pub(crate) seed: u64,
+    pub(crate) tables: usize,
+    pub(crate) bits: usize,
+    pub(crate) dimensions: usize,
+    pub(crate) planes: Vec<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrigramIndexConfig {
+    pub enabled: bool,
+    pub min_similarity: f64,
+    pub max_edit_distance: usize,
+    pub min_term_length: usize,
+    pub max_matches: usize,
+    pub fuzzy_weight: f64,
+}
+
+impl Default for TrigramIndexConfig {
+    fn default() -> Self {
+        TrigramIndexConfig {
+            enabled: true,
+            min_similarity: 0.45,
+            max_edit_distance: 2,
+            min_term_length: 4,
+            max_matches: 8,
+            fuzzy_weight: 0.7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrigramIndex {
+    pub config: TrigramIndexConfig,
+    pub trigrams: HashMap<String, HashSet<String>>,
+    pub keys: HashMap<String, HashSet<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeywordMatch {
+    pub key: String,
+    pub similarity: f64,
+    pub exact: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bm25Config {
+    pub enabled: bool,
+    pub k1: f64,
+    pub b: f64,
+    pub weight: f64,
+    pub max_candidates: usize,
+}
+
+impl Default for Bm25Config {
+    fn default() -> Self {
+        Bm25Config {
+            enabled: true,
+            k1: 1.2,
+            b: 0.75,
+            weight: 0.5,
+            max_candidates: 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Bm25Index {
+    pub config: Bm25Config,
+    pub postings: HashMap<String, HashMap<Uuid, u32>>,
+    pub doc_lengths: HashMap<Uuid, usize>,
+    pub total_length: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IngestSource {
+    pub domain: Option<String>,
+    pub context: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttentionConfig {
+    pub focus_entity_boost: f64,
+    pub focus_relation_boost: f64,
+    pub focus_domain_boost: f64,
+}
+
+impl Default for AttentionConfig {
+    fn default() -> Self {
+        AttentionConfig {
+            focus_entity_boost: 0.3,
+            focus_relation_boost: 0.3,
+            focus_domain_boost: 0.2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainClassifierConfig {
+    pub enabled: bool,
+    pub domain_terms: HashMap<String, Vec<String>>,
+    pub min_score: f64,
+    pub max_domains: usize,
+    pub fallback_domain: String,
+    pub activation_boost: f64,
+    pub goal_affinity_boost: f64,
+}
+
+impl Default for DomainClassifierConfig {
+    fn default() -> Self {
+        let domain_terms = [
+            (
+                "api_integration",
+                vec![
+                    "api", "http", "https", "endpoint", "request", "response", "rest", "json",
+                    "header", "status", "url", "webhook", "oauth", "token", "404", "500",
+                ],
+            ),
+            (
+                "web_development",
+                vec![
+                    "web",
+                    "html",
+                    "css",
+                    "javascript",
+                    "browser",
+                    "frontend",
+                    "react",
+                    "dom",
+                    "page",
+                    "website",
+                ],
+            ),
+            (
+                "debugging",
+                vec![
+                    "error",
+                    "bug",
+                    "crash",
+                    "exception",
+                    "stack",
+                    "trace",
+                    "fail",
+                    "failure",
+                    "debug",
+                    "fix",
+                    "broken",
+                    "timeout",
+                    "panic",
+                    "issue",
+                ],
+            ),
+            (
+                "programming",
+                vec![
+                    "code", "function", "compile", "compiler", "variable", "type", "rust",
+                    "python", "library", "crate", "module", "class", "method", "refactor",
+                ],
+            ),
+            (
+                "database",
+                vec![
+                    "sql",
+                    "query",
+                    "table",
+                    "index",
+                    "postgres",
+                    "mysql",
+                    "schema",
+                    "migration",
+                    "database",
+                    "row",
+                    "column",
+                    "transaction",
+                ],
+            ),
+            (
+                "personal",
+                vec![
+                    "name", "like", "love", "hate", "prefer", "favorite", "family", "friend",
+                    "birthday", "age", "live", "hobby", "wife", "husband", "mother", "father",
+                    "sister", "brother", "pet",
+                ],
+            ),
+        ]
+        .into_iter()
+        .map(|(domain, terms)| {
+            (
+                domain.to_string(),
+                terms.into_iter().map(|t| t.to_string()).collect(),
+            )
+        })
+        .collect();
+
+        DomainClassifierConfig {
+            enabled: true,
+            domain_terms,
+            min_score: 0.5,
+            max_domains: 3,
+            fallback_domain: "general".to_string(),
+            activation_boost: 0.5,
+            goal_affinity_boost: 0.4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Token {
+    pub text: String,
+    pub term: String,
+    pub position: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tokenizer {
+    pub stop_words: HashSet<String>,
+    pub min_token_length: usize,
+    pub max_phrase_length: usize,
+}
+
+impl Default for Tokenizer {
+    fn default() -> Self {
+        Tokenizer {
+            stop_words: crate::tokenizer::STOP_WORDS
+                .iter()
+                .map(|w| w.to_string())
+                .collect(),
+            min_token_length: 2,
+            max_phrase_length: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PhraseIndex {
+    pub positions: HashMap<String, HashMap<Uuid, Vec<usize>>>,
+    #[serde(default)]
+    pub documents: HashMap<Uuid, Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum StagedMutation {
+    InsertFragment {
+        fragment: Box<MFragment>,
+        edges: Vec<Edge>,
+        source: IngestSource,
+    },
+    AddEdge {
+        edge: Edge,
+    },
+    Reinforce {
+        id: Uuid,
+        outcome: Outcome,
+    },
+    RemoveFragment {
+        id: Uuid,
+    },
+    RecordCoActivation {
+        fragment_ids: Vec<Uuid>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FragmentSnapshot {
+    pub fragment: MFragment,
+    pub domains: Vec<String>,
+    pub source: Option<String>,
+    pub prior: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UndoEntry {
+    pub fragments: Vec<(Uuid, Option<FragmentSnapshot>)>,
+    pub edges: Vec<((Uuid, Uuid), Option<Edge>)>,
+    pub co_activation_patterns: Vec<CoActivationPattern>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoLog {
+    pub entries: VecDeque<UndoEntry>,
+    pub capacity: usize,
+}
+
+impl Default for UndoLog {
+    fn default() -> Self {
+        UndoLog {
+            entries: VecDeque::new(),
+            capacity: 20,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmotionalBiasOverrides {
+    pub frustration: Option<f64>,
+    pub curiosity: Option<f64>,
+    pub confidence: Option<f64>,
+    pub urgency: Option<f64>,
+    pub satisfaction: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ContextProfile {
+    pub domain: Option<String>,
+    pub confidence_threshold: Option<f64>,
+    pub max_fragments: Option<usize>,
+    pub time_pressure: Option<f64>,
+    pub emotional_bias: EmotionalBiasOverrides,
+    pub domain_tags: Vec<String>,
+    pub exclusions: Vec<String>,
+    pub must_include: Vec<String>,
+    pub resource_limits: HashMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ContextProfiles {
+    pub profiles: HashMap<String, ContextProfile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkingMemory {
+    pub turns: VecDeque<Vec<Uuid>>,
+    pub capacity: usize,
+    pub decay: f64,
+    pub boost: f64,
+    pub max_primed: usize,
+}
+
+impl Default for WorkingMemory {
+    fn default() -> Self {
+        WorkingMemory {
+            turns: VecDeque::new(),
+            capacity: 5,
+            decay: 0.6,
+            boost: 0.5,
+            max_primed: 20,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MemoryEvent {
+    FragmentInserted {
+        fragment: MFragment,
+    },
+    FragmentUpdated {
+        fragment: MFragment,
+    },
+    FragmentReinforced {
+        id: Uuid,
+        outcome_type: OutcomeType,
+        confidence: f64,
+        reinforcement_count: u32,
+    },
+    FragmentDecayed {
+        id: Uuid,
+        salience: f64,
+        confidence: f64,
+    },
+    FragmentRemoved {
+        id: Uuid,
+    },
+    EdgeAdded {
+        edge: Edge,
+    },
+    EdgeRemoved {
+        from_fragment: Uuid,
+        to_fragment: Uuid,
+    },
+    ModuleAdded {
+        module_id: Uuid,
+        module_type: ModuleType,
+        confidence: f64,
+    },
+    BudgetEnforced {
+        report: EvictionReport,
+    },
+}
+
+pub type MemoryHook = std::sync::Arc<dyn Fn(&MemoryEvent) + Send + Sync>;
+
+#[derive(Clone, Default)]
+pub struct MemoryObservers {
+    pub next_id: u64,
+    pub hooks: Vec<(u64, MemoryHook)>,
+}
+
+impl std::fmt::Debug for MemoryObservers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryObservers")
+            .field("subscriptions", &self.hooks.len())
+            .finish()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryGraph {
     pub fragments: HashMap<Uuid, MFragment>,
@@ -281,6 +818,38 @@ pub struct MemoryGraph {
     pub compiled_modules: Vec<CompiledModule>,
     pub co_activation_patterns: Vec<CoActivationPattern>,
     pub version: u32,
+    #[serde(default)]
+    pub normalization_version: u32,
+    #[serde(default)]
+    pub budget: Option<MemoryBudget>,
+    #[serde(default)]
+    pub activation_priors: HashMap<Uuid, f64>,
+    #[serde(default)]
+    pub vector_index: VectorIndex,
+    #[serde(default)]
+    pub keyword_index: TrigramIndex,
+    #[serde(default)]
+    pub text_index: Bm25Index,
+    #[serde(default)]
+    pub domain_classifier: DomainClassifierConfig,
+    #[serde(default)]
+    pub tokenizer: Tokenizer,
+    #[serde(default)]
+    pub phrase_index: PhraseIndex,
+    #[serde(default)]
+    pub attention: AttentionConfig,
+    #[serde(default)]
+    pub fragment_sources: HashMap<Uuid, String>,
+    #[serde(skip)]
+    pub observers: MemoryObservers,
+    #[serde(skip)]
+    pub undo_log: UndoLog,
+    #[serde(skip, default = "crate::compile_cache::next_revision")]
+    pub(crate) revision: u64,
+    #[serde(skip)]
+    pub(crate) footprint: ByteFootprint,
+    #[serde(skip)]
+    pub(crate) domain_terms: HashMap<String, HashSet<String>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -292,6 +861,95 @@ pub enum GoalType {
     Predict,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextFeatures {
+    pub goal_type: GoalType,
+    pub parameters: HashMap<String, String>,
+    pub entities: HashSet<String>,
+    pub relations: HashSet<String>,
+    pub tags: HashSet<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GoalRequirement {
+    Relation(String),
+    Entity(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceTrustResolver {
+    pub trust: HashMap<String, f64>,
+    pub default_trust: f64,
+}
+
+impl Default for SourceTrustResolver {
+    fn default() -> Self {
+        SourceTrustResolver {
+            trust: HashMap::new(),
+            default_trust: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostModel {
+    pub node_costs: HashMap<NodeType, f64>,
+    pub fragment_costs: HashMap<FragmentType, f64>,
+    pub default_cost: f64,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        CostModel {
+            node_costs: HashMap::from([
+                (NodeType::FragmentNode, 1.0),
+                (NodeType::ConflictNode, 0.5),
+                (NodeType::DecisionNode, 0.5),
+                (NodeType::GapFillNode, 0.25),
+            ]),
+            fragment_costs: HashMap::new(),
+            default_cost: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VocabularyContextGenerator {
+    pub goal_cues: Vec<(String, GoalType)>,
+    pub cue_weight: f64,
+    pub default_goal_type: GoalType,
+    pub max_entities: usize,
+}
+
+impl Default for VocabularyContextGenerator {
+    fn default() -> Self {
+        let goal_cues = [
+            ("debug", GoalType::Debug),
+            ("fix", GoalType::Debug),
+            ("troubleshoot", GoalType::Debug),
+            ("create", GoalType::Create),
+            ("build", GoalType::Create),
+            ("make", GoalType::Create),
+            ("learn", GoalType::Learn),
+            ("understand", GoalType::Learn),
+            ("explain", GoalType::Explain),
+            ("why", GoalType::Explain),
+            ("predict", GoalType::Predict),
+            ("forecast", GoalType::Predict),
+        ]
+        .into_iter()
+        .map(|(cue, goal_type)| (cue.to_string(), goal_type))
+        .collect();
+
+        VocabularyContextGenerator {
+            goal_cues,
+            cue_weight: 2.0,
+            default_goal_type: GoalType::Learn,
+            max_entities: 16,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GoalSpec {
     pub description: String,
@@ -338,13 +996,15 @@ pub struct ContextVector {
     pub emotional_bias: EmotionalState,
     pub environmental_constraints: Constraints,
     pub recent_activations: Vec<Uuid>,
+    #[serde(default)]
+    pub activation_primes: HashMap<Uuid, f64>,
     pub time_pressure: f64,
     pub domain_hint: DomainPattern,
     pub confidence_threshold: f64,
     pub max_fragments: usize,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum NodeType {
     FragmentNode,
     ConflictNode,
@@ -375,6 +1035,10 @@ pub enum NodeContent {
         action_type: String,
         parameters: HashMap<String, String>,
     },
+    FusedChain {
+        node_ids: Vec<Uuid>,
+        fragment_ids: Vec<Uuid>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -420,6 +1084,191 @@ pub struct EEG {
     pub metadata: EEGMetadata,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizerConfig {
+    pub eliminate_dead_branches: bool,
+    pub eliminate_unreachable: bool,
+    pub merge_common_subgraphs: bool,
+    pub fuse_chains: bool,
+}
+
+impl Default for OptimizerConfig {
+    fn default() -> Self {
+        OptimizerConfig {
+            eliminate_dead_branches: true,
+            eliminate_unreachable: true,
+            merge_common_subgraphs: true,
+            fuse_chains: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OptimizationReport {
+    pub nodes_before: usize,
+    pub nodes_after: usize,
+    pub dead_branches_removed: usize,
+    pub unreachable_removed: usize,
+    pub nodes_merged: usize,
+    pub nodes_fused: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub evictions: usize,
+    pub invalidations: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct CachedEEG {
+    pub(crate) key: Vec<u8>,
+    pub revision: u64,
+    pub activated: Vec<Uuid>,
+    pub eeg: EEG,
+}
+
+#[derive(Debug, Clone)]
+pub struct CompilationCache {
+    pub capacity: usize,
+    pub(crate) entries: HashMap<u64, CachedEEG>,
+    pub(crate) recency: VecDeque<u64>,
+    pub(crate) stats: CacheStats,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MatchSource {
+    Goal {
+        pattern: String,
+    },
+    GoalAffinity {
+        pattern: String,
+    },
+    Domain {
+        domain: String,
+    },
+    Keyword {
+        term: String,
+        key: String,
+        exact: bool,
+        weight: f64,
+    },
+    Semantic {
+        similarity: f64,
+    },
+    Text {
+        score: f64,
+    },
+    Phrase {
+        phrase: String,
+    },
+    Recent {
+        prime: f64,
+    },
+    Required,
+    Edge {
+        from: Uuid,
+        edge_type: EdgeType,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ActivationDecision {
+    Selected { rank: usize },
+    OutOfBudget { rank: usize, max_fragments: usize },
+    BelowConfidence { confidence: f64, threshold: f64 },
+    Excluded,
+    Required,
+    Spread,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    pub text_match: f64,
+    pub confidence: f64,
+    pub recency: f64,
+    pub salience: f64,
+    pub emotion: f64,
+    pub reinforcement: f64,
+    pub relevance: f64,
+    pub match_weight: f64,
+    pub domain_boost: f64,
+    pub goal_affinity_boost: f64,
+    pub prime_boost: f64,
+    pub focus_boost: f64,
+    pub prior: f64,
+    pub total: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FragmentExplanation {
+    pub fragment_id: Uuid,
+    pub label: String,
+    pub activated: bool,
+    pub decision: ActivationDecision,
+    pub score: Option<ScoreBreakdown>,
+    pub sources: Vec<MatchSource>,
+    pub spread_path: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivationExplanation {
+    pub goal_patterns: Vec<String>,
+    pub confidence_threshold: f64,
+    pub max_fragments: usize,
+    pub fragments: Vec<FragmentExplanation>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompilationExplanation {
+    pub activation: ActivationExplanation,
+    pub passes: Vec<PassStats>,
+    pub compiled: Vec<Uuid>,
+    pub dropped: Vec<Uuid>,
+}
+
+impl Default for CompilationCache {
+    fn default() -> Self {
+        CompilationCache {
+            capacity: 128,
+            entries: HashMap::new(),
+            recency: VecDeque::new(),
+            stats: CacheStats::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IRNode {
+    pub id: Uuid,
+    pub node_type: NodeType,
+    pub order: usize,
+    pub confidence: f64,
+    pub execution_cost: f64,
+    pub content: Option<NodeContent>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompilationIR {
+    pub activated: HashSet<Uuid>,
+    pub required: HashSet<Uuid>,
+    pub nodes: Vec<IRNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassStats {
+    pub name: String,
+    pub duration_secs: f64,
+    pub nodes_before: usize,
+    pub nodes_after: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PruningPass {
+    pub cost_model: CostModel,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum OutcomeType {
     Success,
@@ -535,6 +1384,33 @@ pub struct LinterInput {
     pub min_occurrences: usize,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextSimilarity {
+    pub goal_type_weight: f64,
+    pub goal_keyword_weight: f64,
+    pub domain_weight: f64,
+    pub tag_weight: f64,
+    pub emotional_weight: f64,
+    pub attention_weight: f64,
+    pub match_threshold: f64,
+    pub cluster_threshold: f64,
+}
+
+impl Default for ContextSimilarity {
+    fn default() -> Self {
+        ContextSimilarity {
+            goal_type_weight: 0.2,
+            goal_keyword_weight: 0.25,
+            domain_weight: 0.15,
+            tag_weight: 0.15,
+            emotional_weight: 0.1,
+            attention_weight: 0.15,
+            match_threshold: 0.6,
+            cluster_threshold: 0.7,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LinterConfig {
     pub min_occurrences: usize,
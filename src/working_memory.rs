@@ -0,0 +1,76 @@
+// Copyright (c) 2026 Nolan Taft
+use crate::types::*;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+impl WorkingMemory {
+    pub fn len(&self) -> usize {
+        self.turns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.turns.is_empty()
+    }
+
+    pub fn record_turn(&mut self, activated: impl IntoIterator<Item = Uuid>) {
+        let mut seen = HashSet::new();
+        let mut turn: Vec<Uuid> = activated
+            .into_iter()
+            .filter(|id| seen.insert(*id))
+            .collect();
+        turn.sort();
+        self.turns.push_back(turn);
+        while self.turns.len() > self.capacity {
+            self.turns.pop_front();
+        }
+    }
+
+    pub fn weights(&self) -> HashMap<Uuid, f64> {
+        let mut weights: HashMap<Uuid, f64> = HashMap::new();
+        for (distance, turn) in self.turns.iter().rev().enumerate() {
+            let weight = self.boost * self.decay.powi(distance as i32);
+            for id in turn {
+                let entry = weights.entry(*id).or_insert(weight);
+                *entry = entry.max(weight);
+            }
+        }
+        weights
+    }
+
+    pub fn primed(&self) -> Vec<(Uuid, f64)> {
+        let mut primed: Vec<(Uuid, f64)> = self.weights().into_iter().collect();
+        primed.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.0.cmp(&b.0))
+        });
+        primed.truncate(self.max_primed);
+        primed
+    }
+
+    pub fn prime(&self, context: &mut ContextVector) {
+        for (id, weight) in self.primed() {
+            if !context.recent_activations.contains(&id) {
+                context.recent_activations.push(id);
+            }
+            let entry = context.activation_primes.entry(id).or_insert(weight);
+            *entry = entry.max(weight);
+        }
+    }
+
+    pub fn forget(&mut self, id: Uuid) {
+        for turn in self.turns.iter_mut() {
+            turn.retain(|activated| *activated != id);
+        }
+    }
+
+    pub fn retain_present(&mut self, memory: &MemoryGraph) {
+        for turn in self.turns.iter_mut() {
+            turn.retain(|id| memory.fragments.contains_key(id));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.turns.clear();
+    }
+}
@@ -0,0 +1,194 @@
+use c_mer::*;
+use std::collections::HashSet;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+#[test]
+fn test_explained_activation_matches_plain_activation() {
+    let mut memory = create_test_memory();
+    for target in ["pizza", "pasta", "sushi"] {
+        memory.insert_fragment(
+            create_entity_relation_fragment("alice", "likes", target),
+            Vec::new(),
+        );
+    }
+    let context = create_test_context("alice", "general", 0.0);
+
+    let plain = memory.clone().activate_fragments(&context);
+    let (activated, explanation) = memory.activate_fragments_explained(&context);
+
+    assert_eq!(activated, plain);
+    assert_eq!(
+        explanation.activated().into_iter().collect::<HashSet<_>>(),
+        activated
+    );
+    assert_eq!(explanation.confidence_threshold, 0.6);
+    assert!(explanation.goal_patterns.contains(&"alic".to_string()));
+}
+
+#[test]
+fn test_score_components_add_up() {
+    let mut memory = create_test_memory();
+    let pizza = create_entity_relation_fragment("alice", "likes", "pizza");
+    memory.insert_fragment(pizza.clone(), Vec::new());
+
+    let (_, explanation) =
+        memory.activate_fragments_explained(&create_test_context("alice", "general", 0.0));
+    let entry = explanation.fragment(pizza.id).unwrap();
+    let score = entry.score.as_ref().unwrap();
+
+    assert!(entry.activated);
+    assert_eq!(entry.decision, ActivationDecision::Selected { rank: 0 });
+    let relevance = score.text_match
+        + score.confidence
+        + score.recency
+        + score.salience
+        + score.emotion
+        + score.reinforcement;
+    assert!((score.relevance - relevance).abs() < 1e-9);
+    let boost = 1.0
+        + score.domain_boost
+        + score.goal_affinity_boost
+        + score.prime_boost
+        + score.focus_boost;
+    assert!(
+        (score.total - (score.relevance * score.match_weight * boost + score.prior)).abs() < 1e-9
+    );
+    assert!((score.confidence - 0.8 * 0.3).abs() < 1e-9);
+    assert!(entry.sources.iter().any(|source| matches!(
+        source,
+        MatchSource::Keyword { key, exact: true, .. } if key == "alic"
+    )));
+}
+
+#[test]
+fn test_threshold_and_budget_decisions() {
+    let mut memory = create_test_memory();
+    let mut shaky = create_entity_relation_fragment("alice", "likes", "pizza");
+    shaky.confidence = 0.3;
+    let first = create_entity_relation_fragment("alice", "likes", "pasta");
+    let mut second = create_entity_relation_fragment("alice", "likes", "sushi");
+    second.confidence = 0.7;
+    for fragment in [&shaky, &first, &second] {
+        memory.insert_fragment(fragment.clone(), Vec::new());
+    }
+    let mut context = create_test_context("alice", "general", 0.0);
+    context.max_fragments = 1;
+
+    let (activated, explanation) = memory.activate_fragments_explained(&context);
+
+    assert_eq!(activated, HashSet::from([first.id]));
+    assert_eq!(
+        explanation.fragment(shaky.id).unwrap().decision,
+        ActivationDecision::BelowConfidence {
+            confidence: 0.3,
+            threshold: 0.6
+        }
+    );
+    assert!(explanation.fragment(shaky.id).unwrap().score.is_none());
+    let cut = explanation.fragment(second.id).unwrap();
+    assert!(!cut.activated);
+    assert_eq!(
+        cut.decision,
+        ActivationDecision::OutOfBudget {
+            rank: 1,
+            max_fragments: 1
+        }
+    );
+}
+
+#[test]
+fn test_exclusions_and_requirements_are_reported() {
+    let mut memory = create_test_memory();
+    let pizza = create_entity_relation_fragment("alice", "likes", "pizza");
+    let pasta = create_entity_relation_fragment("alice", "likes", "pasta");
+    let tea = create_entity_relation_fragment("bob", "drinks", "tea");
+    for fragment in [&pizza, &pasta, &tea] {
+        memory.insert_fragment(fragment.clone(), Vec::new());
+    }
+    let mut context = create_test_context("alice", "general", 0.0);
+    context
+        .environmental_constraints
+        .must_exclude
+        .insert("pasta".to_string());
+    context
+        .environmental_constraints
+        .must_include
+        .insert("tea".to_string());
+
+    let (activated, explanation) = memory.activate_fragments_explained(&context);
+
+    assert!(!activated.contains(&pasta.id));
+    assert_eq!(
+        explanation.fragment(pasta.id).unwrap().decision,
+        ActivationDecision::Excluded
+    );
+    let required = explanation.fragment(tea.id).unwrap();
+    assert!(required.activated);
+    assert_eq!(required.decision, ActivationDecision::Required);
+    assert!(required.sources.contains(&MatchSource::Required));
+}
+
+#[test]
+fn test_spreading_path_is_recorded() {
+    let mut memory = create_test_memory();
+    let seed = create_entity_relation_fragment("alice", "likes", "pizza");
+    let step = create_causal_rule_fragment("cheese melts", "crust browns", 0.9);
+    let end = create_causal_rule_fragment("crust browns", "dinner ready", 0.9);
+    let weak = create_causal_rule_fragment("oven cools", "crust sags", 0.3);
+    memory.insert_fragment(seed.clone(), Vec::new());
+    memory.insert_fragment(step.clone(), vec![create_test_edges(seed.id, step.id, 0.9)]);
+    memory.insert_fragment(end.clone(), vec![create_test_edges(step.id, end.id, 0.9)]);
+    memory.insert_fragment(weak.clone(), vec![create_test_edges(seed.id, weak.id, 0.9)]);
+
+    let (_, explanation) =
+        memory.activate_fragments_explained(&create_test_context("alice", "general", 0.0));
+
+    let reached = explanation.fragment(end.id).unwrap();
+    assert!(reached.activated);
+    assert_eq!(reached.decision, ActivationDecision::Spread);
+    assert_eq!(reached.spread_path, vec![seed.id, step.id, end.id]);
+    assert!(reached.sources.contains(&MatchSource::Edge {
+        from: step.id,
+        edge_type: EdgeType::Causal
+    }));
+
+    let skipped = explanation.fragment(weak.id).unwrap();
+    assert!(!skipped.activated);
+    assert!(matches!(
+        skipped.decision,
+        ActivationDecision::BelowConfidence { .. }
+    ));
+}
+
+#[test]
+fn test_compile_explanation_reports_dropped_fragments() {
+    let mut memory = create_test_memory();
+    let likes = create_entity_relation_fragment("alice", "likes", "pizza");
+    let mut dislikes = create_entity_relation_fragment("alice", "dislikes", "pizza");
+    dislikes.confidence = 0.7;
+    memory.insert_fragment(likes.clone(), Vec::new());
+    memory.insert_fragment(dislikes.clone(), Vec::new());
+    let context = create_test_context("alice", "general", 0.0);
+
+    let (eeg, explanation) = compile_thought_explained(&context, &mut memory);
+
+    assert!(eeg.nodes.contains_key(&likes.id));
+    assert_eq!(explanation.compiled, vec![likes.id]);
+    assert_eq!(explanation.dropped, vec![dislikes.id]);
+    assert_eq!(explanation.passes.len(), 5);
+    assert!(
+        explanation
+            .activation
+            .fragment(dislikes.id)
+            .unwrap()
+            .activated
+    );
+
+    let printed = explanation.to_string();
+    assert!(printed.contains(&format!("+ {}", likes.id)));
+    assert!(printed.contains("pass prune_resources"));
+    assert!(printed.contains(&format!("dropped by compiler: {}", dislikes.id)));
+}
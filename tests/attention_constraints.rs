@@ -0,0 +1,176 @@
+use c_mer::*;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+fn eeg_fragments(eeg: &EEG) -> Vec<uuid::Uuid> {
+    eeg.nodes
+        .values()
+        .filter_map(|node| match &node.content {
+            NodeContent::Fragment { fragment_id, .. } => Some(*fragment_id),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_exclusion_patterns_hide_confidential_fragments() {
+    let mut memory = create_test_memory();
+    let public = create_entity_relation_fragment("project", "uses", "postgres");
+    let secret = create_entity_relation_fragment("project", "codename", "nightjar");
+    memory.insert_fragment(public.clone(), Vec::new());
+    memory.insert_fragment_from(
+        secret.clone(),
+        Vec::new(),
+        &IngestSource::in_domain("confidential"),
+    );
+
+    let mut context = create_test_context("project", "general", 0.3);
+    assert!(memory.activate_fragments(&context).contains(&secret.id));
+
+    context
+        .attention_window
+        .exclusion_patterns
+        .insert("confidential".to_string());
+    let activated = memory.activate_fragments(&context);
+    assert!(activated.contains(&public.id));
+    assert!(!activated.contains(&secret.id));
+    assert!(!eeg_fragments(&compile_thought(&context, &mut memory)).contains(&secret.id));
+}
+
+#[test]
+fn test_must_exclude_blocks_matches_and_spreading() {
+    let mut memory = create_test_memory();
+    let start = create_entity_relation_fragment("deploy", "requires", "approval");
+    let linked = create_entity_relation_fragment("approval", "stored_in", "vault");
+    let phrase = create_causal_rule_fragment("vault token expires", "deploy fails", 0.8);
+    memory.insert_fragment(linked.clone(), Vec::new());
+    memory.insert_fragment(phrase.clone(), Vec::new());
+    memory.insert_fragment(
+        start.clone(),
+        vec![create_test_edges(start.id, linked.id, 0.9)],
+    );
+
+    let mut context = create_test_context("deploy", "general", 0.3);
+    assert!(memory.activate_fragments(&context).contains(&linked.id));
+
+    let excluded = &mut context.environmental_constraints.must_exclude;
+    excluded.insert("target=vault".to_string());
+    excluded.insert("token expires".to_string());
+    let activated = memory.activate_fragments(&context);
+
+    assert!(activated.contains(&start.id));
+    assert!(!activated.contains(&linked.id));
+    assert!(!activated.contains(&phrase.id));
+    assert!(memory.matches_pattern(phrase.id, "Token Expires"));
+    assert!(!memory.matches_pattern(phrase.id, "expires token"));
+}
+
+#[test]
+fn test_must_include_forces_fragments_into_the_eeg() {
+    let mut memory = create_test_memory();
+    let relevant = create_entity_relation_fragment("server", "runs", "nginx");
+    let mut policy = create_causal_rule_fragment("change window", "notify oncall", 0.2);
+    policy.confidence = 0.2;
+    memory.insert_fragment(relevant.clone(), Vec::new());
+    memory.insert_fragment(policy.clone(), Vec::new());
+
+    let mut context = create_test_context("server", "general", 0.3);
+    assert!(!memory.activate_fragments(&context).contains(&policy.id));
+
+    context
+        .environmental_constraints
+        .must_include
+        .insert("change window".to_string());
+    assert_eq!(memory.required_fragments(&context), vec![policy.id]);
+    assert!(memory.activate_fragments(&context).contains(&policy.id));
+    assert!(eeg_fragments(&compile_thought(&context, &mut memory)).contains(&policy.id));
+}
+
+#[test]
+fn test_exclusion_wins_over_inclusion() {
+    let mut memory = create_test_memory();
+    let secret = create_entity_relation_fragment("payroll", "stored_in", "ledger");
+    memory.insert_fragment_from(
+        secret.clone(),
+        Vec::new(),
+        &IngestSource::in_domain("confidential"),
+    );
+
+    let mut context = create_test_context("payroll", "general", 0.3);
+    let constraints = &mut context.environmental_constraints;
+    constraints.must_include.insert(secret.id.to_string());
+    constraints
+        .must_exclude
+        .insert("domain=confidential".to_string());
+
+    assert!(memory.required_fragments(&context).is_empty());
+    assert!(!memory.activate_fragments(&context).contains(&secret.id));
+}
+
+#[test]
+fn test_focus_sets_boost_ranking() {
+    let mut memory = create_test_memory();
+    let by_entity = create_entity_relation_fragment("cluster", "hosts", "redis");
+    let by_relation = create_entity_relation_fragment("cluster", "monitors", "disk");
+    memory.insert_fragment(by_entity.clone(), Vec::new());
+    memory.insert_fragment(by_relation.clone(), Vec::new());
+
+    let mut context = create_test_context("cluster", "general", 0.3);
+    context.max_fragments = 1;
+    context.attention_window.focus_entities.clear();
+    context.attention_window.focus_relations.clear();
+    context.attention_window.focus_domains.clear();
+
+    context
+        .attention_window
+        .focus_entities
+        .insert("Redis".to_string());
+    assert!(memory.focus_boost(by_entity.id, &context) > 0.0);
+    assert!(memory.activate_fragments(&context).contains(&by_entity.id));
+
+    context.attention_window.focus_entities.clear();
+    context
+        .attention_window
+        .focus_relations
+        .insert("monitors".to_string());
+    assert_eq!(memory.focus_boost(by_entity.id, &context), 0.0);
+    assert!(memory
+        .activate_fragments(&context)
+        .contains(&by_relation.id));
+}
+
+#[test]
+fn test_resource_limits_cap_eeg_size_and_cost() {
+    let mut memory = create_test_memory();
+    let mut ids = Vec::new();
+    for target in ["alpha", "beta", "gamma", "delta", "epsilon"] {
+        let fragment = create_entity_relation_fragment("service", "calls", target);
+        ids.push(fragment.id);
+        memory.insert_fragment(fragment, Vec::new());
+    }
+
+    let mut context = create_test_context("service", "general", 0.0);
+    assert_eq!(compile_thought(&context, &mut memory).nodes.len(), 5);
+
+    context
+        .environmental_constraints
+        .resource_limits
+        .insert("max_nodes".to_string(), 3.0);
+    let eeg = compile_thought(&context, &mut memory);
+    assert_eq!(eeg.nodes.len(), 3);
+    assert_eq!(eeg.edges.len(), 2);
+
+    let limits = &mut context.environmental_constraints.resource_limits;
+    limits.insert("max_nodes".to_string(), 10.0);
+    limits.insert("max_cost".to_string(), 2.0);
+    context
+        .environmental_constraints
+        .must_include
+        .insert("target=epsilon".to_string());
+    let eeg = compile_thought(&context, &mut memory);
+    assert_eq!(eeg.nodes.len(), 2);
+    assert!(eeg.metadata.estimated_execution_time <= 2.0);
+    assert!(eeg_fragments(&eeg).contains(&ids[4]));
+}
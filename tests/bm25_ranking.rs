@@ -0,0 +1,138 @@
+use c_mer::*;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+#[test]
+fn test_rare_terms_have_higher_idf() {
+    let mut memory = create_test_memory();
+    for i in 0..5 {
+        let fragment =
+            create_causal_rule_fragment(&format!("system check {}", i), "system healthy", 0.8);
+        memory.insert_fragment(fragment, Vec::new());
+    }
+    let rare = create_causal_rule_fragment("kernel panic", "system reboot", 0.8);
+    memory.insert_fragment(rare, Vec::new());
+
+    assert_eq!(memory.text_index.document_frequency("system"), 6);
+    assert_eq!(memory.text_index.document_frequency("kernel"), 1);
+    assert!(memory.text_index.idf("kernel") > memory.text_index.idf("system"));
+}
+
+#[test]
+fn test_multi_term_match_beats_generic_tag() {
+    let mut memory = create_test_memory();
+    let generic = create_entity_relation_fragment("system", "runs", "services");
+    let specific = create_causal_rule_fragment("eviction in the redis cache", "latency", 0.8);
+    memory.insert_fragment(generic.clone(), Vec::new());
+    memory.insert_fragment(specific.clone(), Vec::new());
+    for i in 0..4 {
+        let filler = create_entity_relation_fragment(&format!("system_{}", i), "runs", "jobs");
+        memory.insert_fragment(filler, Vec::new());
+    }
+
+    let mut context = create_test_context("redis cache eviction", "system", 0.5);
+    context.max_fragments = 1;
+    let activated = memory.activate_fragments(&context);
+
+    assert!(activated.contains(&specific.id));
+    assert!(!activated.contains(&generic.id));
+}
+
+#[test]
+fn test_shorter_documents_score_higher() {
+    let mut index = Bm25Index::default();
+    let tokenizer = Tokenizer::default();
+    let short = uuid::Uuid::new_v4();
+    let long = uuid::Uuid::new_v4();
+    index.insert(short, "database timeout", &tokenizer);
+    index.insert(
+        long,
+        "database timeout after retry storm during nightly backup window",
+        &tokenizer,
+    );
+
+    let scores = index.score_terms(&["databas".to_string()]);
+    assert!(scores[&short] > scores[&long]);
+
+    let ranked = index.search("database", &tokenizer);
+    assert_eq!(ranked[0].0, short);
+
+    let mut custom = Tokenizer::default();
+    custom.stop_words.insert("database".to_string());
+    let mut filtered = Bm25Index::default();
+    filtered.insert(short, "database timeout", &custom);
+    assert_eq!(filtered.document_frequency("databas"), 0);
+    assert_eq!(filtered.document_frequency("timeout"), 1);
+}
+
+#[test]
+fn test_text_hits_do_not_override_weaker_matches() {
+    let mut memory = create_test_memory();
+    let specific = create_causal_rule_fragment("eviction in the redis cache", "latency", 0.8);
+    let warmup = create_entity_relation_fragment("warmup", "fills", "cache");
+    memory.insert_fragment(specific.clone(), Vec::new());
+    memory.insert_fragment(warmup.clone(), Vec::new());
+
+    let context = create_test_context("redis cache eviction", "system", 0.0);
+    let (_, explanation) = memory.activate_fragments_explained(&context);
+    let score = |id| {
+        explanation
+            .fragment(id)
+            .and_then(|entry| entry.score.clone())
+            .unwrap()
+    };
+
+    assert_eq!(score(specific.id).match_weight, 1.0);
+    let weak = score(warmup.id);
+    assert!(weak.match_weight < 1.0);
+    assert!(weak.match_weight >= weak.text_match / memory.text_index.config.weight - 1e-9);
+}
+
+#[test]
+fn test_removal_updates_statistics() {
+    let mut memory = create_test_memory();
+    let first = create_causal_rule_fragment("disk full", "write failure", 0.8);
+    let second = create_causal_rule_fragment("disk slow", "high latency", 0.8);
+    memory.insert_fragment(first.clone(), Vec::new());
+    memory.insert_fragment(second.clone(), Vec::new());
+    let total = memory.text_index.total_length;
+
+    memory.remove_fragment(first.id);
+
+    assert_eq!(memory.text_index.len(), 1);
+    assert_eq!(memory.text_index.document_frequency("disk"), 1);
+    assert_eq!(memory.text_index.document_frequency("full"), 0);
+    assert!(memory.text_index.total_length < total);
+    assert_eq!(memory.bm25_search("disk")[0].0, second.id);
+}
+
+#[test]
+fn test_disabled_index_scores_nothing() {
+    let mut memory = create_test_memory();
+    memory.text_index = Bm25Index::new(Bm25Config {
+        enabled: false,
+        ..Bm25Config::default()
+    });
+    let fragment = create_causal_rule_fragment("memory leak", "crash", 0.8);
+    memory.insert_fragment(fragment, Vec::new());
+
+    assert!(memory.bm25_search("memory leak").is_empty());
+}
+
+#[test]
+fn test_index_rebuilt_on_load() {
+    let mut memory = create_test_memory();
+    let fragment = create_causal_rule_fragment("certificate expired", "tls error", 0.8);
+    memory.insert_fragment(fragment.clone(), Vec::new());
+    memory.text_index = Bm25Index::default();
+
+    let path = std::env::temp_dir().join(format!("bm25_{}.cmc", fragment.id));
+    memory.save(&path).unwrap();
+    let loaded = MemoryGraph::load(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(loaded.text_index.len(), 1);
+    assert_eq!(loaded.bm25_search("certificates")[0].0, fragment.id);
+}
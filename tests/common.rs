@@ -78,6 +78,38 @@ pub fn create_entity_relation_fragment(entity: &str, relation: &str, target: &st
     }
 }
 
+pub fn create_deploy_step_fragment(target: &str, confidence: f64) -> MFragment {
+    let mut fragment = create_entity_relation_fragment("deploy", "runs", target);
+    fragment.confidence = confidence;
+    fragment
+}
+
+pub fn create_personal_fact_fragment(
+    person: &str,
+    fact_type: &str,
+    value: &str,
+    confidence: f64,
+) -> MFragment {
+    MFragment {
+        id: Uuid::new_v4(),
+        fragment_type: FragmentType::PersonalFact,
+        content: FragmentContent::PersonalFact {
+            person: person.to_string(),
+            fact_type: fact_type.to_string(),
+            value: value.to_string(),
+            confidence,
+        },
+        confidence,
+        salience: 1.0,
+        emotional_tag: 0.0,
+        reinforcement_count: 0,
+        last_activated: 0.0,
+        activation_history: Vec::new(),
+        created_at: current_timestamp(),
+        decay_rate: 0.001,
+    }
+}
+
 pub fn assert_fragments_equal(f1: &MFragment, f2: &MFragment) -> bool {
     f1.fragment_type == f2.fragment_type
         && f1.confidence == f2.confidence
@@ -116,3 +148,26 @@ pub fn populate_memory_with_fragments(memory: &mut MemoryGraph, fragments: Vec<M
         memory.insert_fragment(fragment, Vec::new());
     }
 }
+
+pub struct OfflineLLM;
+
+impl LLMProvider for OfflineLLM {
+    fn extract_semantics(&self, _text: &str) -> std::result::Result<SemanticEvent, LLMError> {
+        Err(LLMError::NetworkError("offline".to_string()))
+    }
+
+    fn format_response_from_memory(
+        &self,
+        _user_query: &str,
+        memory_data: &MemoryData,
+    ) -> std::result::Result<String, LLMError> {
+        Ok(memory_data.to_compact_string())
+    }
+
+    fn extract_goal_and_domain(
+        &self,
+        query: &str,
+    ) -> std::result::Result<(String, String), LLMError> {
+        Ok((query.to_string(), "general".to_string()))
+    }
+}
@@ -0,0 +1,190 @@
+use c_mer::*;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+fn faq_memory() -> (MemoryGraph, MFragment) {
+    let mut memory = create_test_memory();
+    let reset = create_entity_relation_fragment("password", "reset_via", "settings page");
+    memory.insert_fragment(reset.clone(), Vec::new());
+    (memory, reset)
+}
+
+fn node_ids(eeg: &EEG) -> HashSet<Uuid> {
+    eeg.nodes.keys().copied().collect()
+}
+
+#[test]
+fn test_repeated_query_is_served_from_cache() {
+    let (mut memory, reset) = faq_memory();
+    let mut cache = CompilationCache::default();
+    let context = create_test_context("password", "support", 0.0);
+
+    let first = compile_thought_cached(&context, &mut memory, &mut cache);
+    let history = memory.fragments[&reset.id].activation_history.len();
+    let second = compile_thought_cached(&context, &mut memory, &mut cache);
+
+    assert!(node_ids(&first).contains(&reset.id));
+    assert_eq!(node_ids(&first), node_ids(&second));
+    assert_eq!(first.entry_point, second.entry_point);
+    assert_eq!(
+        memory.fragments[&reset.id].activation_history.len(),
+        history + 1
+    );
+    assert_eq!(
+        cache.stats(),
+        CacheStats {
+            hits: 1,
+            misses: 1,
+            ..CacheStats::default()
+        }
+    );
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_memory_mutation_invalidates_entry() {
+    let (mut memory, reset) = faq_memory();
+    let mut cache = CompilationCache::default();
+    let context = create_test_context("password", "support", 0.0);
+
+    compile_thought_cached(&context, &mut memory, &mut cache);
+    let expiry = create_entity_relation_fragment("password", "expires_after", "90 days");
+    memory.insert_fragment(expiry.clone(), Vec::new());
+    let eeg = compile_thought_cached(&context, &mut memory, &mut cache);
+
+    assert!(node_ids(&eeg).contains(&reset.id) && node_ids(&eeg).contains(&expiry.id));
+    assert_eq!(cache.stats().invalidations, 1);
+    assert_eq!((cache.stats().hits, cache.stats().misses), (0, 2));
+
+    compile_thought_cached(&context, &mut memory, &mut cache);
+    assert_eq!(cache.stats().hits, 1);
+}
+
+#[test]
+fn test_fingerprint_is_canonical() {
+    let mut a = create_test_context("password", "support", 0.0);
+    let mut b = create_test_context("password", "support", 0.0);
+    for tag in ["reset", "login", "account"] {
+        a.domain_hint.tags.insert(tag.to_string());
+    }
+    for tag in ["account", "reset", "login"] {
+        b.domain_hint.tags.insert(tag.to_string());
+    }
+    let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+    a.activation_primes.insert(first, 0.5);
+    a.activation_primes.insert(second, 0.25);
+    b.activation_primes.insert(second, 0.25);
+    b.activation_primes.insert(first, 0.5);
+
+    assert_eq!(context_fingerprint(&a), context_fingerprint(&b));
+
+    b.confidence_threshold = 0.7;
+    assert_ne!(context_fingerprint(&a), context_fingerprint(&b));
+    assert_ne!(
+        context_fingerprint(&create_test_context("password", "support", 0.0)),
+        context_fingerprint(&create_test_context("billing", "support", 0.0))
+    );
+}
+
+#[test]
+fn test_cache_is_bounded_with_lru_eviction() {
+    let (mut memory, _) = faq_memory();
+    let mut cache = CompilationCache::new(2);
+    let contexts: Vec<ContextVector> = ["password", "billing", "shipping"]
+        .iter()
+        .map(|goal| create_test_context(goal, "support", 0.0))
+        .collect();
+
+    compile_thought_cached(&contexts[0], &mut memory, &mut cache);
+    compile_thought_cached(&contexts[1], &mut memory, &mut cache);
+    compile_thought_cached(&contexts[0], &mut memory, &mut cache);
+    compile_thought_cached(&contexts[2], &mut memory, &mut cache);
+
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.stats().evictions, 1);
+    assert!(cache.get(&contexts[0], &memory).is_some());
+    assert!(cache.get(&contexts[1], &memory).is_none());
+
+    let mut disabled = CompilationCache::new(0);
+    compile_thought_cached(&contexts[0], &mut memory, &mut disabled);
+    assert!(disabled.is_empty());
+}
+
+#[test]
+fn test_revision_tracks_structural_mutations() {
+    let (mut memory, reset) = faq_memory();
+    let other = create_entity_relation_fragment("account", "locked_after", "5 attempts");
+    memory.insert_fragment(other.clone(), Vec::new());
+
+    let mut revision = memory.revision();
+    memory.activate_fragments(&create_test_context("password", "support", 0.0));
+    assert_eq!(memory.revision(), revision);
+
+    memory.add_edge(create_test_edges(reset.id, other.id, 0.8));
+    assert_ne!(memory.revision(), revision);
+    revision = memory.revision();
+
+    memory.reinforce_fragment(
+        reset.id,
+        &Outcome {
+            result: "resolved".to_string(),
+            explanation: None,
+            confidence: 0.9,
+            outcome_type: OutcomeType::Success,
+        },
+    );
+    assert_ne!(memory.revision(), revision);
+    revision = memory.revision();
+
+    memory.record_co_activation(&[reset.id, other.id]);
+    assert_ne!(memory.revision(), revision);
+    revision = memory.revision();
+
+    memory.remove_fragment(other.id);
+    assert_ne!(memory.revision(), revision);
+    revision = memory.revision();
+
+    memory.touch();
+    assert_ne!(memory.revision(), revision);
+}
+
+#[test]
+fn test_distinct_graphs_never_share_entries() {
+    let (mut memory, _) = faq_memory();
+    let (mut other, _) = faq_memory();
+    let mut cache = CompilationCache::default();
+    let context = create_test_context("password", "support", 0.0);
+
+    assert_ne!(memory.revision(), other.revision());
+    compile_thought_cached(&context, &mut memory, &mut cache);
+    compile_thought_cached(&context, &mut other, &mut cache);
+
+    assert_eq!(cache.stats().hits, 0);
+    assert_eq!(cache.stats().invalidations, 1);
+    assert_eq!(memory.clone().revision(), memory.revision());
+}
+
+#[test]
+fn test_agent_cache_hits_only_when_priming_and_memory_are_unchanged() {
+    let mut agent = CMCAgent::new(Box::new(OfflineLLM));
+    agent.process("My favorite color is blue.").unwrap();
+
+    agent.query_memory("favorite color", "personal");
+    let before = agent.compilation_cache().stats();
+    agent.query_memory("favorite color", "personal");
+    let after = agent.compilation_cache().stats();
+    assert_eq!(after.hits, before.hits + 1);
+    assert_eq!(after.misses, before.misses);
+
+    agent.process("What is my favorite color?").unwrap();
+    let before = agent.compilation_cache().stats();
+    agent.process("What is my favorite color?").unwrap();
+    let after = agent.compilation_cache().stats();
+    assert!(!agent.working_memory().primed().is_empty());
+    assert_eq!(after.hits, before.hits);
+    assert_eq!(after.misses, before.misses + 1);
+}
@@ -0,0 +1,184 @@
+use c_mer::*;
+use std::collections::HashSet;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+struct RedactionPass {
+    term: String,
+}
+
+impl CompilerPass for RedactionPass {
+    fn name(&self) -> &str {
+        "redact"
+    }
+
+    fn run(&self, ir: &mut CompilationIR, _context: &ContextVector, memory: &MemoryGraph) {
+        ir.nodes.retain(|node| {
+            memory
+                .fragments
+                .get(&node.id)
+                .map(|fragment| !format!("{:?}", fragment.content).contains(&self.term))
+                .unwrap_or(true)
+        });
+    }
+}
+
+fn conflicting_memory() -> (MemoryGraph, MFragment, MFragment) {
+    let mut memory = create_test_memory();
+    let likes = create_entity_relation_fragment("alice", "likes", "pizza");
+    let mut dislikes = create_entity_relation_fragment("alice", "dislikes", "pizza");
+    dislikes.confidence = 0.7;
+    memory.insert_fragment(likes.clone(), Vec::new());
+    memory.insert_fragment(dislikes.clone(), Vec::new());
+    (memory, likes, dislikes)
+}
+
+fn node_ids(eeg: &EEG) -> HashSet<uuid::Uuid> {
+    eeg.nodes
+        .values()
+        .filter(|node| node.node_type == NodeType::FragmentNode)
+        .map(|node| node.id)
+        .collect()
+}
+
+#[test]
+fn test_standard_pipeline_matches_compile_thought() {
+    let (mut memory, likes, dislikes) = conflicting_memory();
+    let context = create_test_context("alice", "general", 0.0);
+
+    let mut manager = PassManager::default();
+    assert_eq!(
+        manager.pass_names(),
+        vec![
+            "resolve_conflicts",
+            "fill_gaps",
+            "order_fragments",
+            "add_branching",
+            "prune_resources"
+        ]
+    );
+
+    let managed = manager.compile(&context, &mut memory);
+    let direct = compile_thought(&context, &mut memory);
+    assert_eq!(node_ids(&managed), node_ids(&direct));
+    assert!(node_ids(&managed).contains(&likes.id));
+    assert!(!node_ids(&managed).contains(&dislikes.id));
+}
+
+#[test]
+fn test_stats_record_each_pass() {
+    let mut memory = create_test_memory();
+    let solid = create_entity_relation_fragment("deploy", "runs", "build");
+    let mut shaky = create_entity_relation_fragment("deploy", "runs", "lint");
+    shaky.confidence = 0.65;
+    memory.insert_fragment(solid, Vec::new());
+    memory.insert_fragment(shaky, Vec::new());
+
+    let mut manager = PassManager::default();
+    manager.compile(&create_test_context("deploy", "general", 0.5), &mut memory);
+    let stats = manager.stats();
+
+    assert_eq!(stats.len(), 5);
+    assert_eq!(stats[0].nodes_before, 2);
+    for pair in stats.windows(2) {
+        assert_eq!(pair[0].nodes_after, pair[1].nodes_before);
+    }
+    let pruning = stats.last().unwrap();
+    assert_eq!(pruning.name, "prune_resources");
+    assert_eq!((pruning.nodes_before, pruning.nodes_after), (2, 1));
+    assert!(stats.iter().all(|s| s.duration_secs >= 0.0));
+}
+
+#[test]
+fn test_disabled_pass_is_skipped() {
+    let (mut memory, likes, dislikes) = conflicting_memory();
+    let context = create_test_context("alice", "general", 0.0);
+
+    let mut manager = PassManager::default();
+    manager.disable("resolve_conflicts");
+    assert!(!manager.is_enabled("resolve_conflicts"));
+    let eeg = manager.compile(&context, &mut memory);
+
+    assert!(node_ids(&eeg).contains(&likes.id) && node_ids(&eeg).contains(&dislikes.id));
+    assert!(!eeg
+        .nodes
+        .values()
+        .any(|node| node.node_type == NodeType::ConflictNode));
+    assert!(manager
+        .stats()
+        .iter()
+        .all(|s| s.name != "resolve_conflicts"));
+
+    manager.enable("resolve_conflicts");
+    let eeg = manager.compile(&context, &mut memory);
+    assert!(!node_ids(&eeg).contains(&dislikes.id));
+}
+
+#[test]
+fn test_custom_pass_runs_where_inserted() {
+    let mut memory = create_test_memory();
+    let public = create_entity_relation_fragment("server", "uses", "tls");
+    let secret = create_entity_relation_fragment("server", "password", "hunter2");
+    memory.insert_fragment(public.clone(), Vec::new());
+    memory.insert_fragment(secret.clone(), Vec::new());
+
+    let mut manager = PassManager::default();
+    let redact = RedactionPass {
+        term: "hunter2".to_string(),
+    };
+    assert!(manager.insert_before("prune_resources", Box::new(redact)));
+    assert_eq!(manager.pass_names()[4], "redact");
+
+    let eeg = manager.compile(&create_test_context("server", "general", 0.0), &mut memory);
+    assert!(node_ids(&eeg).contains(&public.id));
+    assert!(!node_ids(&eeg).contains(&secret.id));
+    assert_eq!(manager.stats()[4].nodes_after, 1);
+}
+
+#[test]
+fn test_passes_can_be_reordered_and_removed() {
+    let mut manager = PassManager::default();
+
+    assert!(manager.move_pass("add_branching", 0));
+    assert_eq!(manager.pass_names()[0], "add_branching");
+    assert!(manager.remove_pass("fill_gaps").is_some());
+    assert!(!manager.is_enabled("fill_gaps"));
+    assert!(!manager.insert_after(
+        "fill_gaps",
+        Box::new(RedactionPass {
+            term: String::new()
+        })
+    ));
+    assert!(!manager.move_pass("fill_gaps", 1));
+    assert_eq!(manager.pass_names().len(), 4);
+
+    let replaced = manager.replace_pass(Box::new(PruningPass::default()));
+    assert_eq!(
+        replaced.map(|pass| pass.name().to_string()),
+        Some("prune_resources".to_string())
+    );
+    assert!(PassManager::new().pass_names().is_empty());
+}
+
+#[test]
+fn test_run_operates_on_intermediate_representation() {
+    let (mut memory, likes, dislikes) = conflicting_memory();
+    let context = create_test_context("alice", "general", 0.0);
+    let mut ir = CompilationIR::from_activation(&context, &mut memory);
+
+    assert_eq!(ir.nodes.len(), 2);
+    assert!(ir.activated.contains(&likes.id) && ir.activated.contains(&dislikes.id));
+
+    let mut manager = PassManager::new();
+    manager.add_pass(Box::new(ConflictResolutionPass::new(Box::new(
+        ConfidenceResolver,
+    ))));
+    manager.run(&mut ir, &context, &memory);
+
+    assert_eq!(ir.nodes.len(), 2);
+    assert_eq!(ir.nodes[0].node_type, NodeType::ConflictNode);
+    assert_eq!(ir.nodes[1].id, likes.id);
+    assert_eq!(ir.nodes[1].order, 1);
+}
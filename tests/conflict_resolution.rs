@@ -0,0 +1,188 @@
+use c_mer::*;
+use std::collections::HashMap;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+fn transition(entity: &str, from: &str, to: &str, condition: Option<&str>) -> MFragment {
+    let mut fragment = create_entity_relation_fragment(entity, from, to);
+    fragment.fragment_type = FragmentType::StateTransition;
+    fragment.content = FragmentContent::StateTransition {
+        entity: entity.to_string(),
+        from_state: from.to_string(),
+        to_state: to.to_string(),
+        condition: condition.map(|c| c.to_string()),
+        timestamp: None,
+        confidence: 0.8,
+    };
+    fragment
+}
+
+fn conflict_nodes(eeg: &EEG) -> Vec<(Vec<uuid::Uuid>, Option<uuid::Uuid>)> {
+    eeg.nodes
+        .values()
+        .filter_map(|node| match &node.content {
+            NodeContent::Conflict {
+                conflicting_fragments,
+                selected_fragment,
+            } => Some((conflicting_fragments.clone(), *selected_fragment)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_compile_emits_conflict_node_for_contradictory_facts() {
+    let mut memory = create_test_memory();
+    let berlin = create_personal_fact_fragment("alice", "city", "berlin", 0.9);
+    let paris = create_personal_fact_fragment("alice", "city", "paris", 0.7);
+    let job = create_personal_fact_fragment("alice", "job", "pilot", 0.9);
+    for fragment in [&berlin, &paris, &job] {
+        memory.insert_fragment(fragment.clone(), Vec::new());
+    }
+
+    let context = create_test_context("alice", "general", 0.3);
+    let eeg = compile_thought(&context, &mut memory);
+
+    let conflicts = conflict_nodes(&eeg);
+    assert_eq!(conflicts.len(), 1);
+    let (group, selected) = &conflicts[0];
+    assert_eq!(group.len(), 2);
+    assert!(group.contains(&berlin.id) && group.contains(&paris.id));
+    assert_eq!(*selected, Some(berlin.id));
+    assert!(eeg.nodes.contains_key(&berlin.id));
+    assert!(!eeg.nodes.contains_key(&paris.id));
+
+    let result = execute_eeg(&eeg, &mut memory);
+    assert!(!result.outcome.result.contains("paris"));
+}
+
+#[test]
+fn test_opposing_likes_and_dislikes_conflict_across_variants() {
+    let likes = create_entity_relation_fragment("Alice", "likes", "pizza");
+    let dislikes = create_personal_fact_fragment("alice", "dislikes", "Pizza", 0.8);
+    let likes_pasta = create_entity_relation_fragment("alice", "likes", "pasta");
+    let preference = create_personal_fact_fragment("alice", "preference", "sushi", 0.8);
+
+    assert!(fragments_conflict(&likes, &dislikes));
+    assert!(fragments_conflict(&dislikes, &likes));
+    assert!(!fragments_conflict(&likes, &likes_pasta));
+    assert!(!fragments_conflict(&likes, &preference));
+    assert!(!fragments_conflict(&dislikes, &likes_pasta));
+    assert!(!fragments_conflict(
+        &create_entity_relation_fragment("service", "calls", "auth"),
+        &create_entity_relation_fragment("service", "calls", "billing"),
+    ));
+}
+
+#[test]
+fn test_contradictory_state_transitions() {
+    let approved = transition("order", "pending", "approved", Some("payment ok"));
+    let rejected = transition("order", "pending", "rejected", Some("payment ok"));
+    let failed = transition("order", "pending", "rejected", Some("payment failed"));
+
+    assert!(fragments_conflict(&approved, &rejected));
+    assert!(!fragments_conflict(&approved, &failed));
+    assert!(!fragments_conflict(&rejected, &failed));
+}
+
+#[test]
+fn test_detect_conflicts_groups_transitively() {
+    let mut memory = create_test_memory();
+    let red = create_personal_fact_fragment("bob", "car", "red", 0.8);
+    let blue = create_personal_fact_fragment("bob", "car", "blue", 0.8);
+    let green = create_personal_fact_fragment("bob", "car", "green", 0.8);
+    let unrelated = create_personal_fact_fragment("bob", "pet", "cat", 0.8);
+    for fragment in [&red, &blue, &green, &unrelated] {
+        memory.insert_fragment(fragment.clone(), Vec::new());
+    }
+
+    let groups = detect_conflicts(&[red.id, blue.id, green.id, unrelated.id], &memory);
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].len(), 3);
+    assert!(!groups[0].contains(&unrelated.id));
+    assert!(detect_conflicts(&[red.id, unrelated.id], &memory).is_empty());
+}
+
+#[test]
+fn test_resolution_strategies_pick_different_winners() {
+    let memory = create_test_memory();
+    let mut old_confident = create_personal_fact_fragment("carol", "team", "platform", 0.95);
+    old_confident.created_at -= 1000.0;
+    let mut new_reinforced = create_personal_fact_fragment("carol", "team", "search", 0.7);
+    new_reinforced.reinforcement_count = 5;
+    let candidates = [&old_confident, &new_reinforced];
+
+    assert_eq!(
+        ConfidenceResolver.select(&candidates, &memory),
+        Some(old_confident.id)
+    );
+    assert_eq!(
+        RecencyResolver.select(&candidates, &memory),
+        Some(new_reinforced.id)
+    );
+    assert_eq!(
+        ReinforcementResolver.select(&candidates, &memory),
+        Some(new_reinforced.id)
+    );
+    assert_eq!(ConfidenceResolver.select(&[], &memory), None);
+}
+
+#[test]
+fn test_source_trust_resolver_uses_ingest_source() {
+    let mut memory = create_test_memory();
+    let rumor = create_personal_fact_fragment("dave", "role", "manager", 0.9);
+    let hr = create_personal_fact_fragment("dave", "role", "engineer", 0.7);
+    let source = |name: &str| IngestSource {
+        domain: None,
+        context: [("source".to_string(), name.to_string())]
+            .into_iter()
+            .collect(),
+    };
+    memory.insert_fragment_from(rumor.clone(), Vec::new(), &source("chat"));
+    memory.insert_fragment_from(hr.clone(), Vec::new(), &source("HR"));
+
+    let resolver = SourceTrustResolver {
+        trust: HashMap::from([("hr".to_string(), 0.95), ("chat".to_string(), 0.2)]),
+        ..SourceTrustResolver::default()
+    };
+    assert_eq!(resolver.select(&[&rumor, &hr], &memory), Some(hr.id));
+
+    let context = create_test_context("dave", "general", 0.3);
+    let eeg = compile_thought_with_resolver(&context, &mut memory, None, &resolver);
+    assert_eq!(conflict_nodes(&eeg)[0].1, Some(hr.id));
+    assert!(!eeg.nodes.contains_key(&rumor.id));
+
+    memory.remove_fragment(hr.id);
+    assert!(!memory.fragment_sources.contains_key(&hr.id));
+}
+
+#[test]
+fn test_required_fragments_survive_conflict_resolution() {
+    let mut memory = create_test_memory();
+    let berlin = create_personal_fact_fragment("alice", "city", "berlin", 0.9);
+    let paris = create_personal_fact_fragment("alice", "city", "paris", 0.7);
+    memory.insert_fragment(berlin.clone(), Vec::new());
+    memory.insert_fragment(paris.clone(), Vec::new());
+
+    let mut context = create_test_context("alice", "general", 0.3);
+    context
+        .environmental_constraints
+        .must_include
+        .insert("paris".to_string());
+    let eeg = compile_thought(&context, &mut memory);
+
+    assert_eq!(conflict_nodes(&eeg)[0].1, Some(paris.id));
+    assert!(eeg.nodes.contains_key(&paris.id));
+    assert!(!eeg.nodes.contains_key(&berlin.id));
+
+    context
+        .environmental_constraints
+        .must_include
+        .insert("berlin".to_string());
+    let eeg = compile_thought(&context, &mut memory);
+
+    assert_eq!(conflict_nodes(&eeg)[0].1, Some(berlin.id));
+    assert!(eeg.nodes.contains_key(&paris.id) && eeg.nodes.contains_key(&berlin.id));
+}
@@ -0,0 +1,143 @@
+use c_mer::*;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+fn garden_memory() -> MemoryGraph {
+    let mut memory = create_test_memory();
+    memory.insert_fragment(
+        create_entity_relation_fragment("tomato", "grows_in", "greenhouse"),
+        Vec::new(),
+    );
+    memory.insert_fragment(
+        create_causal_rule_fragment("overwatering", "root rot", 0.8),
+        Vec::new(),
+    );
+    let mut semantic = std::collections::HashMap::new();
+    semantic.insert("soil_ph".to_string(), "6.5".to_string());
+    semantic.insert("variety".to_string(), "roma".to_string());
+    memory.insert_fragment(
+        MFragment {
+            id: uuid::Uuid::new_v4(),
+            fragment_type: FragmentType::SemanticAtom,
+            content: FragmentContent::SemanticAtom {
+                atom_id: None,
+                atom_type: AtomType::Entity,
+                content: semantic,
+            },
+            confidence: 0.9,
+            salience: 1.0,
+            emotional_tag: 0.0,
+            reinforcement_count: 0,
+            last_activated: 0.0,
+            activation_history: Vec::new(),
+            created_at: current_timestamp(),
+            decay_rate: 0.001,
+        },
+        Vec::new(),
+    );
+    memory
+}
+
+#[test]
+fn test_heuristic_generator_matches_generate_context() {
+    let memory = create_test_memory();
+    let generated = HeuristicContextGenerator.generate("debug HTTP 404 error", "web", 0.4, &memory);
+    let legacy = generate_context("debug HTTP 404 error", "web", 0.4);
+
+    assert_eq!(generated.goal.goal_type, legacy.goal.goal_type);
+    assert_eq!(generated.goal.parameters, legacy.goal.parameters);
+    assert_eq!(
+        generated.attention_window.focus_entities,
+        legacy.attention_window.focus_entities
+    );
+    assert_eq!(generated.domain_hint.tags, legacy.domain_hint.tags);
+    assert_eq!(generated.goal.parameters["error_code"], "404");
+}
+
+#[test]
+fn test_vocabulary_generator_finds_graph_entities() {
+    let memory = garden_memory();
+    let generator = VocabularyContextGenerator::default();
+    let context = generator.generate(
+        "tomato keeps wilting in the greenhouse",
+        "gardening",
+        0.3,
+        &memory,
+    );
+
+    let entities = &context.attention_window.focus_entities;
+    assert!(entities.contains("tomato"));
+    assert!(entities.contains("greenhouse"));
+    assert!(!entities.contains("wilting"));
+    assert!(context.domain_hint.tags.contains("gardening"));
+    assert!(context.domain_hint.tags.contains("tomato"));
+    assert!(!context.domain_hint.tags.contains("http"));
+}
+
+#[test]
+fn test_vocabulary_generator_reads_relations_and_parameters() {
+    let memory = garden_memory();
+    let generator = VocabularyContextGenerator::default();
+    let context = generator.generate(
+        "which roma tomato grows in the greenhouse",
+        "gardening",
+        0.3,
+        &memory,
+    );
+
+    assert!(context
+        .attention_window
+        .focus_relations
+        .contains("grows_in"));
+    assert_eq!(
+        context.goal.parameters.get("variety").map(String::as_str),
+        Some("roma")
+    );
+}
+
+#[test]
+fn test_goal_type_comes_from_matched_fragments() {
+    let memory = garden_memory();
+    let generator = VocabularyContextGenerator::default();
+
+    let context = generator.generate("overwatering", "gardening", 0.3, &memory);
+    assert_eq!(context.goal.goal_type, GoalType::Debug);
+
+    let context = generator.generate("tomato", "gardening", 0.3, &memory);
+    assert_eq!(context.goal.goal_type, GoalType::Learn);
+
+    let context = generator.generate("forecast the tomato harvest", "gardening", 0.3, &memory);
+    assert_eq!(context.goal.goal_type, GoalType::Predict);
+}
+
+#[test]
+fn test_unknown_goal_falls_back_to_default_goal_type() {
+    let memory = create_test_memory();
+    let generator = VocabularyContextGenerator {
+        default_goal_type: GoalType::Explain,
+        ..VocabularyContextGenerator::default()
+    };
+    let context = generator.generate("something unrelated", "misc", 0.3, &memory);
+
+    assert_eq!(context.goal.goal_type, GoalType::Explain);
+    assert!(context.attention_window.focus_entities.is_empty());
+    assert_eq!(
+        context.domain_hint.tags,
+        std::collections::HashSet::from(["misc".to_string()])
+    );
+}
+
+#[test]
+fn test_vocabulary_context_activates_matching_fragments() {
+    let mut memory = garden_memory();
+    let generator = VocabularyContextGenerator::default();
+    let context = generator.generate("tomato in the greenhouse", "gardening", 0.3, &memory);
+    let activated = memory.activate_fragments(&context);
+
+    assert!(activated.iter().any(|id| matches!(
+        &memory.fragments[id].content,
+        FragmentContent::EntityRelation { entity, .. } if entity == "tomato"
+    )));
+}
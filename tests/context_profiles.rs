@@ -0,0 +1,142 @@
+use c_mer::*;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+const PROFILES: &str = r#"
+[profiles.debugging]
+confidence_threshold = 0.4
+max_fragments = 30
+time_pressure = 0.7
+domain_tags = ["stacktrace"]
+exclusions = ["confidential"]
+
+[profiles.debugging.emotional_bias]
+frustration = 0.9
+
+[profiles.incident]
+domain = "operations"
+must_include = ["target=runbook"]
+
+[profiles.incident.resource_limits]
+max_nodes = 4
+"#;
+
+#[test]
+fn test_toml_profile_overrides_context_defaults() {
+    let profiles = ContextProfiles::from_toml(PROFILES).unwrap();
+    let profile = profiles.get("debugging").unwrap();
+    let mut context = create_test_context("fix the crash", "general", 0.3);
+    let curiosity = context.emotional_bias.curiosity;
+    profile.apply(&mut context);
+
+    assert_eq!(context.confidence_threshold, 0.4);
+    assert_eq!(context.max_fragments, 30);
+    assert_eq!(context.time_pressure, 0.7);
+    assert_eq!(context.emotional_bias.urgency, 0.7);
+    assert_eq!(context.emotional_bias.frustration, 0.9);
+    assert_eq!(context.emotional_bias.curiosity, curiosity);
+    assert!(context.domain_hint.tags.contains("stacktrace"));
+    assert!(context
+        .attention_window
+        .exclusion_patterns
+        .contains("confidential"));
+}
+
+#[test]
+fn test_json_profiles_match_toml() {
+    let json = r#"{"profiles": {"debugging": {
+        "confidence_threshold": 0.4,
+        "max_fragments": 30,
+        "emotional_bias": {"frustration": 0.9}
+    }}}"#;
+    let from_json = ContextProfiles::from_json(json).unwrap();
+    let from_toml = ContextProfiles::from_toml(PROFILES).unwrap();
+
+    let json_profile = from_json.get("debugging").unwrap();
+    let toml_profile = from_toml.get("debugging").unwrap();
+    assert_eq!(
+        json_profile.confidence_threshold,
+        toml_profile.confidence_threshold
+    );
+    assert_eq!(json_profile.max_fragments, toml_profile.max_fragments);
+    assert_eq!(
+        json_profile.emotional_bias.frustration,
+        toml_profile.emotional_bias.frustration
+    );
+}
+
+#[test]
+fn test_load_picks_format_from_extension() {
+    let id = uuid::Uuid::new_v4();
+    let toml_path = std::env::temp_dir().join(format!("profiles_{}.toml", id));
+    let json_path = std::env::temp_dir().join(format!("profiles_{}.json", id));
+    std::fs::write(&toml_path, PROFILES).unwrap();
+    std::fs::write(
+        &json_path,
+        r#"{"profiles": {"quick": {"max_fragments": 5}}}"#,
+    )
+    .unwrap();
+
+    let from_toml = ContextProfiles::load(&toml_path).unwrap();
+    let from_json = ContextProfiles::load(&json_path).unwrap();
+    std::fs::remove_file(&toml_path).ok();
+    std::fs::remove_file(&json_path).ok();
+
+    assert_eq!(from_toml.names(), vec!["debugging", "incident"]);
+    assert_eq!(from_json.get("quick").unwrap().max_fragments, Some(5));
+    assert!(matches!(
+        from_json.get("debugging"),
+        Err(ProfileError::UnknownProfile(name)) if name == "debugging"
+    ));
+    assert!(matches!(
+        ContextProfiles::load(std::env::temp_dir().join(format!("missing_{}.toml", id))),
+        Err(ProfileError::IoError(_))
+    ));
+}
+
+#[test]
+fn test_misspelled_fields_are_rejected() {
+    let result = ContextProfiles::from_toml("[profiles.debugging]\nmax_fragment = 10\n");
+    assert!(matches!(result, Err(ProfileError::ParseError(_))));
+
+    let result = ContextProfiles::from_toml("[profiles.debugging.emotional_bias]\nanger = 0.5\n");
+    assert!(matches!(result, Err(ProfileError::ParseError(_))));
+}
+
+#[test]
+fn test_generate_applies_profile_domain_and_constraints() {
+    let mut memory = create_test_memory();
+    let runbook = create_entity_relation_fragment("pager", "links", "runbook");
+    memory.insert_fragment(runbook.clone(), Vec::new());
+
+    let profiles = ContextProfiles::from_toml(PROFILES).unwrap();
+    let context = profiles.get("incident").unwrap().generate(
+        &HeuristicContextGenerator,
+        "database is down",
+        "general",
+        0.3,
+        &memory,
+    );
+
+    assert_eq!(context.domain_hint.domain, "operations");
+    assert!(context
+        .attention_window
+        .focus_domains
+        .contains("operations"));
+    assert_eq!(
+        context.environmental_constraints.resource_limits["max_nodes"],
+        4.0
+    );
+    assert!(memory.activate_fragments(&context).contains(&runbook.id));
+}
+
+#[test]
+fn test_bundled_profiles_file_parses() {
+    let profiles = ContextProfiles::from_toml(include_str!("../profiles.toml")).unwrap();
+    let debugging = profiles.get("debugging").unwrap();
+
+    assert!(debugging.max_fragments.is_some());
+    assert!(debugging.exclusions.contains(&"confidential".to_string()));
+}
@@ -0,0 +1,175 @@
+use c_mer::*;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+fn pattern(goals: &[&str], domains: &[&str], threshold: f64) -> ContextPattern {
+    ContextPattern {
+        goal_patterns: goals.iter().map(|g| g.to_string()).collect(),
+        domain_hints: domains.iter().map(|d| d.to_string()).collect(),
+        confidence_threshold: threshold,
+    }
+}
+
+fn module(activation_condition: ContextPattern) -> CompiledModule {
+    CompiledModule {
+        id: Uuid::new_v4(),
+        module_type: ModuleType::FSM,
+        code: Vec::new(),
+        input_signature: InputSignature {
+            parameters: Vec::new(),
+            context_requirements: Vec::new(),
+        },
+        output_signature: OutputSignature {
+            return_type: "Outcome".to_string(),
+            side_effects: Vec::new(),
+        },
+        activation_condition,
+        confidence: 0.9,
+        usage_count: 0,
+        success_count: 0,
+        failure_count: 0,
+        last_used: 0.0,
+        created_at: current_timestamp(),
+        source_pattern: Uuid::new_v4(),
+        version: 1,
+    }
+}
+
+#[test]
+fn test_similarity_ranks_related_contexts_higher() {
+    let similarity = ContextSimilarity::default();
+    let base = create_test_context("debug HTTP 404 error", "web", 0.2);
+    let close = create_test_context("debug HTTP 500 error", "web", 0.2);
+    let far = create_test_context("plan the garden layout", "gardening", 0.8);
+
+    assert!((similarity.similarity(&base, &base) - 1.0).abs() < 1e-9);
+    let close_score = similarity.similarity(&base, &close);
+    let far_score = similarity.similarity(&base, &far);
+    assert!(close_score > 0.6);
+    assert!(far_score < 0.4);
+    assert!((close_score - similarity.similarity(&close, &base)).abs() < 1e-9);
+
+    let generated = HeuristicContextGenerator.generate(
+        "debug HTTP 404 error",
+        "web",
+        0.2,
+        &create_test_memory(),
+    );
+    assert!((similarity.similarity(&base, &generated) - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_weights_select_components() {
+    let domain_only = ContextSimilarity {
+        goal_type_weight: 0.0,
+        goal_keyword_weight: 0.0,
+        domain_weight: 1.0,
+        tag_weight: 0.0,
+        emotional_weight: 0.0,
+        attention_weight: 0.0,
+        ..ContextSimilarity::default()
+    };
+    let a = create_test_context("debug HTTP 404 error", "web", 0.2);
+    let b = create_test_context("plan the garden layout", "WEB", 0.9);
+    let c = create_test_context("debug HTTP 404 error", "api", 0.2);
+
+    assert_eq!(domain_only.similarity(&a, &b), 1.0);
+    assert_eq!(domain_only.similarity(&a, &c), 0.0);
+}
+
+#[test]
+fn test_variance_reflects_context_spread() {
+    let similarity = ContextSimilarity::default();
+    let same = vec![create_test_context("debug HTTP 404 error", "web", 0.2); 4];
+    let mixed = vec![
+        create_test_context("debug HTTP 404 error", "web", 0.2),
+        create_test_context("plan the garden layout", "gardening", 0.8),
+        create_test_context("forecast sales", "finance", 0.5),
+    ];
+
+    assert!(similarity.variance(&same).abs() < 1e-9);
+    assert_eq!(similarity.variance(&same[..1]), 0.0);
+    assert!(similarity.variance(&mixed) > 0.5);
+}
+
+#[test]
+fn test_cluster_groups_similar_contexts() {
+    let similarity = ContextSimilarity::default();
+    let contexts = [
+        create_test_context("debug HTTP 404 error", "web", 0.2),
+        create_test_context("plan the garden layout", "gardening", 0.8),
+        create_test_context("debug HTTP 500 error", "web", 0.2),
+        create_test_context("plan the garden beds", "gardening", 0.8),
+    ];
+    let refs: Vec<&ContextVector> = contexts.iter().collect();
+
+    assert_eq!(similarity.cluster(&refs), vec![0, 1, 0, 1]);
+}
+
+#[test]
+fn test_module_matching_uses_terms_not_substrings() {
+    let context = create_test_context("rapid deploy of the service", "web", 0.2);
+    let api_module = module(pattern(&["api"], &["web"], 0.5));
+    let deploy_module = module(pattern(&["deploy"], &["web"], 0.5));
+    let partial_module = module(pattern(&["deploy", "rollback"], &["web"], 0.5));
+    let modules = vec![api_module, partial_module.clone(), deploy_module.clone()];
+
+    let found = find_applicable_module(&context, &modules).unwrap();
+    assert_eq!(found.id, deploy_module.id);
+
+    let strict = ContextSimilarity {
+        match_threshold: 0.9,
+        ..ContextSimilarity::default()
+    };
+    assert!(strict.matches_pattern(&deploy_module.activation_condition, &context));
+    assert!(!strict.matches_pattern(&partial_module.activation_condition, &context));
+    assert!(find_applicable_module(&context, &modules[..1]).is_none());
+}
+
+#[test]
+fn test_linter_clusters_traces_by_context() {
+    let web = create_test_context("debug HTTP 404 error", "web", 0.2);
+    let garden = create_test_context("plan the garden layout", "gardening", 0.8);
+    let path = vec![Uuid::new_v4(), Uuid::new_v4()];
+
+    let traces: Vec<ExecutionTrace> = (0..7)
+        .map(|i| ExecutionTrace {
+            eeg_id: Uuid::new_v4(),
+            context: if i < 4 { web.clone() } else { garden.clone() },
+            node_sequence: path.clone(),
+            branch_decisions: HashMap::new(),
+            execution_time: 0.1,
+            timestamp: current_timestamp(),
+        })
+        .collect();
+
+    let input = LinterInput {
+        execution_traces: traces,
+        compiled_eegs: Vec::new(),
+        execution_results: Vec::new(),
+        time_window: None,
+        min_occurrences: 3,
+    };
+    let config = LinterConfig {
+        min_occurrences: 3,
+        min_path_length: 2,
+        ..LinterConfig::default()
+    };
+    let report = run_linter(input, config);
+
+    let mut counts: Vec<usize> = report
+        .repeated_paths
+        .iter()
+        .map(|p| p.occurrence_count)
+        .collect();
+    counts.sort();
+    assert_eq!(counts, vec![3, 4]);
+    assert!(report
+        .invariant_subgraphs
+        .iter()
+        .all(|s| s.context_variance < 1e-9));
+}
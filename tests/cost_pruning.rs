@@ -0,0 +1,192 @@
+use c_mer::*;
+use std::collections::HashMap;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+fn deploy_context(time_pressure: f64) -> ContextVector {
+    create_test_context("deploy", "general", time_pressure)
+}
+
+fn limit(context: &mut ContextVector, key: &str, value: f64) {
+    context
+        .environmental_constraints
+        .resource_limits
+        .insert(key.to_string(), value);
+}
+
+#[test]
+fn test_cost_model_prefers_fragment_type_over_node_type() {
+    let model = CostModel {
+        fragment_costs: HashMap::from([(FragmentType::CausalRule, 2.5)]),
+        ..CostModel::default()
+    };
+    let rule = create_causal_rule_fragment("deploy", "restart", 0.9);
+    let relation = create_deploy_step_fragment("build", 0.9);
+
+    assert_eq!(model.node_cost(&NodeType::FragmentNode, Some(&rule)), 2.5);
+    assert_eq!(
+        model.node_cost(&NodeType::FragmentNode, Some(&relation)),
+        1.0
+    );
+    assert_eq!(model.node_cost(&NodeType::ConflictNode, None), 0.5);
+    assert_eq!(
+        model.node_cost(&NodeType::ActionNode, None),
+        model.default_cost
+    );
+    assert_eq!(model.expected_value(0.9, 2.5, 0.6), 0.9 - 1.5);
+}
+
+#[test]
+fn test_execution_time_reflects_cost_model() {
+    let mut memory = create_test_memory();
+    memory.insert_fragment(create_deploy_step_fragment("build", 0.9), Vec::new());
+    memory.insert_fragment(create_deploy_step_fragment("release", 0.8), Vec::new());
+
+    let model = CostModel {
+        fragment_costs: HashMap::from([(FragmentType::EntityRelation, 0.25)]),
+        ..CostModel::default()
+    };
+    let eeg = compile_thought_with_cost_model(
+        &deploy_context(0.0),
+        &mut memory,
+        None,
+        &ConfidenceResolver,
+        &model,
+    );
+
+    assert_eq!(eeg.nodes.len(), 2);
+    assert!(eeg.nodes.values().all(|node| node.execution_cost == 0.25));
+    assert_eq!(eeg.metadata.estimated_execution_time, 0.5);
+}
+
+#[test]
+fn test_budget_maximizes_value_per_cost() {
+    let mut memory = create_test_memory();
+    let rule = create_causal_rule_fragment("deploy", "page oncall", 0.95);
+    let build = create_deploy_step_fragment("build", 0.9);
+    let release = create_deploy_step_fragment("release", 0.85);
+    for fragment in [&rule, &build, &release] {
+        memory.insert_fragment(fragment.clone(), Vec::new());
+    }
+
+    let model = CostModel {
+        fragment_costs: HashMap::from([(FragmentType::CausalRule, 1.5)]),
+        ..CostModel::default()
+    };
+    let mut context = deploy_context(0.0);
+    limit(&mut context, "max_cost", 2.0);
+    let eeg =
+        compile_thought_with_cost_model(&context, &mut memory, None, &ConfidenceResolver, &model);
+
+    assert!(eeg.nodes.contains_key(&build.id));
+    assert!(eeg.nodes.contains_key(&release.id));
+    assert!(!eeg.nodes.contains_key(&rule.id));
+    assert!(eeg.metadata.estimated_execution_time <= 2.0);
+}
+
+#[test]
+fn test_selection_keeps_dependency_closure() {
+    let mut memory = create_test_memory();
+    let build = create_deploy_step_fragment("build", 0.7);
+    let release = create_deploy_step_fragment("release", 0.95);
+    let audit = create_deploy_step_fragment("audit", 0.9);
+    memory.insert_fragment(release.clone(), Vec::new());
+    memory.insert_fragment(
+        build.clone(),
+        vec![create_test_edges(build.id, release.id, 0.9)],
+    );
+
+    let mut context = deploy_context(0.0);
+    limit(&mut context, "max_nodes", 2.0);
+    let eeg = compile_thought(&context, &mut memory);
+    assert!(eeg.nodes.contains_key(&release.id) && eeg.nodes.contains_key(&build.id));
+
+    memory.insert_fragment(audit.clone(), Vec::new());
+    let eeg = compile_thought(&context, &mut memory);
+    assert_eq!(eeg.nodes.len(), 2);
+    assert!(eeg.nodes.contains_key(&audit.id));
+    assert!(!eeg.nodes.contains_key(&release.id));
+}
+
+#[test]
+fn test_time_pressure_and_cost_shrink_the_eeg() {
+    let mut memory = create_test_memory();
+    let solid = create_deploy_step_fragment("build", 0.9);
+    let shaky = create_deploy_step_fragment("lint", 0.65);
+    memory.insert_fragment(solid.clone(), Vec::new());
+    memory.insert_fragment(shaky.clone(), Vec::new());
+
+    let relaxed = compile_thought(&deploy_context(0.0), &mut memory);
+    assert!(relaxed.nodes.contains_key(&shaky.id));
+
+    let urgent = compile_thought(&deploy_context(0.5), &mut memory);
+    assert!(urgent.nodes.contains_key(&solid.id));
+    assert!(!urgent.nodes.contains_key(&shaky.id));
+
+    let model = CostModel {
+        fragment_costs: HashMap::from([(FragmentType::EntityRelation, 1.6)]),
+        ..CostModel::default()
+    };
+    let costly = compile_thought_with_cost_model(
+        &deploy_context(0.0),
+        &mut memory,
+        None,
+        &ConfidenceResolver,
+        &model,
+    );
+    assert!(!costly.nodes.contains_key(&solid.id));
+}
+
+#[test]
+fn test_required_fragments_bring_prerequisites_past_budget() {
+    let mut memory = create_test_memory();
+    let build = create_deploy_step_fragment("build", 0.7);
+    let release = create_deploy_step_fragment("release", 0.95);
+    memory.insert_fragment(release.clone(), Vec::new());
+    memory.insert_fragment(
+        build.clone(),
+        vec![create_test_edges(build.id, release.id, 0.9)],
+    );
+
+    let mut context = deploy_context(0.0);
+    limit(&mut context, "max_nodes", 1.0);
+    context
+        .environmental_constraints
+        .must_include
+        .insert("target=release".to_string());
+    let eeg = compile_thought(&context, &mut memory);
+
+    assert_eq!(eeg.nodes.len(), 2);
+    assert_eq!(eeg.entry_point, build.id);
+    assert_eq!(eeg.exit_points, vec![release.id]);
+}
+
+#[test]
+fn test_gap_nodes_are_priced_like_other_nodes() {
+    let mut memory = create_test_memory();
+    memory.insert_fragment(create_deploy_step_fragment("build", 0.9), Vec::new());
+    let mut context = deploy_context(0.3);
+    context
+        .attention_window
+        .focus_relations
+        .insert("causes".to_string());
+    context.attention_window.focus_domains.clear();
+    let has_gap = |eeg: &EEG| {
+        eeg.nodes
+            .values()
+            .any(|node| node.node_type == NodeType::GapFillNode)
+    };
+
+    let cheap = compile_thought(&context, &mut memory);
+    assert!(has_gap(&cheap));
+
+    let model = CostModel {
+        node_costs: HashMap::from([(NodeType::GapFillNode, 1.0)]),
+        ..CostModel::default()
+    };
+    let priced =
+        compile_thought_with_cost_model(&context, &mut memory, None, &ConfidenceResolver, &model);
+    assert!(!has_gap(&priced));
+}
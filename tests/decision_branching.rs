@@ -0,0 +1,161 @@
+use c_mer::*;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+fn decisions(eeg: &EEG) -> Vec<(String, Vec<Branch>)> {
+    eeg.nodes
+        .values()
+        .filter_map(|node| match &node.content {
+            NodeContent::Decision {
+                condition,
+                branches,
+            } => Some((condition.clone(), branches.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_alternative_outcomes_become_branches() {
+    let mut memory = create_test_memory();
+    let url = create_causal_rule_fragment("404 error", "check url", 0.9);
+    let auth = create_causal_rule_fragment("404 error", "check auth", 0.7);
+    memory.insert_fragment(url.clone(), Vec::new());
+    memory.insert_fragment(auth.clone(), Vec::new());
+
+    let context = create_test_context("404 error", "web", 0.0);
+    let eeg = compile_thought(&context, &mut memory);
+
+    let found = decisions(&eeg);
+    assert_eq!(found.len(), 1);
+    let (condition, branches) = &found[0];
+    assert_eq!(condition, "404 error");
+    assert_eq!(branches.len(), 2);
+    assert_eq!(branches[0].target_node, url.id);
+    assert_eq!(branches[0].condition, "check url");
+    assert_eq!(branches[1].target_node, auth.id);
+    assert!(branches
+        .iter()
+        .all(|b| eeg.nodes.contains_key(&b.target_node)));
+    let total: f64 = branches.iter().map(|b| b.weight).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+    assert!(branches[0].weight > branches[1].weight);
+}
+
+#[test]
+fn test_single_outcome_needs_no_decision() {
+    let mut memory = create_test_memory();
+    memory.insert_fragment(
+        create_causal_rule_fragment("disk full", "clean logs", 0.9),
+        Vec::new(),
+    );
+
+    let context = create_test_context("disk full", "ops", 0.0);
+    let eeg = compile_thought(&context, &mut memory);
+
+    assert!(decisions(&eeg).is_empty());
+}
+
+#[test]
+fn test_past_success_outweighs_confidence() {
+    let mut memory = create_test_memory();
+    let url = create_causal_rule_fragment("404 error", "check url", 0.85);
+    let mut auth = create_causal_rule_fragment("404 error", "check auth", 0.8);
+    auth.reinforcement_count = 6;
+    memory.insert_fragment(url.clone(), Vec::new());
+    memory.insert_fragment(auth.clone(), Vec::new());
+
+    let context = create_test_context("404 error", "web", 0.0);
+    let eeg = compile_thought(&context, &mut memory);
+
+    let (_, branches) = &decisions(&eeg)[0];
+    assert_eq!(branches[0].target_node, auth.id);
+    assert_eq!(branches[1].target_node, url.id);
+}
+
+#[test]
+fn test_unactivated_alternatives_are_pulled_from_memory() {
+    let mut memory = create_test_memory();
+    let url = create_causal_rule_fragment("404 error", "check url", 0.9);
+    let auth = create_causal_rule_fragment("404 error", "check auth", 0.8);
+    memory.insert_fragment(url.clone(), Vec::new());
+    memory.insert_fragment(auth.clone(), Vec::new());
+
+    let context = create_test_context("url", "web", 0.0);
+    assert!(!memory.activate_fragments(&context).contains(&auth.id));
+
+    let eeg = compile_thought(&context, &mut memory);
+    let (_, branches) = &decisions(&eeg)[0];
+    assert!(branches.iter().any(|b| b.target_node == auth.id));
+    assert!(eeg.nodes.contains_key(&auth.id));
+}
+
+#[test]
+fn test_execution_takes_one_branch_and_rejoins() {
+    let mut memory = create_test_memory();
+    let url = create_causal_rule_fragment("404 error", "check url", 0.9);
+    let auth = create_causal_rule_fragment("404 error", "check auth", 0.7);
+    let mut report = create_causal_rule_fragment("404 error resolved", "close ticket", 0.75);
+    report.reinforcement_count = 1;
+    memory.insert_fragment(url.clone(), Vec::new());
+    memory.insert_fragment(auth.clone(), Vec::new());
+    memory.insert_fragment(report.clone(), Vec::new());
+
+    let context = create_test_context("404 error", "web", 0.0);
+    let eeg = compile_thought(&context, &mut memory);
+    let result = execute_eeg(&eeg, &mut memory);
+
+    assert!(result.execution_trace.contains(&url.id));
+    assert!(!result.execution_trace.contains(&auth.id));
+    assert!(result.execution_trace.contains(&report.id));
+    assert!(eeg
+        .edges
+        .iter()
+        .any(|e| e.from_node == auth.id && e.to_node == report.id));
+}
+
+#[test]
+fn test_excluded_or_pruned_alternatives_drop_the_decision() {
+    let mut memory = create_test_memory();
+    let url = create_causal_rule_fragment("404 error", "check url", 0.9);
+    let weak = create_causal_rule_fragment("404 error", "reboot router", 0.2);
+    let secret = create_causal_rule_fragment("404 error", "check vault", 0.9);
+    memory.insert_fragment(url.clone(), Vec::new());
+    memory.insert_fragment(weak.clone(), Vec::new());
+    memory.insert_fragment(secret.clone(), Vec::new());
+
+    let mut context = create_test_context("404 error", "web", 0.0);
+    context
+        .environmental_constraints
+        .must_exclude
+        .insert("vault".to_string());
+    let eeg = compile_thought(&context, &mut memory);
+
+    assert!(decisions(&eeg).is_empty());
+    assert!(eeg.nodes.contains_key(&url.id));
+    assert!(!eeg.nodes.contains_key(&secret.id));
+    assert!(!eeg.nodes.contains_key(&weak.id));
+}
+
+#[test]
+fn test_sub_threshold_alternatives_are_not_branches() {
+    let mut memory = create_test_memory();
+    let url = create_causal_rule_fragment("404 error", "check url", 0.9);
+    let auth = create_causal_rule_fragment("404 error", "check auth", 0.7);
+    let stale = create_causal_rule_fragment("404 error", "restart server", 0.3);
+    for fragment in [&url, &auth, &stale] {
+        memory.insert_fragment(fragment.clone(), Vec::new());
+    }
+
+    let mut passes = PassManager::default();
+    passes.disable("prune_resources");
+    let eeg = passes.compile(&create_test_context("404 error", "web", 0.0), &mut memory);
+
+    let found = decisions(&eeg);
+    assert_eq!(found.len(), 1);
+    let targets: Vec<_> = found[0].1.iter().map(|b| b.target_node).collect();
+    assert_eq!(targets, vec![url.id, auth.id]);
+    assert!(!eeg.nodes.contains_key(&stale.id));
+}
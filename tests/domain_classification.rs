@@ -0,0 +1,133 @@
+use c_mer::*;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+#[test]
+fn test_content_terms_assign_domains() {
+    let mut memory = create_test_memory();
+    let fragment = create_causal_rule_fragment("http request returns 404", "endpoint missing", 0.9);
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    let domains = memory.fragment_domains(fragment.id);
+    assert!(domains.contains(&"api_integration".to_string()));
+    assert!(memory.activation_index.by_domain["api_integration"].contains(&fragment.id));
+}
+
+#[test]
+fn test_unmatched_fragment_falls_back_to_general() {
+    let mut memory = create_test_memory();
+    let fragment = create_entity_relation_fragment("garden", "contains", "tulips");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    assert_eq!(memory.fragment_domains(fragment.id), vec!["general"]);
+}
+
+#[test]
+fn test_explicit_and_source_domains_are_recorded() {
+    let mut memory = create_test_memory();
+    let explicit = create_entity_relation_fragment("garden", "contains", "tulips");
+    memory.insert_fragment_from(
+        explicit.clone(),
+        Vec::new(),
+        &IngestSource::in_domain("Botany"),
+    );
+    assert_eq!(memory.fragment_domains(explicit.id), vec!["botany"]);
+
+    let mut source = IngestSource::default();
+    source
+        .context
+        .insert("project".to_string(), "greenhouse".to_string());
+    let from_context = create_entity_relation_fragment("soil", "needs", "water");
+    memory.insert_fragment_from(from_context.clone(), Vec::new(), &source);
+    assert!(memory
+        .fragment_domains(from_context.id)
+        .contains(&"greenhouse".to_string()));
+
+    let event = ingest_conversation_in_domain("My favorite color is green", "onboarding");
+    let ids = memory.ingest_event(&event);
+    assert!(!ids.is_empty());
+    for id in ids {
+        assert!(memory
+            .fragment_domains(id)
+            .contains(&"onboarding".to_string()));
+    }
+}
+
+#[test]
+fn test_goal_index_covers_all_fragment_types() {
+    let mut memory = create_test_memory();
+    let rule = create_causal_rule_fragment("disk full", "writes fail", 0.8);
+    let relation = create_entity_relation_fragment("rust", "is_a", "language");
+    memory.insert_fragment(rule.clone(), Vec::new());
+    memory.insert_fragment(relation.clone(), Vec::new());
+
+    assert!(memory.activation_index.by_goal["debug"].contains(&rule.id));
+    assert!(memory.activation_index.by_goal[&normalize_keyword("learn")].contains(&relation.id));
+}
+
+#[test]
+fn test_domain_match_wins_activation() {
+    let mut memory = create_test_memory();
+    let in_domain = create_causal_rule_fragment("timeout on http request", "retry", 0.8);
+    let out_of_domain = create_causal_rule_fragment("timeout on sql query", "retry", 0.8);
+    memory.insert_fragment(in_domain.clone(), Vec::new());
+    memory.insert_fragment(out_of_domain.clone(), Vec::new());
+
+    let mut context = create_test_context("timeout retry", "api_integration", 0.5);
+    context.max_fragments = 1;
+    let activated = memory.activate_fragments(&context);
+    assert!(activated.contains(&in_domain.id));
+
+    let mut context = create_test_context("timeout retry", "database", 0.5);
+    context.max_fragments = 1;
+    let activated = memory.activate_fragments(&context);
+    assert!(activated.contains(&out_of_domain.id));
+}
+
+#[test]
+fn test_domains_survive_removal_and_reload() {
+    let mut memory = create_test_memory();
+    let kept = create_causal_rule_fragment("http request returns 500", "server error", 0.9);
+    let removed = create_causal_rule_fragment("http header missing", "request rejected", 0.9);
+    memory.insert_fragment(kept.clone(), Vec::new());
+    memory.insert_fragment(removed.clone(), Vec::new());
+    memory.remove_fragment(removed.id);
+    assert!(memory.fragment_domains(removed.id).is_empty());
+
+    memory.activation_index.by_domain.clear();
+    let path = std::env::temp_dir().join(format!("domains_{}.cmc", kept.id));
+    memory.save(&path).unwrap();
+    let loaded = MemoryGraph::load(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert!(loaded
+        .fragment_domains(kept.id)
+        .contains(&"api_integration".to_string()));
+}
+
+#[test]
+fn test_configured_domain_terms_are_normalized_once() {
+    let mut memory = create_test_memory();
+    let mut config = DomainClassifierConfig::default();
+    config.domain_terms.insert(
+        "gardening".to_string(),
+        vec!["Tulips".to_string(), "Gardens".to_string()],
+    );
+    let revision = memory.revision();
+    memory.set_domain_classifier(config.clone());
+    assert_ne!(memory.revision(), revision);
+
+    let fragment = create_entity_relation_fragment("garden", "contains", "tulips");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+    assert!(memory
+        .fragment_domains(fragment.id)
+        .contains(&"gardening".to_string()));
+
+    let normalized = normalize_domain_terms(&config);
+    assert_eq!(
+        classify_domains(&fragment, &IngestSource::default(), &config, &normalized)[0].0,
+        "gardening"
+    );
+}
@@ -0,0 +1,378 @@
+use c_mer::*;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+fn fragment_node(id: Uuid, fragment: &MFragment) -> EEGNode {
+    EEGNode {
+        id,
+        node_type: NodeType::FragmentNode,
+        content: NodeContent::Fragment {
+            fragment_id: fragment.id,
+            interpretation: format!("{:?}", fragment.fragment_type),
+        },
+        confidence: fragment.confidence,
+        source_fragments: vec![fragment.id],
+        execution_cost: 1.0,
+    }
+}
+
+fn link(from: Uuid, to: Uuid) -> EEGEdge {
+    EEGEdge {
+        from_node: from,
+        to_node: to,
+        edge_type: EdgeType::Contextual,
+        condition: None,
+        weight: 1.0,
+    }
+}
+
+fn graph(entry_point: Uuid, nodes: Vec<EEGNode>, edges: Vec<EEGEdge>) -> EEG {
+    EEG {
+        metadata: EEGMetadata {
+            compilation_timestamp: 0.0,
+            fragment_count: nodes.len(),
+            estimated_execution_time: nodes.len() as f64,
+            confidence_score: 0.8,
+        },
+        nodes: nodes
+            .into_iter()
+            .map(|node| (node.id, node))
+            .collect::<HashMap<_, _>>(),
+        edges,
+        entry_point,
+        exit_points: Vec::new(),
+    }
+}
+
+fn observe(eeg: &EEG, memory: &mut MemoryGraph) -> (String, Vec<Uuid>, Vec<Uuid>) {
+    let result = execute_eeg(eeg, memory);
+    let signals = result
+        .reinforcement_signals
+        .iter()
+        .map(|signal| signal.fragment_id)
+        .collect();
+    (result.outcome.result, result.execution_trace, signals)
+}
+
+fn step(memory: &mut MemoryGraph, target: &str) -> MFragment {
+    let fragment = create_entity_relation_fragment("deploy", "runs", target);
+    memory.insert_fragment(fragment.clone(), Vec::new());
+    fragment
+}
+
+#[test]
+fn test_linear_chain_fuses_into_one_node() {
+    let mut memory = create_test_memory();
+    let release = step(&mut memory, "release");
+    let test = create_entity_relation_fragment("deploy", "runs", "tests");
+    memory.insert_fragment(
+        test.clone(),
+        vec![create_test_edges(test.id, release.id, 0.9)],
+    );
+    let build = create_entity_relation_fragment("deploy", "runs", "build");
+    memory.insert_fragment(
+        build.clone(),
+        vec![create_test_edges(build.id, test.id, 0.9)],
+    );
+
+    let mut eeg = compile_thought(&create_test_context("deploy", "general", 0.0), &mut memory);
+    let before = observe(&eeg, &mut memory);
+    let metadata = eeg.metadata.clone();
+    let report = optimize_eeg(&mut eeg, &memory);
+
+    assert_eq!(report.nodes_fused, 2);
+    assert_eq!((report.nodes_before, report.nodes_after), (3, 1));
+    assert_eq!(eeg.exit_points, vec![eeg.entry_point]);
+    assert_eq!(eeg.metadata.fragment_count, metadata.fragment_count);
+    assert_eq!(
+        eeg.metadata.estimated_execution_time,
+        metadata.estimated_execution_time
+    );
+    assert!((eeg.metadata.confidence_score - metadata.confidence_score).abs() < 1e-9);
+    assert_eq!(observe(&eeg, &mut memory), before);
+}
+
+#[test]
+fn test_unreachable_nodes_are_removed() {
+    let mut memory = create_test_memory();
+    let a = step(&mut memory, "alpha");
+    let b = step(&mut memory, "beta");
+    let orphan = step(&mut memory, "gamma");
+    let mut eeg = graph(
+        a.id,
+        vec![
+            fragment_node(a.id, &a),
+            fragment_node(b.id, &b),
+            fragment_node(orphan.id, &orphan),
+        ],
+        vec![link(a.id, b.id), link(orphan.id, b.id)],
+    );
+    let before = observe(&eeg, &mut memory);
+
+    let report = optimize_eeg_with_config(
+        &mut eeg,
+        &memory,
+        &OptimizerConfig {
+            fuse_chains: false,
+            ..OptimizerConfig::default()
+        },
+    );
+
+    assert_eq!(report.unreachable_removed, 1);
+    assert!(!eeg.nodes.contains_key(&orphan.id));
+    assert!(eeg.edges.iter().all(|e| e.from_node != orphan.id));
+    assert_eq!(eeg.metadata.fragment_count, 2);
+    assert_eq!(eeg.exit_points, vec![b.id]);
+    assert_eq!(observe(&eeg, &mut memory), before);
+}
+
+#[test]
+fn test_dead_branches_and_their_targets_are_removed() {
+    let mut memory = create_test_memory();
+    let taken = step(&mut memory, "url");
+    let never = step(&mut memory, "auth");
+    let decision = Uuid::new_v4();
+    let branch = |target: Uuid, weight: f64| Branch {
+        condition: String::new(),
+        target_node: target,
+        weight,
+    };
+    let mut eeg = graph(
+        decision,
+        vec![
+            EEGNode {
+                id: decision,
+                node_type: NodeType::DecisionNode,
+                content: NodeContent::Decision {
+                    condition: "404".to_string(),
+                    branches: vec![
+                        branch(taken.id, 1.0),
+                        branch(never.id, 0.0),
+                        branch(Uuid::new_v4(), 0.5),
+                    ],
+                },
+                confidence: 0.9,
+                source_fragments: Vec::new(),
+                execution_cost: 0.5,
+            },
+            fragment_node(taken.id, &taken),
+            fragment_node(never.id, &never),
+        ],
+        vec![link(decision, taken.id), link(decision, never.id)],
+    );
+    let before = observe(&eeg, &mut memory);
+
+    let report = optimize_eeg(&mut eeg, &memory);
+
+    assert_eq!(report.dead_branches_removed, 2);
+    assert_eq!(report.unreachable_removed, 1);
+    assert!(!eeg.nodes.contains_key(&never.id));
+    match &eeg.nodes[&decision].content {
+        NodeContent::Decision { branches, .. } => {
+            assert_eq!(branches.len(), 1);
+            assert_eq!(branches[0].target_node, taken.id);
+        }
+        _ => panic!("decision node was rewritten"),
+    }
+    assert_eq!(observe(&eeg, &mut memory), before);
+}
+
+#[test]
+fn test_equivalent_subgraphs_are_merged() {
+    let mut memory = create_test_memory();
+    let start = step(&mut memory, "start");
+    let shared = step(&mut memory, "shared");
+    let join = step(&mut memory, "join");
+    let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+    let mut eeg = graph(
+        start.id,
+        vec![
+            fragment_node(start.id, &start),
+            fragment_node(first, &shared),
+            fragment_node(second, &shared),
+            fragment_node(join.id, &join),
+        ],
+        vec![
+            link(start.id, first),
+            link(start.id, second),
+            link(first, join.id),
+            link(second, join.id),
+        ],
+    );
+    let before = observe(&eeg, &mut memory);
+
+    let report = optimize_eeg_with_config(
+        &mut eeg,
+        &memory,
+        &OptimizerConfig {
+            fuse_chains: false,
+            ..OptimizerConfig::default()
+        },
+    );
+
+    assert_eq!(report.nodes_merged, 1);
+    assert_eq!(eeg.nodes.len(), 3);
+    assert_eq!(eeg.edges.len(), 2);
+    let (result, trace, signals) = observe(&eeg, &mut memory);
+    assert_eq!((result, trace.len(), signals), (before.0, 3, before.2));
+}
+
+#[test]
+fn test_duplicates_on_one_path_are_not_merged() {
+    let mut memory = create_test_memory();
+    let repeated = step(&mut memory, "retry");
+    let middle = step(&mut memory, "wait");
+    let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+    let mut eeg = graph(
+        first,
+        vec![
+            fragment_node(first, &repeated),
+            fragment_node(middle.id, &middle),
+            fragment_node(second, &repeated),
+        ],
+        vec![
+            link(first, middle.id),
+            link(middle.id, second),
+            link(second, middle.id),
+        ],
+    );
+    let before = observe(&eeg, &mut memory);
+
+    let report = optimize_eeg(&mut eeg, &memory);
+
+    assert_eq!(report.nodes_merged, 0);
+    assert_eq!(observe(&eeg, &mut memory), before);
+}
+
+#[test]
+fn test_decision_branches_survive_optimization() {
+    let mut memory = create_test_memory();
+    let url = create_causal_rule_fragment("404 error", "check url", 0.9);
+    let auth = create_causal_rule_fragment("404 error", "check auth", 0.7);
+    let report_rule = create_causal_rule_fragment("404 error resolved", "close ticket", 0.75);
+    for fragment in [&url, &auth, &report_rule] {
+        memory.insert_fragment(fragment.clone(), Vec::new());
+    }
+
+    let mut eeg = compile_thought(&create_test_context("404 error", "web", 0.0), &mut memory);
+    let before = observe(&eeg, &mut memory);
+    let report = optimize_eeg(&mut eeg, &memory);
+
+    assert_eq!(report.nodes_after, report.nodes_before);
+    assert!(eeg.nodes.contains_key(&url.id) && eeg.nodes.contains_key(&auth.id));
+    assert_eq!(observe(&eeg, &mut memory), before);
+}
+
+#[test]
+fn test_duplicate_fragments_with_equal_content_are_merged() {
+    let mut memory = create_test_memory();
+    let start = step(&mut memory, "start");
+    let shared = step(&mut memory, "shared");
+    let copy = step(&mut memory, "shared");
+    let join = step(&mut memory, "join");
+    let mut eeg = graph(
+        start.id,
+        vec![
+            fragment_node(start.id, &start),
+            fragment_node(shared.id, &shared),
+            fragment_node(copy.id, &copy),
+            fragment_node(join.id, &join),
+        ],
+        vec![
+            link(start.id, shared.id),
+            link(start.id, copy.id),
+            link(shared.id, join.id),
+            link(copy.id, join.id),
+        ],
+    );
+
+    let report = optimize_eeg_with_config(
+        &mut eeg,
+        &memory,
+        &OptimizerConfig {
+            fuse_chains: false,
+            ..OptimizerConfig::default()
+        },
+    );
+
+    assert_eq!(report.nodes_merged, 1);
+    assert_eq!(eeg.nodes.len(), 3);
+    assert!(eeg.nodes.contains_key(&shared.id) != eeg.nodes.contains_key(&copy.id));
+}
+
+#[test]
+fn test_first_branch_with_missing_target_is_removed() {
+    let mut memory = create_test_memory();
+    let taken = step(&mut memory, "url");
+    let decision = Uuid::new_v4();
+    let mut eeg = graph(
+        decision,
+        vec![
+            EEGNode {
+                id: decision,
+                node_type: NodeType::DecisionNode,
+                content: NodeContent::Decision {
+                    condition: "404".to_string(),
+                    branches: vec![
+                        Branch {
+                            condition: String::new(),
+                            target_node: Uuid::new_v4(),
+                            weight: 0.6,
+                        },
+                        Branch {
+                            condition: String::new(),
+                            target_node: taken.id,
+                            weight: 0.4,
+                        },
+                    ],
+                },
+                confidence: 0.9,
+                source_fragments: Vec::new(),
+                execution_cost: 0.5,
+            },
+            fragment_node(taken.id, &taken),
+        ],
+        vec![link(decision, taken.id)],
+    );
+
+    let report = optimize_eeg(&mut eeg, &memory);
+
+    assert_eq!(report.dead_branches_removed, 1);
+    match &eeg.nodes[&decision].content {
+        NodeContent::Decision { branches, .. } => {
+            assert_eq!(branches.len(), 1);
+            assert_eq!(branches[0].target_node, taken.id);
+            assert_eq!(branches[0].weight, 1.0);
+        }
+        _ => panic!("decision node was rewritten"),
+    }
+    let (_, trace, _) = observe(&eeg, &mut memory);
+    assert_eq!(trace, vec![decision, taken.id]);
+}
+
+#[test]
+fn test_pass_manager_optimizes_compiled_eeg() {
+    let mut memory = create_test_memory();
+    let release = step(&mut memory, "release");
+    let build = create_entity_relation_fragment("deploy", "runs", "build");
+    memory.insert_fragment(
+        build.clone(),
+        vec![create_test_edges(build.id, release.id, 0.9)],
+    );
+    let context = create_test_context("deploy", "general", 0.0);
+    let mut passes = PassManager::default();
+    assert!(passes.optimizer().is_none());
+
+    passes.set_optimizer(Some(OptimizerConfig::default()));
+    let eeg = passes.compile(&context, &mut memory);
+
+    assert_eq!(eeg.nodes.len(), 1);
+    assert_eq!(eeg.metadata.fragment_count, 2);
+    let last = passes.stats().last().unwrap();
+    assert_eq!(last.name, "optimize_eeg");
+    assert_eq!((last.nodes_before, last.nodes_after), (2, 1));
+}
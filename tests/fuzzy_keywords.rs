@@ -0,0 +1,121 @@
+use c_mer::query_expansion::QueryExpander;
+use c_mer::*;
+use std::collections::HashMap;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+#[test]
+fn test_edit_distance_and_trigrams() {
+    assert_eq!(edit_distance("alice", "alcie"), 2);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("", "abc"), 3);
+    assert_eq!(compact_keyword("Dark Mode"), "darkmode");
+    assert_eq!(compact_keyword("dark_mode"), compact_keyword("dark-mode"));
+    assert!(trigrams("abc").contains(" ab"));
+}
+
+#[test]
+fn test_misspelled_entity_activates_with_discount() {
+    let mut memory = create_test_memory();
+    memory.vector_index.config.enabled = false;
+    let fragment = create_entity_relation_fragment("Katherine", "likes", "chess");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    let matches = memory.keyword_matches("katerine");
+    assert!(!matches.is_empty());
+    assert!(matches.iter().all(|m| !m.exact && m.similarity < 1.0));
+
+    let weights = memory.lookup_keyword_fuzzy("katerine");
+    let weight = weights[&fragment.id];
+    assert!(weight > 0.0 && weight < memory.keyword_index.config.fuzzy_weight);
+
+    let exact = memory.lookup_keyword_fuzzy("Katherine");
+    assert_eq!(exact[&fragment.id], 1.0);
+
+    for typo in ["katherin", "katerine"] {
+        assert!(memory.lookup_keyword_fuzzy(typo)[&fragment.id] > 0.0);
+        let context = create_test_context(&format!("what does {} like", typo), "personal", 0.5);
+        assert!(memory.activate_fragments(&context).contains(&fragment.id));
+    }
+}
+
+#[test]
+fn test_separator_variants_match_exactly() {
+    let mut memory = create_test_memory();
+    let fragment = create_entity_relation_fragment("dark_mode", "enabled_in", "editor");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    for variant in ["dark mode", "Dark-Mode", "darkmode"] {
+        let weights = memory.lookup_keyword_fuzzy(variant);
+        assert_eq!(weights.get(&fragment.id), Some(&1.0), "{}", variant);
+    }
+}
+
+#[test]
+fn test_similarity_cutoff_is_configurable() {
+    let mut memory = create_test_memory();
+    let fragment = create_entity_relation_fragment("photosynthesis", "requires", "sunlight");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    assert!(memory
+        .lookup_keyword_fuzzy("photosinthesis")
+        .contains_key(&fragment.id));
+    assert!(memory.lookup_keyword_fuzzy("photography").is_empty());
+
+    memory.keyword_index.config.min_similarity = 1.0;
+    memory.keyword_index.config.max_edit_distance = 0;
+    assert!(memory.lookup_keyword_fuzzy("photosinthesis").is_empty());
+
+    memory.keyword_index.config.enabled = false;
+    assert!(memory
+        .lookup_keyword_fuzzy("photosynthesis")
+        .contains_key(&fragment.id));
+}
+
+#[test]
+fn test_short_terms_are_not_fuzzed() {
+    let mut memory = create_test_memory();
+    let fragment = create_entity_relation_fragment("cat", "chases", "mouse");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    assert!(memory.lookup_keyword_fuzzy("car").is_empty());
+    assert!(memory
+        .lookup_keyword_fuzzy("cat")
+        .contains_key(&fragment.id));
+}
+
+#[test]
+fn test_removed_keywords_leave_the_index() {
+    let mut memory = create_test_memory();
+    let fragment = create_entity_relation_fragment("glacier", "carves", "valley");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+    assert!(memory.keyword_index.keys.contains_key("glacier"));
+
+    memory.remove_fragment(fragment.id);
+
+    assert!(memory.keyword_index.keys.is_empty());
+    assert!(memory.keyword_index.trigrams.is_empty());
+    assert!(memory.lookup_keyword_fuzzy("glaciers").is_empty());
+}
+
+#[test]
+fn test_query_expansion_uses_fuzzy_index() {
+    let mut memory = create_test_memory();
+    let mut content = HashMap::new();
+    content.insert("key".to_string(), "katherine".to_string());
+    content.insert("favorite_game".to_string(), "chess".to_string());
+    let mut fragment = create_entity_relation_fragment("unused", "unused", "unused");
+    fragment.content = FragmentContent::SemanticAtom {
+        atom_type: AtomType::Person,
+        content,
+        atom_id: None,
+    };
+    memory.insert_fragment(fragment, Vec::new());
+
+    for typo in ["katherin", "katerine"] {
+        let expanded = QueryExpander::expand_query(&format!("tell me about {}", typo), &memory, 2);
+        assert!(expanded.contains("favorite_game"), "{:?}", expanded);
+    }
+}
@@ -0,0 +1,203 @@
+use c_mer::*;
+use std::collections::HashSet;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+fn gap_descriptions(eeg: &EEG) -> Vec<String> {
+    eeg.nodes
+        .values()
+        .filter_map(|node| match &node.content {
+            NodeContent::GapFill {
+                gap_description, ..
+            } => Some(gap_description.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn focused_context(goal: &str, relations: &[&str], entities: &[&str]) -> ContextVector {
+    let mut context = create_test_context(goal, "general", 0.3);
+    let window = &mut context.attention_window;
+    window.focus_relations = relations.iter().map(|r| r.to_string()).collect();
+    window.focus_entities = entities.iter().map(|e| e.to_string()).collect();
+    window.focus_domains.clear();
+    context
+}
+
+#[test]
+fn test_satisfied_requirements_emit_no_gaps() {
+    let mut memory = create_test_memory();
+    let cause = create_entity_relation_fragment("crash", "causes", "outage");
+    memory.insert_fragment(cause.clone(), Vec::new());
+
+    let context = focused_context("crash", &["causes"], &["outage"]);
+    let eeg = compile_thought(&context, &mut memory);
+
+    assert!(gap_descriptions(&eeg).is_empty());
+    assert!(eeg.nodes.contains_key(&cause.id));
+}
+
+#[test]
+fn test_missing_relation_produces_specific_gap() {
+    let mut memory = create_test_memory();
+    let crash = create_entity_relation_fragment("crash", "happens_on", "startup");
+    memory.insert_fragment(crash.clone(), Vec::new());
+
+    let context = focused_context("crash", &["Causes"], &[]);
+    let eeg = compile_thought(&context, &mut memory);
+
+    assert_eq!(
+        gap_descriptions(&eeg),
+        vec!["No memory provides relation 'causes'".to_string()]
+    );
+    assert!(eeg.nodes.contains_key(&crash.id));
+}
+
+#[test]
+fn test_unactivated_related_fragment_bridges_gap() {
+    let mut memory = create_test_memory();
+    let crash = create_entity_relation_fragment("crash", "happens_on", "startup");
+    let leak = create_entity_relation_fragment("leak", "causes", "outage");
+    memory.insert_fragment(crash.clone(), Vec::new());
+    memory.insert_fragment(leak.clone(), Vec::new());
+
+    let context = focused_context("crash", &["causes"], &[]);
+    assert!(!memory.activate_fragments(&context).contains(&leak.id));
+
+    let eeg = compile_thought(&context, &mut memory);
+    assert!(gap_descriptions(&eeg).is_empty());
+    assert!(eeg.nodes.contains_key(&leak.id));
+}
+
+#[test]
+fn test_bridge_follows_transitive_edges() {
+    let mut memory = create_test_memory();
+    let crash = create_entity_relation_fragment("crash", "triggers", "restart");
+    let restart = create_entity_relation_fragment("restart", "flushes", "buffer");
+    let cache = create_entity_relation_fragment("cache", "stores", "sessions");
+    let far_cache = create_entity_relation_fragment("cache", "holds", "tokens");
+    memory.insert_fragment(far_cache.clone(), Vec::new());
+    memory.insert_fragment(cache.clone(), Vec::new());
+    memory.insert_fragment(
+        restart.clone(),
+        vec![create_test_edges(restart.id, cache.id, 0.6)],
+    );
+    memory.insert_fragment(
+        crash.clone(),
+        vec![create_test_edges(crash.id, restart.id, 0.9)],
+    );
+
+    let context = focused_context("crash", &[], &[]);
+    let requirement = GoalRequirement::Entity("cache".to_string());
+    let skip: HashSet<_> = [crash.id].into_iter().collect();
+    assert_eq!(
+        memory.bridge_gap(&requirement, &[crash.id], &skip, &context),
+        Some(vec![restart.id, cache.id])
+    );
+
+    let missing = GoalRequirement::Entity("database".to_string());
+    assert_eq!(
+        memory.bridge_gap(&missing, &[crash.id], &skip, &context),
+        None
+    );
+}
+
+#[test]
+fn test_excluded_fragments_do_not_bridge_gaps() {
+    let mut memory = create_test_memory();
+    let crash = create_entity_relation_fragment("crash", "happens_on", "startup");
+    let secret = create_entity_relation_fragment("vault", "causes", "crash");
+    memory.insert_fragment(crash.clone(), Vec::new());
+    memory.insert_fragment_from(
+        secret.clone(),
+        Vec::new(),
+        &IngestSource::in_domain("confidential"),
+    );
+
+    let mut context = focused_context("startup", &["causes"], &[]);
+    context
+        .attention_window
+        .exclusion_patterns
+        .insert("confidential".to_string());
+    let eeg = compile_thought(&context, &mut memory);
+
+    assert!(!eeg.nodes.contains_key(&secret.id));
+    assert_eq!(
+        gap_descriptions(&eeg),
+        vec!["No memory provides relation 'causes'".to_string()]
+    );
+}
+
+#[test]
+fn test_requirements_come_from_focus_sets() {
+    let context = focused_context("x", &["Causes", "causes", "fixes"], &["Server"]);
+    let requirements = GoalRequirement::from_context(&context);
+
+    assert_eq!(
+        requirements,
+        vec![
+            GoalRequirement::Relation("causes".to_string()),
+            GoalRequirement::Relation("fixes".to_string()),
+            GoalRequirement::Entity("server".to_string()),
+        ]
+    );
+    assert_eq!(requirements[2].describe(), "entity 'server'");
+
+    let mut memory = create_test_memory();
+    let fragment = create_entity_relation_fragment("causes", "relates", "server");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+    assert!(!memory.satisfies(fragment.id, &requirements[0]));
+    assert!(memory.satisfies(fragment.id, &requirements[2]));
+}
+
+#[test]
+fn test_relation_requirements_match_fragment_types() {
+    let mut memory = create_test_memory();
+    let rule = create_causal_rule_fragment("404 error", "missing route", 0.9);
+    memory.insert_fragment(rule.clone(), Vec::new());
+
+    for relation in ["causes", "leads_to", "Caused"] {
+        let context = focused_context("404 error", &[relation], &[]);
+        let eeg = compile_thought(&context, &mut memory);
+
+        assert!(gap_descriptions(&eeg).is_empty(), "{}", relation);
+        assert!(eeg.nodes.contains_key(&rule.id));
+    }
+
+    let context = focused_context("404 error", &["owns"], &[]);
+    let eeg = compile_thought(&context, &mut memory);
+    assert_eq!(
+        gap_descriptions(&eeg),
+        vec!["No memory provides relation 'owns'".to_string()]
+    );
+}
+
+#[test]
+fn test_low_confidence_fragments_do_not_bridge_gaps() {
+    let mut memory = create_test_memory();
+    let crash = create_entity_relation_fragment("crash", "happens_on", "startup");
+    let mut leak = create_entity_relation_fragment("leak", "causes", "outage");
+    leak.confidence = 0.4;
+    memory.insert_fragment(crash.clone(), Vec::new());
+    memory.insert_fragment(
+        leak.clone(),
+        vec![create_test_edges(leak.id, crash.id, 0.9)],
+    );
+
+    let context = focused_context("crash", &["causes"], &[]);
+    let eeg = compile_thought(&context, &mut memory);
+    assert!(!eeg.nodes.contains_key(&leak.id));
+    assert_eq!(
+        gap_descriptions(&eeg),
+        vec!["No memory provides relation 'causes'".to_string()]
+    );
+
+    let requirement = GoalRequirement::Entity("leak".to_string());
+    let skip: HashSet<_> = [crash.id].into_iter().collect();
+    assert_eq!(
+        memory.bridge_gap(&requirement, &[crash.id], &skip, &context),
+        None
+    );
+}
@@ -0,0 +1,136 @@
+use c_mer::*;
+use uuid::Uuid;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+fn star_memory() -> (MemoryGraph, Uuid, Vec<Uuid>, Uuid) {
+    let mut memory = create_test_memory();
+    let hub = create_entity_relation_fragment("hub", "connects", "spokes");
+    let spokes: Vec<MFragment> = (0..4)
+        .map(|i| create_entity_relation_fragment(&format!("spoke_{}", i), "links", "hub"))
+        .collect();
+    let island = create_entity_relation_fragment("island", "alone", "nothing");
+
+    for spoke in &spokes {
+        memory.insert_fragment(
+            spoke.clone(),
+            vec![create_test_edges(spoke.id, hub.id, 0.8)],
+        );
+    }
+    memory.insert_fragment(hub.clone(), Vec::new());
+    memory.insert_fragment(island.clone(), Vec::new());
+
+    let spoke_ids = spokes.iter().map(|s| s.id).collect();
+    (memory, hub.id, spoke_ids, island.id)
+}
+
+#[test]
+fn test_centrality_identifies_hub() {
+    let (memory, hub, spokes, _) = star_memory();
+    let analytics = analyze_graph(&memory);
+
+    assert_eq!(analytics.degree_centrality[&hub], 4.0 / 5.0);
+    assert_eq!(analytics.degree_centrality[&spokes[0]], 1.0 / 5.0);
+
+    let total: f64 = analytics.pagerank.values().sum();
+    assert!((total - 1.0).abs() < 1e-6);
+    assert_eq!(analytics.top_hubs(1)[0].0, hub);
+}
+
+#[test]
+fn test_components_and_islands() {
+    let (memory, hub, _, island) = star_memory();
+    let analytics = analyze_graph(&memory);
+
+    assert_eq!(analytics.components.len(), 2);
+    assert_eq!(analytics.components[0].len(), 5);
+    assert!(analytics.components[0].contains(&hub));
+    assert_eq!(analytics.isolated, vec![island]);
+}
+
+#[test]
+fn test_label_propagation_separates_clusters() {
+    let mut memory = create_test_memory();
+    let cluster_a: Vec<MFragment> = (0..3)
+        .map(|i| create_entity_relation_fragment(&format!("a{}", i), "r", "t"))
+        .collect();
+    let cluster_b: Vec<MFragment> = (0..3)
+        .map(|i| create_entity_relation_fragment(&format!("b{}", i), "r", "t"))
+        .collect();
+
+    for cluster in [&cluster_a, &cluster_b] {
+        for i in 0..cluster.len() {
+            let next = &cluster[(i + 1) % cluster.len()];
+            memory.insert_fragment(
+                cluster[i].clone(),
+                vec![create_test_edges(cluster[i].id, next.id, 0.9)],
+            );
+        }
+    }
+    memory.insert_fragment(
+        create_entity_relation_fragment("bridge", "r", "t"),
+        vec![create_test_edges(cluster_a[0].id, cluster_b[0].id, 0.1)],
+    );
+
+    let analytics = analyze_graph(&memory);
+    let community_a = analytics.community_of(cluster_a[0].id).unwrap();
+    let community_b = analytics.community_of(cluster_b[0].id).unwrap();
+
+    assert_ne!(community_a, community_b);
+    for fragment in &cluster_a {
+        assert_eq!(analytics.community_of(fragment.id), Some(community_a));
+    }
+    for fragment in &cluster_b {
+        assert_eq!(analytics.community_of(fragment.id), Some(community_b));
+    }
+}
+
+#[test]
+fn test_hub_prior_boosts_activation_ranking() {
+    let (mut memory, hub, spokes, _) = star_memory();
+    let analytics = analyze_graph(&memory);
+    let prior = analytics.hub_prior(0.5);
+
+    assert_eq!(prior[&hub], 0.5);
+    assert!(prior[&spokes[0]] < prior[&hub]);
+
+    memory.apply_hub_prior(&analytics, 0.5);
+    assert_eq!(memory.activation_priors.len(), memory.fragments.len());
+
+    memory.remove_fragment(hub);
+    assert!(!memory.activation_priors.contains_key(&hub));
+
+    memory.clear_activation_priors();
+    assert!(memory.activation_priors.is_empty());
+}
+
+#[test]
+fn test_empty_graph_analytics() {
+    let memory = create_test_memory();
+    let analytics = analyze_graph(&memory);
+
+    assert!(analytics.pagerank.is_empty());
+    assert!(analytics.components.is_empty());
+    assert!(analytics.top_hubs(3).is_empty());
+    assert!(analytics.hub_prior(1.0).is_empty());
+}
+
+#[test]
+fn test_activation_prior_changes_selection() {
+    let mut memory = create_test_memory();
+    let plain = create_entity_relation_fragment("shared", "r", "plain");
+    let boosted = create_entity_relation_fragment("shared", "r", "boosted");
+    memory.insert_fragment(plain.clone(), Vec::new());
+    memory.insert_fragment(boosted.clone(), Vec::new());
+    memory.activation_priors.insert(boosted.id, 1.0);
+
+    let mut context = generate_context("lookup", "general", 0.0);
+    context.domain_hint.tags.insert("shared".to_string());
+    context.max_fragments = 1;
+
+    let activated = memory.activate_fragments(&context);
+    assert!(activated.contains(&boosted.id));
+    assert!(!activated.contains(&plain.id));
+}
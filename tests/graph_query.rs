@@ -0,0 +1,167 @@
+use c_mer::*;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+#[test]
+fn test_parse_full_query() {
+    let query = parse_query(
+        "FIND PersonalFact ABOUT \"alice\" WHERE confidence > 0.8 AND fact_type = likes \
+         LINKED causally TO ANY ABOUT \"insomnia\" LIMIT 5",
+    )
+    .unwrap();
+
+    assert_eq!(query.root.fragment_type, Some(FragmentType::PersonalFact));
+    assert_eq!(query.root.about, vec!["alice".to_string()]);
+    assert_eq!(query.root.conditions.len(), 2);
+    assert_eq!(query.root.conditions[0].op, CompareOp::Gt);
+    assert_eq!(query.root.conditions[0].value, QueryValue::Number(0.8));
+    assert_eq!(query.links.len(), 1);
+    assert_eq!(query.links[0].edge_type, Some(EdgeType::Causal));
+    assert_eq!(query.links[0].direction, LinkDirection::Outgoing);
+    assert_eq!(query.links[0].target.fragment_type, None);
+    assert_eq!(query.limit, Some(5));
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(matches!(
+        parse_query("FIND Unicorn"),
+        Err(GraphQueryError::UnknownFragmentType(_))
+    ));
+    assert!(matches!(
+        parse_query("FIND * ABOUT \"open"),
+        Err(GraphQueryError::UnterminatedString { .. })
+    ));
+    assert!(matches!(
+        parse_query("FIND * WHERE confidence"),
+        Err(GraphQueryError::UnexpectedEnd { .. })
+    ));
+    assert!(matches!(
+        parse_query("FIND * LINKED sideways TO *"),
+        Err(GraphQueryError::UnknownEdgeType(_))
+    ));
+}
+
+#[test]
+fn test_query_filters_by_type_and_confidence() {
+    let mut memory = create_test_memory();
+    let strong = create_personal_fact_fragment("alice", "likes", "coffee", 0.9);
+    let weak = create_personal_fact_fragment("alice", "likes", "tea", 0.5);
+    let other = create_entity_relation_fragment("alice", "works_at", "acme");
+
+    memory.insert_fragment(strong.clone(), Vec::new());
+    memory.insert_fragment(weak.clone(), Vec::new());
+    memory.insert_fragment(other.clone(), Vec::new());
+
+    let matches = memory
+        .query("FIND PersonalFact ABOUT \"Alice\" WHERE confidence > 0.8")
+        .unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].fragment_id, strong.id);
+
+    let matches = memory.query("FIND * ABOUT alice").unwrap();
+    assert_eq!(matches.len(), 3);
+
+    let matches = memory
+        .query("FIND PersonalFact WHERE value ~ \"te\" AND age < 1h")
+        .unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].fragment_id, weak.id);
+}
+
+#[test]
+fn test_query_follows_typed_edges() {
+    let mut memory = create_test_memory();
+    let fact = create_personal_fact_fragment("bob", "drinks", "espresso", 0.9);
+    let effect = create_causal_rule_fragment("espresso", "insomnia", 0.8);
+    let unrelated = create_personal_fact_fragment("bob", "drinks", "water", 0.9);
+
+    let mut causal = create_test_edges(fact.id, effect.id, 0.7);
+    causal.edge_type = EdgeType::Causal;
+    let mut semantic = create_test_edges(unrelated.id, effect.id, 0.7);
+    semantic.edge_type = EdgeType::Semantic;
+
+    memory.insert_fragment(fact.clone(), vec![causal]);
+    memory.insert_fragment(unrelated.clone(), vec![semantic]);
+    memory.insert_fragment(effect.clone(), Vec::new());
+
+    let matches = memory
+        .query("FIND PersonalFact ABOUT bob LINKED causal TO CausalRule WHERE outcome = insomnia")
+        .unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].fragment_id, fact.id);
+    assert_eq!(matches[0].linked, vec![vec![effect.id]]);
+
+    let matches = memory
+        .query("FIND CausalRule LINKED FROM PersonalFact ABOUT bob")
+        .unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].linked[0].len(), 2);
+
+    let matches = memory
+        .query("FIND PersonalFact LINKED temporal WITH *")
+        .unwrap();
+    assert!(matches.is_empty());
+}
+
+#[test]
+fn test_planner_uses_activation_index() {
+    let mut memory = create_test_memory();
+    for fragment in create_test_fragments(10, "test") {
+        memory.insert_fragment(fragment, Vec::new());
+    }
+
+    let plan = memory
+        .explain_query("FIND EntityRelation ABOUT entity_3")
+        .unwrap();
+    assert_eq!(
+        plan.root.access_path,
+        AccessPath::KeywordLookup(vec!["entity_3".to_string()])
+    );
+    assert_eq!(plan.root.estimated_rows, 1);
+
+    let plan = memory.explain_query("FIND EntityRelation LIMIT 2").unwrap();
+    assert_eq!(
+        plan.root.access_path,
+        AccessPath::TypeScan(FragmentType::EntityRelation)
+    );
+    assert!(plan.to_string().contains("type scan"));
+    assert_eq!(execute_plan(&plan, &memory).len(), 2);
+}
+
+#[test]
+fn test_type_scan_reads_only_indexed_fragments_of_that_type() {
+    let mut memory = create_test_memory();
+    let relation = create_entity_relation_fragment("alice", "knows", "bob");
+    let rule = create_causal_rule_fragment("disk full", "write fails", 0.9);
+    let removed = create_causal_rule_fragment("cache cold", "slow start", 0.9);
+    memory.insert_fragment(relation.clone(), Vec::new());
+    memory.insert_fragment(rule.clone(), Vec::new());
+    memory.insert_fragment(removed.clone(), Vec::new());
+    memory.remove_fragment(removed.id);
+
+    assert_eq!(
+        memory.lookup_type(&FragmentType::CausalRule),
+        [rule.id].into_iter().collect()
+    );
+    let plan = memory.explain_query("FIND CausalRule").unwrap();
+    assert_eq!(plan.root.estimated_rows, 1);
+
+    memory.activation_index.by_type.clear();
+    let path = std::env::temp_dir().join(format!("type_scan_{}.cmc", rule.id));
+    memory.save(&path).unwrap();
+    let loaded = MemoryGraph::load(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    let plan = loaded.explain_query("FIND CausalRule").unwrap();
+    assert_eq!(
+        plan.root.access_path,
+        AccessPath::TypeScan(FragmentType::CausalRule)
+    );
+    assert_eq!(plan.root.estimated_rows, 1);
+    let matches = loaded.query("FIND EntityRelation").unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].fragment_id, relation.id);
+}
@@ -0,0 +1,243 @@
+use c_mer::*;
+use std::collections::HashMap;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+fn fragment_with_salience(entity: &str, salience: f64) -> MFragment {
+    let mut fragment = create_entity_relation_fragment(entity, "relates_to", "target");
+    fragment.salience = salience;
+    fragment
+}
+
+#[test]
+fn test_max_fragments_evicts_lowest_salience() {
+    let mut memory = create_test_memory();
+    let weak = fragment_with_salience("weak", 0.1);
+    let medium = fragment_with_salience("medium", 0.5);
+    let strong = fragment_with_salience("strong", 0.9);
+
+    memory.insert_fragment(weak.clone(), Vec::new());
+    memory.insert_fragment(medium.clone(), Vec::new());
+    memory.insert_fragment(strong.clone(), Vec::new());
+
+    let report = memory.enforce_budget_with(&MemoryBudget {
+        max_fragments: Some(2),
+        ..MemoryBudget::default()
+    });
+
+    assert_eq!(memory.fragments.len(), 2);
+    assert!(!memory.fragments.contains_key(&weak.id));
+    assert_eq!(report.evicted_fragments.len(), 1);
+    assert_eq!(report.evicted_fragments[0].id, weak.id);
+    assert_eq!(
+        report.evicted_fragments[0].reason,
+        EvictionReason::MaxFragments
+    );
+    assert!(report.budget_satisfied);
+    assert!(!memory.activation_index.by_keyword.contains_key("weak"));
+}
+
+#[test]
+fn test_reinforced_fragments_are_protected() {
+    let mut memory = create_test_memory();
+    let mut reinforced = fragment_with_salience("reinforced", 0.01);
+    reinforced.reinforcement_count = 3;
+    let plain = fragment_with_salience("plain", 0.9);
+
+    memory.insert_fragment(reinforced.clone(), Vec::new());
+    memory.insert_fragment(plain.clone(), Vec::new());
+
+    let report = memory.enforce_budget_with(&MemoryBudget {
+        max_fragments: Some(0),
+        ..MemoryBudget::default()
+    });
+
+    assert!(memory.fragments.contains_key(&reinforced.id));
+    assert!(!memory.fragments.contains_key(&plain.id));
+    assert!(!report.budget_satisfied);
+}
+
+#[test]
+fn test_lru_policy_evicts_least_recently_activated() {
+    let mut memory = create_test_memory();
+    let mut old = fragment_with_salience("old", 0.9);
+    old.created_at = 1000.0;
+    old.last_activated = 2000.0;
+    let mut recent = fragment_with_salience("recent", 0.1);
+    recent.created_at = 1000.0;
+    recent.last_activated = 5000.0;
+
+    memory.insert_fragment(old.clone(), Vec::new());
+    memory.insert_fragment(recent.clone(), Vec::new());
+
+    memory.enforce_budget_with(&MemoryBudget {
+        max_fragments: Some(1),
+        eviction_policy: EvictionPolicy::LeastRecentlyUsed,
+        ..MemoryBudget::default()
+    });
+
+    assert!(memory.fragments.contains_key(&recent.id));
+    assert!(!memory.fragments.contains_key(&old.id));
+}
+
+#[test]
+fn test_base_level_activation_prefers_frequently_used() {
+    let now = current_timestamp();
+    let mut rarely = fragment_with_salience("rarely", 0.5);
+    rarely.activation_history = vec![now - 86400.0];
+    let mut often = fragment_with_salience("often", 0.5);
+    often.activation_history = vec![now - 60.0, now - 30.0, now - 10.0];
+
+    assert!(base_level_activation(&often, now) > base_level_activation(&rarely, now));
+
+    let mut memory = create_test_memory();
+    memory.insert_fragment(rarely.clone(), Vec::new());
+    memory.insert_fragment(often.clone(), Vec::new());
+    memory.enforce_budget_with(&MemoryBudget {
+        max_fragments: Some(1),
+        eviction_policy: EvictionPolicy::LowestBaseLevelActivation,
+        ..MemoryBudget::default()
+    });
+
+    assert!(memory.fragments.contains_key(&often.id));
+}
+
+#[test]
+fn test_per_type_budget_and_orphaned_edges() {
+    let mut memory = create_test_memory();
+    let relation = fragment_with_salience("relation", 0.2);
+    let rule = create_causal_rule_fragment("timeout", "retry", 0.8);
+    let edge = create_test_edges(relation.id, rule.id, 0.7);
+
+    memory.insert_fragment(relation.clone(), vec![edge]);
+    memory.insert_fragment(rule.clone(), Vec::new());
+
+    let mut max_fragments_per_type = HashMap::new();
+    max_fragments_per_type.insert("EntityRelation".to_string(), 0);
+    let report = memory.enforce_budget_with(&MemoryBudget {
+        max_fragments_per_type,
+        ..MemoryBudget::default()
+    });
+
+    assert!(memory.fragments.contains_key(&rule.id));
+    assert!(memory.edges.is_empty());
+    assert_eq!(
+        report.evicted_fragments[0].fragment_type,
+        FragmentType::EntityRelation
+    );
+    assert_eq!(report.evicted_edges.len(), 1);
+    assert_eq!(report.evicted_edges[0].reason, EvictionReason::OrphanedEdge);
+}
+
+#[test]
+fn test_history_and_co_activation_are_bounded() {
+    let mut memory = create_test_memory();
+    let mut fragment = fragment_with_salience("busy", 0.5);
+    fragment.activation_history = (0..50).map(|i| i as f64).collect();
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    for _ in 0..5 {
+        let a = fragment_with_salience("a", 0.5);
+        let b = fragment_with_salience("b", 0.5);
+        memory.record_co_activation(&[a.id, b.id]);
+    }
+
+    let report = memory.enforce_budget_with(&MemoryBudget {
+        max_activation_history: 10,
+        max_co_activation_patterns: 2,
+        ..MemoryBudget::default()
+    });
+
+    let stored = memory.fragments.get(&fragment.id).unwrap();
+    assert_eq!(stored.activation_history.len(), 10);
+    assert_eq!(stored.activation_history[0], 40.0);
+    assert_eq!(report.trimmed_history_entries, 40);
+    assert_eq!(memory.co_activation_patterns.len(), 2);
+    assert_eq!(report.evicted_co_activation_patterns, 3);
+}
+
+#[test]
+fn test_budget_enforced_on_insert_and_byte_limit() {
+    let mut memory = create_test_memory();
+    memory.set_budget(Some(MemoryBudget {
+        max_fragments: Some(5),
+        ..MemoryBudget::default()
+    }));
+
+    for fragment in create_test_fragments(20, "test") {
+        memory.insert_fragment(fragment, Vec::new());
+    }
+    assert_eq!(memory.fragments.len(), 5);
+
+    let max_bytes = memory.estimated_bytes() / 2;
+    let report = memory.enforce_budget_with(&MemoryBudget {
+        max_bytes: Some(max_bytes),
+        ..MemoryBudget::default()
+    });
+
+    assert!(report.bytes_after <= max_bytes);
+    assert!(report.bytes_after < report.bytes_before);
+    assert!(report
+        .evicted_fragments
+        .iter()
+        .all(|f| f.reason == EvictionReason::MaxBytes));
+}
+
+#[test]
+fn test_estimated_bytes_tracks_indexes_incrementally() {
+    let mut memory = create_test_memory();
+    let empty = memory.estimated_bytes();
+    let fragments = create_test_fragments(30, "budget");
+    let edges: Vec<Edge> = fragments
+        .windows(2)
+        .map(|pair| create_test_edges(pair[0].id, pair[1].id, 0.5))
+        .collect();
+    populate_memory_with_fragments(&mut memory, fragments);
+    for edge in edges {
+        memory.add_edge(edge);
+    }
+
+    let path = std::env::temp_dir().join(format!("budget_{}.cmc", uuid::Uuid::new_v4()));
+    memory.save(&path).unwrap();
+    let saved = std::fs::metadata(&path).unwrap().len() as usize;
+    let loaded = MemoryGraph::load(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert!(memory.estimated_bytes() * 2 >= saved);
+    assert!(memory.estimated_bytes() <= saved * 2);
+    assert_eq!(loaded.estimated_bytes(), memory.estimated_bytes());
+
+    let ids: Vec<uuid::Uuid> = memory.fragments.keys().copied().collect();
+    for id in ids {
+        memory.remove_fragment(id);
+    }
+    assert_eq!(memory.estimated_bytes(), empty);
+}
+
+#[test]
+fn test_evictions_on_insert_are_reported() {
+    let mut memory = create_test_memory();
+    let reports = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = reports.clone();
+    memory.subscribe(move |event| {
+        if let MemoryEvent::BudgetEnforced { report } = event {
+            sink.lock().unwrap().push(report.clone());
+        }
+    });
+    memory.set_budget(Some(MemoryBudget {
+        max_fragments: Some(1),
+        ..MemoryBudget::default()
+    }));
+
+    let first = fragment_with_salience("first", 0.1);
+    memory.insert_fragment(first.clone(), Vec::new());
+    assert!(reports.lock().unwrap().is_empty());
+    memory.insert_fragment(fragment_with_salience("second", 0.9), Vec::new());
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].evicted_fragments[0].id, first.id);
+    assert!(reports[0].bytes_after < reports[0].bytes_before);
+}
@@ -0,0 +1,167 @@
+use c_mer::*;
+use std::sync::{Arc, Mutex};
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+fn record_events(memory: &mut MemoryGraph) -> (u64, Arc<Mutex<Vec<MemoryEvent>>>) {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let subscription = memory.subscribe(move |event| sink.lock().unwrap().push(event.clone()));
+    (subscription, events)
+}
+
+#[test]
+fn test_insert_emits_fragment_and_edge_events() {
+    let mut memory = create_test_memory();
+    let (_, events) = record_events(&mut memory);
+    let target = create_entity_relation_fragment("rust", "is_a", "language");
+    let source = create_causal_rule_fragment("borrow error", "fix lifetimes", 0.8);
+    memory.insert_fragment(target.clone(), Vec::new());
+    memory.insert_fragment(
+        source.clone(),
+        vec![create_test_edges(source.id, target.id, 0.7)],
+    );
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 3);
+    assert!(
+        matches!(&events[0], MemoryEvent::FragmentInserted { fragment } if fragment.id == target.id)
+    );
+    assert!(
+        matches!(&events[1], MemoryEvent::FragmentInserted { fragment } if fragment.id == source.id)
+    );
+    assert!(matches!(
+        &events[2],
+        MemoryEvent::EdgeAdded { edge } if edge.from_fragment == source.id && edge.to_fragment == target.id
+    ));
+}
+
+#[test]
+fn test_reinserting_emits_update() {
+    let mut memory = create_test_memory();
+    let mut fragment = create_entity_relation_fragment("rust", "is_a", "language");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+    let (_, events) = record_events(&mut memory);
+
+    fragment.confidence = 0.95;
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    let events = events.lock().unwrap();
+    assert!(matches!(
+        &events[..],
+        [MemoryEvent::FragmentUpdated { fragment: updated }] if updated.confidence == 0.95
+    ));
+}
+
+#[test]
+fn test_reinforcement_reports_new_confidence() {
+    let mut memory = create_test_memory();
+    let fragment = create_causal_rule_fragment("cache miss", "slow response", 0.5);
+    memory.insert_fragment(fragment.clone(), Vec::new());
+    let (_, events) = record_events(&mut memory);
+
+    memory.reinforce_fragment(
+        fragment.id,
+        &Outcome {
+            outcome_type: OutcomeType::Success,
+            result: "ok".to_string(),
+            explanation: None,
+            confidence: 1.0,
+        },
+    );
+
+    let events = events.lock().unwrap();
+    assert!(matches!(
+        &events[..],
+        [MemoryEvent::FragmentReinforced { id, outcome_type: OutcomeType::Success, reinforcement_count: 1, confidence }]
+            if *id == fragment.id && (*confidence - 0.6).abs() < 1e-9
+    ));
+}
+
+#[test]
+fn test_decay_emits_decayed_and_removed_events() {
+    let mut memory = create_test_memory();
+    let source = create_entity_relation_fragment("old", "links", "thing");
+    let target = create_entity_relation_fragment("other", "links", "thing");
+    memory.insert_fragment(target.clone(), Vec::new());
+    memory.insert_fragment(
+        source.clone(),
+        vec![create_test_edges(source.id, target.id, 0.5)],
+    );
+    let (_, events) = record_events(&mut memory);
+
+    memory.decay_memory(1_000_000.0);
+
+    let events = events.lock().unwrap();
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, MemoryEvent::FragmentDecayed { id, .. } if *id == source.id)));
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, MemoryEvent::FragmentRemoved { id } if *id == source.id)));
+    assert!(events.iter().any(|e| matches!(
+        e,
+        MemoryEvent::EdgeRemoved { from_fragment, .. } if *from_fragment == source.id
+    )));
+    assert!(memory.lookup_keyword("old").is_empty());
+}
+
+#[test]
+fn test_module_added_and_unsubscribe() {
+    let mut memory = create_test_memory();
+    let (subscription, events) = record_events(&mut memory);
+    let module = CompiledModule {
+        id: uuid::Uuid::new_v4(),
+        module_type: ModuleType::DecisionTable,
+        code: Vec::new(),
+        input_signature: InputSignature {
+            parameters: Vec::new(),
+            context_requirements: Vec::new(),
+        },
+        output_signature: OutputSignature {
+            return_type: "Outcome".to_string(),
+            side_effects: Vec::new(),
+        },
+        activation_condition: ContextPattern {
+            goal_patterns: Vec::new(),
+            domain_hints: Vec::new(),
+            confidence_threshold: 0.5,
+        },
+        confidence: 0.9,
+        usage_count: 0,
+        success_count: 0,
+        failure_count: 0,
+        last_used: 0.0,
+        created_at: current_timestamp(),
+        source_pattern: uuid::Uuid::new_v4(),
+        version: 1,
+    };
+    memory.add_compiled_module(module.clone());
+
+    assert!(memory.unsubscribe(subscription));
+    assert!(!memory.unsubscribe(subscription));
+    memory.insert_fragment(create_entity_relation_fragment("a", "b", "c"), Vec::new());
+
+    let events = events.lock().unwrap();
+    assert!(matches!(
+        &events[..],
+        [MemoryEvent::ModuleAdded { module_id, .. }] if *module_id == module.id
+    ));
+}
+
+#[test]
+fn test_events_round_trip_through_json() {
+    let mut memory = create_test_memory();
+    let (_, events) = record_events(&mut memory);
+    let fragment = create_entity_relation_fragment("rust", "is_a", "language");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+    memory.remove_fragment(fragment.id);
+
+    let events = events.lock().unwrap();
+    let json = serde_json::to_string(&*events).unwrap();
+    let decoded: Vec<MemoryEvent> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.len(), 2);
+    assert!(matches!(&decoded[1], MemoryEvent::FragmentRemoved { id } if *id == fragment.id));
+}
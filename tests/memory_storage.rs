@@ -26,13 +26,14 @@ fn test_activation_index_updates() {
 
     memory.insert_fragment(fragment.clone(), Vec::new());
 
-    assert!(memory.activation_index.by_keyword.contains_key("HTTP"));
+    assert!(memory.activation_index.by_keyword.contains_key("http"));
     assert!(memory
         .activation_index
         .by_keyword
-        .get("HTTP")
+        .get("http")
         .unwrap()
         .contains(&fragment.id));
+    assert!(memory.lookup_keyword("HTTP").contains(&fragment.id));
 }
 
 #[test]
@@ -42,8 +43,9 @@ fn test_multi_domain_indexing() {
 
     memory.insert_fragment(fragment.clone(), Vec::new());
 
-    assert!(memory.activation_index.by_keyword.contains_key("HTTP_call"));
+    assert!(memory.activation_index.by_keyword.contains_key("http_call"));
     assert!(memory.activation_index.by_keyword.contains_key("404_error"));
+    assert!(memory.lookup_keyword("HTTP_call").contains(&fragment.id));
 }
 
 #[test]
@@ -0,0 +1,123 @@
+use c_mer::*;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+#[test]
+fn test_tokenizer_drops_stop_words_and_keeps_positions() {
+    let tokenizer = Tokenizer::default();
+    let tokens = tokenizer.tokens("The capital of New York");
+
+    let texts: Vec<&str> = tokens.iter().map(|t| t.text.as_str()).collect();
+    assert_eq!(texts, vec!["capital", "new", "york"]);
+    let positions: Vec<usize> = tokens.iter().map(|t| t.position).collect();
+    assert_eq!(positions, vec![1, 3, 4]);
+
+    let phrases: Vec<String> = tokenizer
+        .phrases("The capital of New York")
+        .into_iter()
+        .map(|p| p.text)
+        .collect();
+    assert_eq!(phrases, vec!["new york"]);
+}
+
+#[test]
+fn test_multi_word_values_index_tokens_and_phrases() {
+    let mut memory = create_test_memory();
+    let fragment = create_entity_relation_fragment("new york city", "located_in", "usa");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    assert!(memory
+        .lookup_keyword("new york city")
+        .contains(&fragment.id));
+    assert!(memory.lookup_keyword("york").contains(&fragment.id));
+    assert!(memory.lookup_keyword("new_york").contains(&fragment.id));
+    assert!(memory.lookup_keyword("york_city").contains(&fragment.id));
+}
+
+#[test]
+fn test_stop_words_are_not_indexed() {
+    let mut memory = create_test_memory();
+    let fragment = create_entity_relation_fragment("the lord of the rings", "is_a", "novel");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    assert!(memory.lookup_keyword("the").is_empty());
+    assert!(memory.lookup_keyword("of").is_empty());
+    assert!(memory.lookup_keyword("ring").contains(&fragment.id));
+}
+
+#[test]
+fn test_common_words_do_not_activate_unrelated_fragments() {
+    let mut memory = create_test_memory();
+    let fragment = create_entity_relation_fragment("the garden", "contains", "tulips");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    let mut context = create_test_context("what is my", "general", 0.5);
+    context.domain_hint.tags.insert("the".to_string());
+    context.domain_hint.tags.insert("my".to_string());
+
+    assert!(!memory.activate_fragments(&context).contains(&fragment.id));
+}
+
+#[test]
+fn test_phrase_query_requires_adjacent_terms() {
+    let mut memory = create_test_memory();
+    let adjacent = create_entity_relation_fragment("new york", "has", "subway");
+    let scattered = create_entity_relation_fragment("new library", "opened_in", "york");
+    memory.insert_fragment(adjacent.clone(), Vec::new());
+    memory.insert_fragment(scattered.clone(), Vec::new());
+
+    let hits = memory.phrase_search("New York");
+    assert!(hits.contains(&adjacent.id));
+    assert!(!hits.contains(&scattered.id));
+
+    let context = create_test_context("tell me about \"new york\"", "general", 0.5);
+    assert!(memory.phrase_matches(&context).contains(&adjacent.id));
+    assert!(memory.activate_fragments(&context).contains(&adjacent.id));
+}
+
+#[test]
+fn test_phrase_index_follows_removal_and_reload() {
+    let mut memory = create_test_memory();
+    let kept = create_entity_relation_fragment("san francisco", "has", "fog");
+    let removed = create_entity_relation_fragment("los angeles", "has", "traffic");
+    memory.insert_fragment(kept.clone(), Vec::new());
+    memory.insert_fragment(removed.clone(), Vec::new());
+    memory.remove_fragment(removed.id);
+    assert!(memory.phrase_search("los angeles").is_empty());
+
+    memory.phrase_index = PhraseIndex::default();
+    let path = std::env::temp_dir().join(format!("phrases_{}.cmc", kept.id));
+    memory.save(&path).unwrap();
+    let loaded = MemoryGraph::load(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert!(loaded.phrase_search("san francisco").contains(&kept.id));
+}
+
+#[test]
+fn test_phrases_with_inner_stop_words_match() {
+    let mut memory = create_test_memory();
+    let bank = create_entity_relation_fragment("bank of america", "headquartered_in", "charlotte");
+    let other = create_entity_relation_fragment("bank", "near", "america street");
+    memory.insert_fragment(bank.clone(), Vec::new());
+    memory.insert_fragment(other.clone(), Vec::new());
+
+    let hits = memory.phrase_search("bank of america");
+    assert!(hits.contains(&bank.id));
+    assert!(!hits.contains(&other.id));
+
+    let context = create_test_context("who is \"bank of america\"", "general", 0.5);
+    assert!(memory.phrase_matches(&context).contains(&bank.id));
+
+    memory.insert_fragment(bank.clone(), Vec::new());
+    assert_eq!(memory.phrase_index.len(), 2);
+    memory.remove_fragment(bank.id);
+    assert!(memory.phrase_search("bank of america").is_empty());
+    assert!(memory
+        .phrase_index
+        .positions
+        .values()
+        .all(|documents| !documents.contains_key(&bank.id)));
+}
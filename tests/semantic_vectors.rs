@@ -0,0 +1,166 @@
+use c_mer::*;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+#[test]
+fn test_embeddings_are_deterministic() {
+    let first = VectorIndex::default();
+    let second = VectorIndex::default();
+
+    let a = first.embed("photosynthesis requires sunlight");
+    let b = second.embed("photosynthesis requires sunlight");
+
+    assert_eq!(a, b);
+    assert_eq!(a.len(), VectorIndexConfig::default().dimensions);
+    assert!((cosine_similarity(&a, &b) - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_misspelled_query_finds_fragment() {
+    let mut memory = create_test_memory();
+    let fragment = create_entity_relation_fragment("photosynthesis", "requires", "sunlight");
+    let unrelated = create_entity_relation_fragment("database", "stores", "records");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+    memory.insert_fragment(unrelated.clone(), Vec::new());
+
+    for typo in ["photosynthesys", "fotosynthesis"] {
+        let hits = memory.semantic_search(typo);
+        assert_eq!(hits.first().map(|(id, _)| *id), Some(fragment.id));
+        assert!(hits.iter().all(|(id, _)| *id != unrelated.id));
+
+        let context = create_test_context(&format!("explain {}", typo), "biology", 0.5);
+        let activated = memory.activate_fragments(&context);
+        assert!(activated.contains(&fragment.id));
+        assert!(!activated.contains(&unrelated.id));
+    }
+    assert!(memory.lookup_keyword("fotosynthesis").is_empty());
+}
+
+#[test]
+fn test_semantic_hits_are_discounted() {
+    let mut memory = create_test_memory();
+    let exact = create_entity_relation_fragment("colour", "describes", "palette");
+    let fuzzy = create_entity_relation_fragment("colours", "describe", "palettes");
+    memory.insert_fragment(exact.clone(), Vec::new());
+    memory.insert_fragment(fuzzy.clone(), Vec::new());
+
+    let hits = memory.semantic_search("colour palette");
+    let exact_score = hits.iter().find(|(id, _)| *id == exact.id).map(|h| h.1);
+    let fuzzy_score = hits.iter().find(|(id, _)| *id == fuzzy.id).map(|h| h.1);
+
+    assert!(exact_score.is_some());
+    assert!(fuzzy_score.is_some());
+    assert!(exact_score.unwrap() > fuzzy_score.unwrap());
+    assert!(hits.iter().all(|(_, s)| *s <= 1.0 + 1e-6));
+}
+
+#[test]
+fn test_removed_fragments_leave_the_index() {
+    let mut memory = create_test_memory();
+    let fragment = create_entity_relation_fragment("glacier", "carves", "valley");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+    assert_eq!(memory.vector_index.len(), 1);
+
+    memory.remove_fragment(fragment.id);
+
+    assert!(memory.vector_index.is_empty());
+    assert!(memory.semantic_search("glaciers").is_empty());
+    assert!(memory.vector_index.buckets.iter().all(|t| t.is_empty()));
+    assert!(memory.vector_index.word_contexts.is_empty());
+    assert!(memory.vector_index.documents.is_empty());
+}
+
+#[test]
+fn test_word_contexts_follow_live_fragments() {
+    let mut memory = create_test_memory();
+    memory.vector_index.config.max_context_words = 4;
+    let kept = create_entity_relation_fragment("glacier", "carves", "valley");
+    let removed = create_entity_relation_fragment("glacier", "melts", "quickly");
+    memory.insert_fragment(kept.clone(), Vec::new());
+    let before = memory.vector_index.word_contexts.clone();
+    memory.insert_fragment(removed.clone(), Vec::new());
+
+    assert_eq!(memory.vector_index.word_contexts.len(), 4);
+    memory.remove_fragment(removed.id);
+
+    assert_eq!(memory.vector_index.word_contexts.len(), before.len());
+    for (word, context) in &before {
+        let current = &memory.vector_index.word_contexts[word];
+        assert!(context
+            .iter()
+            .zip(current)
+            .all(|(a, b)| (a - b).abs() < 1e-6));
+    }
+}
+
+#[test]
+fn test_synonym_groups_link_unrelated_spellings() {
+    let mut memory = create_test_memory();
+    let vehicle = create_entity_relation_fragment("vehicle", "needs", "fuel");
+    memory.insert_fragment(vehicle.clone(), Vec::new());
+    assert!(memory.semantic_search("car").is_empty());
+
+    memory.add_synonyms(&["vehicle", "car", "automobile"]);
+
+    assert_eq!(
+        memory.semantic_search("car").first().map(|(id, _)| *id),
+        Some(vehicle.id)
+    );
+    let context = create_test_context("car", "transport", 0.5);
+    assert!(memory.activate_fragments(&context).contains(&vehicle.id));
+}
+
+#[test]
+fn test_disabled_index_adds_no_candidates() {
+    let mut memory = create_test_memory();
+    memory.vector_index = VectorIndex::new(VectorIndexConfig {
+        enabled: false,
+        ..VectorIndexConfig::default()
+    });
+    memory.keyword_index.config.enabled = false;
+    let fragment = create_entity_relation_fragment("photosynthesis", "requires", "sunlight");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    assert!(memory.vector_index.is_empty());
+    let context = create_test_context("explain fotosynthesis", "biology", 0.5);
+    assert!(!memory.activate_fragments(&context).contains(&fragment.id));
+}
+
+#[test]
+fn test_index_survives_save_and_load() {
+    let mut memory = create_test_memory();
+    let fragment = create_entity_relation_fragment("volcano", "erupts", "lava");
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    let path = std::env::temp_dir().join(format!("semantic_{}.cmc", fragment.id));
+    memory.save(&path).unwrap();
+    let mut loaded = MemoryGraph::load(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(loaded.vector_index.len(), 1);
+    assert_eq!(loaded.semantic_search("volcanos")[0].0, fragment.id);
+
+    loaded.vector_index.vectors.clear();
+    loaded.rebuild_vector_index();
+    assert_eq!(loaded.semantic_search("volcanos")[0].0, fragment.id);
+}
+
+#[test]
+fn test_lsh_probe_finds_near_neighbours() {
+    let mut memory = create_test_memory();
+    memory.vector_index = VectorIndex::new(VectorIndexConfig {
+        exact_search_limit: 0,
+        ..VectorIndexConfig::default()
+    });
+
+    let target = create_entity_relation_fragment("thermodynamics", "governs", "entropy");
+    memory.insert_fragment(target.clone(), Vec::new());
+    for fragment in create_test_fragments(50, "noise") {
+        memory.insert_fragment(fragment, Vec::new());
+    }
+
+    let hits = memory.semantic_search("thermodynamic");
+    assert_eq!(hits.first().map(|(id, _)| *id), Some(target.id));
+}
@@ -0,0 +1,105 @@
+use c_mer::*;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+#[test]
+fn test_porter_stemmer_reference_forms() {
+    let cases = [
+        ("caresses", "caress"),
+        ("ponies", "poni"),
+        ("cats", "cat"),
+        ("feed", "feed"),
+        ("agreed", "agre"),
+        ("plastered", "plaster"),
+        ("motoring", "motor"),
+        ("sing", "sing"),
+        ("hopping", "hop"),
+        ("filing", "file"),
+        ("happy", "happi"),
+        ("relational", "relat"),
+        ("hopeful", "hope"),
+        ("goodness", "good"),
+        ("controlling", "control"),
+    ];
+    for (word, stem) in cases {
+        assert_eq!(porter_stem(word), stem, "{}", word);
+    }
+}
+
+#[test]
+fn test_inflections_hit_the_same_fragments() {
+    let mut memory = create_test_memory();
+    let fragment = create_personal_fact_fragment("alice", "likes", "jazz", 0.8);
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    for form in ["liked", "likes", "liking", "Like"] {
+        assert!(
+            memory.lookup_keyword(form).contains(&fragment.id),
+            "{}",
+            form
+        );
+    }
+}
+
+#[test]
+fn test_irregular_forms_and_case_folding() {
+    assert_eq!(normalize_term("went"), "go");
+    assert_eq!(normalize_term("Children"), "child");
+    assert_eq!(normalize_term("WAS"), "be");
+    assert_eq!(normalize_term("Straße"), normalize_term("STRASSE"));
+    assert_eq!(normalize_keyword("Dark_Modes"), "dark_mode");
+    assert_eq!(normalize_terms("People ran!"), vec!["person", "run"]);
+
+    let mut memory = create_test_memory();
+    let fragment = create_personal_fact_fragment("bob", "run", "marathons", 0.8);
+    memory.insert_fragment(fragment.clone(), Vec::new());
+    assert!(memory.lookup_keyword("ran").contains(&fragment.id));
+    assert!(memory.lookup_keyword("Marathon").contains(&fragment.id));
+}
+
+#[test]
+fn test_goal_patterns_are_normalized() {
+    let mut memory = create_test_memory();
+    let mut fragment = create_entity_relation_fragment("unused", "unused", "unused");
+    fragment.fragment_type = FragmentType::GoalStrategy;
+    fragment.content = FragmentContent::GoalStrategy {
+        goal: "debugging failed deployments".to_string(),
+        strategy: "check logs".to_string(),
+        success_rate: 0.9,
+    };
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    assert!(memory.activation_index.by_goal.contains_key("deploy"));
+
+    let context = create_test_context("deploy failing", "ops", 0.5);
+    assert!(memory.activate_fragments(&context).contains(&fragment.id));
+}
+
+#[test]
+fn test_index_keys_are_migrated_on_load() {
+    let mut memory = create_test_memory();
+    let fragment = create_personal_fact_fragment("carol", "likes", "tea", 0.8);
+    memory.insert_fragment(fragment.clone(), Vec::new());
+
+    memory.activation_index.by_keyword.clear();
+    memory
+        .activation_index
+        .by_keyword
+        .entry("Liking".to_string())
+        .or_default()
+        .insert(fragment.id);
+    memory.normalization_version = 0;
+
+    let path = std::env::temp_dir().join(format!("normalize_{}.cmc", fragment.id));
+    memory.save(&path).unwrap();
+    let loaded = MemoryGraph::load(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(loaded.normalization_version, NORMALIZATION_VERSION);
+    assert_eq!(loaded.version, 1);
+    assert!(!loaded.activation_index.by_keyword.contains_key("Liking"));
+    assert!(loaded.lookup_keyword("liked").contains(&fragment.id));
+    assert!(!loaded.keyword_matches("likes").is_empty());
+}
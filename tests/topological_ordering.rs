@@ -0,0 +1,177 @@
+use c_mer::*;
+use uuid::Uuid;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+fn edge(from: &MFragment, to: &MFragment, edge_type: EdgeType, strength: f64) -> Edge {
+    let mut edge = create_test_edges(from.id, to.id, strength);
+    edge.edge_type = edge_type;
+    edge
+}
+
+fn walk(eeg: &EEG) -> Vec<Uuid> {
+    let mut path = vec![eeg.entry_point];
+    while let Some(edge) = eeg
+        .edges
+        .iter()
+        .find(|e| e.from_node == *path.last().unwrap())
+    {
+        if path.contains(&edge.to_node) {
+            break;
+        }
+        path.push(edge.to_node);
+    }
+    path
+}
+
+fn find_edge(eeg: &EEG, from: Uuid, to: Uuid) -> Option<&EEGEdge> {
+    eeg.edges
+        .iter()
+        .find(|e| e.from_node == from && e.to_node == to)
+}
+
+fn compile(memory: &mut MemoryGraph) -> EEG {
+    let context = create_test_context("deploy", "general", 0.0);
+    compile_thought(&context, memory)
+}
+
+#[test]
+fn test_causal_chain_runs_in_dependency_order() {
+    let mut memory = create_test_memory();
+    let build = create_deploy_step_fragment("build", 0.7);
+    let test = create_deploy_step_fragment("tests", 0.8);
+    let release = create_deploy_step_fragment("release", 0.95);
+    memory.insert_fragment(release.clone(), Vec::new());
+    memory.insert_fragment(
+        test.clone(),
+        vec![edge(&test, &release, EdgeType::Causal, 0.9)],
+    );
+    memory.insert_fragment(
+        build.clone(),
+        vec![edge(&build, &test, EdgeType::Causal, 0.9)],
+    );
+
+    let eeg = compile(&mut memory);
+
+    assert_eq!(walk(&eeg), vec![build.id, test.id, release.id]);
+    assert_eq!(eeg.entry_point, build.id);
+    assert_eq!(eeg.exit_points, vec![release.id]);
+}
+
+#[test]
+fn test_edges_carry_dependency_type_and_strength() {
+    let mut memory = create_test_memory();
+    let first = create_deploy_step_fragment("migrate", 0.7);
+    let second = create_deploy_step_fragment("restart", 0.9);
+    memory.insert_fragment(second.clone(), Vec::new());
+    memory.insert_fragment(
+        first.clone(),
+        vec![edge(&first, &second, EdgeType::Temporal, 0.65)],
+    );
+
+    let eeg = compile(&mut memory);
+    let link = find_edge(&eeg, first.id, second.id).unwrap();
+
+    assert_eq!(link.edge_type, EdgeType::Temporal);
+    assert_eq!(link.weight, 0.65);
+}
+
+#[test]
+fn test_independent_fragments_fall_back_to_confidence() {
+    let mut memory = create_test_memory();
+    let low = create_deploy_step_fragment("lint", 0.7);
+    let high = create_deploy_step_fragment("audit", 0.9);
+    let related = create_deploy_step_fragment("notify", 0.8);
+    memory.insert_fragment(low.clone(), Vec::new());
+    memory.insert_fragment(high.clone(), Vec::new());
+    memory.insert_fragment(
+        related.clone(),
+        vec![edge(&related, &low, EdgeType::Semantic, 0.9)],
+    );
+
+    let eeg = compile(&mut memory);
+
+    assert_eq!(walk(&eeg), vec![high.id, related.id, low.id]);
+    assert_eq!(
+        find_edge(&eeg, high.id, related.id).unwrap().edge_type,
+        EdgeType::Contextual
+    );
+    assert_eq!(
+        find_edge(&eeg, related.id, low.id).unwrap().edge_type,
+        EdgeType::Contextual
+    );
+}
+
+#[test]
+fn test_cycles_break_at_weakest_dependency() {
+    let mut memory = create_test_memory();
+    let a = create_deploy_step_fragment("alpha", 0.7);
+    let b = create_deploy_step_fragment("beta", 0.8);
+    let c = create_deploy_step_fragment("gamma", 0.9);
+    memory.insert_fragment(a.clone(), Vec::new());
+    memory.insert_fragment(b.clone(), Vec::new());
+    memory.insert_fragment(c.clone(), Vec::new());
+    memory.add_edge(edge(&a, &b, EdgeType::Causal, 0.9));
+    memory.add_edge(edge(&b, &c, EdgeType::Causal, 0.8));
+    memory.add_edge(edge(&c, &a, EdgeType::Causal, 0.3));
+
+    let eeg = compile(&mut memory);
+
+    assert_eq!(walk(&eeg), vec![a.id, b.id, c.id]);
+    assert!(find_edge(&eeg, c.id, a.id).is_none());
+}
+
+#[test]
+fn test_non_adjacent_dependencies_are_kept_and_executed() {
+    let mut memory = create_test_memory();
+    let root = create_deploy_step_fragment("checkout", 0.7);
+    let left = create_deploy_step_fragment("compile", 0.9);
+    let right = create_deploy_step_fragment("package", 0.8);
+    memory.insert_fragment(left.clone(), Vec::new());
+    memory.insert_fragment(right.clone(), Vec::new());
+    memory.insert_fragment(
+        root.clone(),
+        vec![
+            edge(&root, &left, EdgeType::Causal, 0.9),
+            edge(&root, &right, EdgeType::Causal, 0.75),
+        ],
+    );
+
+    let eeg = compile(&mut memory);
+
+    assert_eq!(walk(&eeg), vec![root.id, left.id, right.id]);
+    assert_eq!(find_edge(&eeg, root.id, right.id).unwrap().weight, 0.75);
+    let result = execute_eeg(&eeg, &mut memory);
+    assert_eq!(result.execution_trace, vec![root.id, left.id, right.id]);
+}
+
+#[test]
+fn test_conflict_node_precedes_selected_fragment() {
+    let mut memory = create_test_memory();
+    let dependency = create_deploy_step_fragment("approve", 0.7);
+    let mut winner = create_entity_relation_fragment("deploy", "likes", "friday");
+    winner.confidence = 0.9;
+    let mut loser = create_entity_relation_fragment("deploy", "dislikes", "friday");
+    loser.confidence = 0.8;
+    memory.insert_fragment(winner.clone(), Vec::new());
+    memory.insert_fragment(loser.clone(), Vec::new());
+    memory.insert_fragment(
+        dependency.clone(),
+        vec![edge(&dependency, &winner, EdgeType::Causal, 0.9)],
+    );
+
+    let eeg = compile(&mut memory);
+    let path = walk(&eeg);
+    let conflict = eeg
+        .nodes
+        .values()
+        .find(|n| n.node_type == NodeType::ConflictNode)
+        .unwrap();
+
+    assert_eq!(path.first(), Some(&dependency.id));
+    let position = path.iter().position(|id| *id == conflict.id).unwrap();
+    assert_eq!(path[position + 1], winner.id);
+    assert!(!path.contains(&loser.id));
+}
@@ -0,0 +1,254 @@
+use c_mer::*;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+fn success() -> Outcome {
+    Outcome {
+        outcome_type: OutcomeType::Success,
+        result: "ok".to_string(),
+        explanation: None,
+        confidence: 1.0,
+    }
+}
+
+#[test]
+fn test_commit_applies_all_staged_mutations() {
+    let mut memory = create_test_memory();
+    let existing = create_causal_rule_fragment("disk full", "writes fail", 0.5);
+    memory.insert_fragment(existing.clone(), Vec::new());
+
+    let a = create_entity_relation_fragment("server", "hosts", "database");
+    let b = create_entity_relation_fragment("database", "stores", "orders");
+    let staged = memory
+        .transaction(|tx| {
+            tx.insert_fragment(a.clone(), vec![create_test_edges(a.id, b.id, 0.6)]);
+            tx.insert_fragment(b.clone(), Vec::new());
+            tx.add_edge(create_test_edges(b.id, existing.id, 0.4));
+            tx.reinforce_fragment(existing.id, &success());
+            Ok(tx.staged().len())
+        })
+        .unwrap();
+
+    assert_eq!(staged, 4);
+    assert!(memory.fragments.contains_key(&a.id));
+    assert!(memory.fragments.contains_key(&b.id));
+    assert!(memory.edges.contains_key(&(a.id, b.id)));
+    assert!(memory.edges.contains_key(&(b.id, existing.id)));
+    assert!((memory.fragments[&existing.id].confidence - 0.6).abs() < 1e-9);
+    assert!(memory.lookup_keyword("server").contains(&a.id));
+}
+
+#[test]
+fn test_dangling_edge_rolls_back_everything() {
+    let mut memory = create_test_memory();
+    let fragment = create_entity_relation_fragment("server", "hosts", "database");
+    let missing = uuid::Uuid::new_v4();
+
+    let result = memory.transaction(|tx| {
+        tx.insert_fragment(fragment.clone(), Vec::new());
+        tx.add_edge(create_test_edges(fragment.id, missing, 0.5));
+        Ok(())
+    });
+
+    assert_eq!(
+        result,
+        Err(TransactionError::DanglingEdge {
+            from_fragment: fragment.id,
+            to_fragment: missing,
+        })
+    );
+    assert!(memory.fragments.is_empty());
+    assert!(memory.edges.is_empty());
+    assert!(memory.lookup_keyword("server").is_empty());
+}
+
+#[test]
+fn test_edges_to_fragments_removed_in_same_transaction_are_rejected() {
+    let mut memory = create_test_memory();
+    let a = create_entity_relation_fragment("a_node", "links", "b_node");
+    let b = create_entity_relation_fragment("b_node", "links", "c_node");
+    memory.insert_fragment(a.clone(), Vec::new());
+    memory.insert_fragment(b.clone(), Vec::new());
+
+    let result = memory.transaction(|tx| {
+        tx.add_edge(create_test_edges(a.id, b.id, 0.5));
+        tx.remove_fragment(b.id);
+        Ok(())
+    });
+
+    assert!(matches!(result, Err(TransactionError::DanglingEdge { .. })));
+    assert!(memory.fragments.contains_key(&b.id));
+
+    let result = memory.transaction(|tx| {
+        tx.remove_fragment(b.id);
+        tx.reinforce_fragment(b.id, &success());
+        Ok(())
+    });
+    assert_eq!(result, Err(TransactionError::MissingFragment(b.id)));
+}
+
+#[test]
+fn test_aborted_closure_stages_nothing() {
+    let mut memory = create_test_memory();
+    let fragment = create_entity_relation_fragment("server", "hosts", "database");
+
+    let result: std::result::Result<(), TransactionError> = memory.transaction(|tx| {
+        tx.insert_fragment(fragment.clone(), Vec::new());
+        Err(TransactionError::Aborted("extraction failed".to_string()))
+    });
+
+    assert!(result.is_err());
+    assert!(memory.fragments.is_empty());
+    assert!(memory.undo_log.entries.is_empty());
+}
+
+#[test]
+fn test_undo_restores_previous_state() {
+    let mut memory = create_test_memory();
+    let base = create_causal_rule_fragment("cache miss", "slow page", 0.5);
+    let neighbour = create_entity_relation_fragment("cache", "backs", "page");
+    memory
+        .transaction(|tx| {
+            tx.insert_fragment(
+                base.clone(),
+                vec![create_test_edges(base.id, neighbour.id, 0.5)],
+            );
+            tx.insert_fragment(neighbour.clone(), Vec::new());
+            Ok(())
+        })
+        .unwrap();
+
+    let added = create_entity_relation_fragment("cdn", "serves", "assets");
+    memory
+        .transaction(|tx| {
+            tx.insert_fragment(
+                added.clone(),
+                vec![create_test_edges(added.id, base.id, 0.5)],
+            );
+            tx.reinforce_fragment(base.id, &success());
+            tx.remove_fragment(neighbour.id);
+            Ok(())
+        })
+        .unwrap();
+    assert!(!memory.fragments.contains_key(&neighbour.id));
+
+    assert_eq!(memory.undo(1), 1);
+
+    assert!(!memory.fragments.contains_key(&added.id));
+    assert!(memory.lookup_keyword("cdn").is_empty());
+    assert!(memory.fragments.contains_key(&neighbour.id));
+    assert!(memory.edges.contains_key(&(base.id, neighbour.id)));
+    assert!(!memory.edges.contains_key(&(added.id, base.id)));
+    assert!((memory.fragments[&base.id].confidence - 0.5).abs() < 1e-9);
+    assert!((memory.edges[&(base.id, neighbour.id)].strength - 0.5).abs() < 1e-9);
+
+    assert_eq!(memory.undo(5), 1);
+    assert!(memory.fragments.is_empty());
+    assert!(memory.edges.is_empty());
+}
+
+#[test]
+fn test_undo_log_is_bounded() {
+    let mut memory = create_test_memory();
+    memory.undo_log.capacity = 2;
+    for i in 0..4 {
+        let fragment = create_entity_relation_fragment(&format!("turn_{}", i), "said", "hello");
+        memory
+            .transaction(|tx| {
+                tx.insert_fragment(fragment, Vec::new());
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    assert_eq!(memory.undo_log.entries.len(), 2);
+    assert_eq!(memory.undo(10), 2);
+    assert_eq!(memory.fragments.len(), 2);
+}
+
+#[test]
+fn test_undo_restores_sources_priors_patterns_and_evictions() {
+    let mut memory = create_test_memory();
+    let kept = create_entity_relation_fragment("invoice", "billed_to", "customer");
+    let mut faded = create_entity_relation_fragment("receipt", "sent_to", "customer");
+    faded.salience = 0.1;
+    let mut source = IngestSource::in_domain("payments");
+    source
+        .context
+        .insert("source".to_string(), "billing.md".to_string());
+    memory.insert_fragment_from(faded.clone(), Vec::new(), &source);
+    memory.insert_fragment(
+        kept.clone(),
+        vec![create_test_edges(kept.id, faded.id, 0.5)],
+    );
+    memory.activation_priors.insert(faded.id, 0.3);
+    memory.record_co_activation(&[kept.id, faded.id]);
+    memory.set_budget(Some(MemoryBudget {
+        max_fragments: Some(2),
+        ..MemoryBudget::default()
+    }));
+
+    let added = create_entity_relation_fragment("refund", "issued_to", "customer");
+    memory
+        .transaction(|tx| {
+            tx.insert_fragment(added.clone(), Vec::new());
+            tx.record_co_activation(&[kept.id, added.id]);
+            Ok(())
+        })
+        .unwrap();
+    assert!(!memory.fragments.contains_key(&faded.id));
+    assert!(memory
+        .co_activation_patterns
+        .iter()
+        .all(|pattern| !pattern.fragment_ids.contains(&faded.id)));
+
+    assert_eq!(memory.undo(1), 1);
+
+    assert!(!memory.fragments.contains_key(&added.id));
+    assert!(memory.fragments.contains_key(&faded.id));
+    assert_eq!(memory.fragment_domains(faded.id), vec!["payments"]);
+    assert_eq!(memory.fragment_sources[&faded.id], "billing.md");
+    assert_eq!(memory.activation_priors[&faded.id], 0.3);
+    assert!(memory.edges.contains_key(&(kept.id, faded.id)));
+    assert_eq!(memory.co_activation_patterns.len(), 1);
+    assert!(memory.co_activation_patterns[0]
+        .fragment_ids
+        .contains(&faded.id));
+}
+
+#[test]
+fn test_agent_undoes_whole_turns() {
+    let mut agent = CMCAgent::new(Box::new(OfflineLLM));
+    agent.process("My favorite color is blue.").unwrap();
+    let stored = agent.memory_stats().fragments;
+    let before = agent_state(&agent);
+
+    agent.process("What is my favorite color?").unwrap();
+    agent.process("What is my favorite color?").unwrap();
+    assert_ne!(agent_state(&agent), before);
+
+    assert_eq!(agent.undo_turns(2), 2);
+    assert_eq!(agent.conversation_history().len(), 1);
+    assert_eq!(agent.memory_stats().fragments, stored);
+    assert_eq!(agent_state(&agent), before);
+
+    assert_eq!(agent.undo_turns(5), 1);
+    assert!(agent.conversation_history().is_empty());
+    assert_eq!(agent.memory_stats().fragments, 0);
+    assert!(agent.working_memory().is_empty());
+}
+
+type AgentState = (Vec<(uuid::Uuid, f64, u32)>, usize);
+
+fn agent_state(agent: &CMCAgent) -> AgentState {
+    let memory = agent.memory();
+    let mut fragments: Vec<(uuid::Uuid, f64, u32)> = memory
+        .fragments
+        .values()
+        .map(|f| (f.id, f.confidence, f.reinforcement_count))
+        .collect();
+    fragments.sort_by_key(|entry| entry.0);
+    (fragments, memory.co_activation_patterns.len())
+}
@@ -0,0 +1,121 @@
+use c_mer::*;
+
+#[path = "common.rs"]
+mod common;
+use common::*;
+
+#[test]
+fn test_weights_decay_with_turn_distance() {
+    let mut working = WorkingMemory::default();
+    let older = uuid::Uuid::new_v4();
+    let newer = uuid::Uuid::new_v4();
+    working.record_turn([older]);
+    working.record_turn([newer]);
+
+    let weights = working.weights();
+    assert!((weights[&newer] - 0.5).abs() < 1e-9);
+    assert!((weights[&older] - 0.3).abs() < 1e-9);
+
+    working.record_turn([older]);
+    assert!((working.weights()[&older] - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_buffer_keeps_only_last_turns() {
+    let mut working = WorkingMemory {
+        capacity: 2,
+        ..WorkingMemory::default()
+    };
+    let first = uuid::Uuid::new_v4();
+    working.record_turn([first, first]);
+    assert_eq!(working.turns[0].len(), 1);
+
+    working.record_turn([uuid::Uuid::new_v4()]);
+    working.record_turn(Vec::new());
+
+    assert_eq!(working.len(), 2);
+    assert!(!working.weights().contains_key(&first));
+}
+
+#[test]
+fn test_prime_seeds_recent_activations() {
+    let mut working = WorkingMemory {
+        max_primed: 2,
+        ..WorkingMemory::default()
+    };
+    let ids: Vec<uuid::Uuid> = (0..3).map(|_| uuid::Uuid::new_v4()).collect();
+    working.record_turn([ids[0]]);
+    working.record_turn([ids[1], ids[2]]);
+
+    let mut context = create_test_context("what else", "general", 0.3);
+    working.prime(&mut context);
+
+    assert_eq!(context.recent_activations.len(), 2);
+    assert!(!context.recent_activations.contains(&ids[0]));
+    assert!((context.activation_primes[&ids[1]] - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_follow_up_recovers_previous_turn_fragments() {
+    let mut memory = create_test_memory();
+    let sibling = create_entity_relation_fragment("alice", "sibling_of", "bob");
+    memory.insert_fragment(sibling.clone(), Vec::new());
+    memory.insert_fragment(
+        create_causal_rule_fragment("rain", "wet roads", 0.8),
+        Vec::new(),
+    );
+
+    let mut working = WorkingMemory::default();
+    let first = create_test_context("tell me about alice", "general", 0.3);
+    let activated = memory.activate_fragments(&first);
+    assert!(activated.contains(&sibling.id));
+    working.record_turn(activated);
+
+    let follow_up = create_test_context("and what about her brother", "general", 0.3);
+    assert!(!memory.activate_fragments(&follow_up).contains(&sibling.id));
+
+    let mut primed = follow_up.clone();
+    working.prime(&mut primed);
+    assert!(memory.activate_fragments(&primed).contains(&sibling.id));
+}
+
+#[test]
+fn test_primed_fragment_wins_ranking() {
+    let mut memory = create_test_memory();
+    let a = create_entity_relation_fragment("server", "hosts", "alpha");
+    let b = create_entity_relation_fragment("server", "hosts", "beta");
+    memory.insert_fragment(a.clone(), Vec::new());
+    memory.insert_fragment(b.clone(), Vec::new());
+
+    let mut working = WorkingMemory::default();
+    for primed in [a.id, b.id] {
+        working.clear();
+        working.record_turn([primed]);
+        let mut context = create_test_context("server", "general", 0.3);
+        context.max_fragments = 1;
+        working.prime(&mut context);
+
+        let activated = memory.activate_fragments(&context);
+        assert!(activated.contains(&primed));
+    }
+}
+
+#[test]
+fn test_removed_fragments_leave_the_buffer() {
+    let mut memory = create_test_memory();
+    let kept = create_entity_relation_fragment("alice", "sibling_of", "bob");
+    let dropped = create_entity_relation_fragment("carol", "friend_of", "dave");
+    memory.insert_fragment(kept.clone(), Vec::new());
+    memory.insert_fragment(dropped.clone(), Vec::new());
+
+    let mut working = WorkingMemory::default();
+    working.record_turn([kept.id, dropped.id]);
+    memory.remove_fragment(dropped.id);
+    working.retain_present(&memory);
+    assert_eq!(working.primed(), vec![(kept.id, 0.5)]);
+
+    working.forget(kept.id);
+    assert!(working.primed().is_empty());
+    working.clear();
+    assert!(working.is_empty());
+}